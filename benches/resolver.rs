@@ -101,6 +101,28 @@ fn create_symlinks() -> io::Result<PathBuf> {
     Ok(temp_path)
 }
 
+/// Creates `node_modules/package{i}` at the root and unrelated packages in `packages/app/node_modules`,
+/// so resolving from `packages/app` misses every package in the nearest `node_modules`.
+fn create_node_modules() -> io::Result<PathBuf> {
+    let root = env::current_dir()?.join("fixtures/enhanced_resolve/test/temp_node_modules");
+    let create_node_modules_fixtures = || -> io::Result<()> {
+        for i in 0..1000 {
+            let package = root.join(format!("node_modules/package{i}"));
+            fs::create_dir_all(&package)?;
+            fs::File::create(package.join("index.js"))?;
+            fs::create_dir_all(root.join(format!("packages/app/node_modules/other{i}")))?;
+        }
+        Ok(())
+    };
+    if !root.exists() {
+        if let Err(err) = create_node_modules_fixtures() {
+            let _ = fs::remove_dir_all(&root);
+            return Err(err);
+        }
+    }
+    Ok(root.join("packages/app"))
+}
+
 fn oxc_resolver() -> oxc_resolver::Resolver {
     use oxc_resolver::{AliasValue, ResolveOptions, Resolver};
     let alias_value = AliasValue::from("./");
@@ -199,5 +221,153 @@ fn bench_resolver(c: &mut Criterion) {
     );
 }
 
-criterion_group!(resolver, bench_resolver);
+fn bench_tsconfig_paths(c: &mut Criterion) {
+    use oxc_resolver::{TsConfig, TsConfigSerde};
+
+    let path = Path::new("/project/tsconfig.json");
+    // Large monorepos generate a `paths` entry for every package.
+    let paths = (0..1000)
+        .map(|i| {
+            (
+                format!("@scope/package-{i}/*"),
+                serde_json::json!([format!("packages/package-{i}/src/*")]),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+    let mut tsconfig_json =
+        serde_json::json!({ "compilerOptions": { "paths": paths } }).to_string();
+    let tsconfig = TsConfigSerde::parse(true, path, &mut tsconfig_json.clone()).unwrap();
+    let mut built_tsconfig = TsConfigSerde::parse(true, path, &mut tsconfig_json).unwrap();
+    built_tsconfig.build();
+
+    let specifiers = ["@scope/package-0/index", "@scope/package-500/index", "unknown"];
+
+    let mut group = c.benchmark_group("tsconfig_paths");
+
+    group.bench_with_input(BenchmarkId::from_parameter("unbuilt"), &specifiers, |b, data| {
+        b.iter(|| {
+            for specifier in data {
+                _ = tsconfig.resolve_path_alias(specifier);
+            }
+        });
+    });
+
+    group.bench_with_input(BenchmarkId::from_parameter("built"), &specifiers, |b, data| {
+        b.iter(|| {
+            for specifier in data {
+                _ = built_tsconfig.resolve_path_alias(specifier);
+            }
+        });
+    });
+}
+
+fn bench_extensions_read_dir(c: &mut Criterion) {
+    use oxc_resolver::{ResolveOptions, Resolver};
+
+    let dir = env::current_dir().unwrap().join("fixtures/enhanced_resolve/test/fixtures");
+    // A misconfigured list where most extensions never match.
+    let mut extensions = (0..30).map(|i| format!(".ext{i}")).collect::<Vec<_>>();
+    extensions.push(".js".into());
+    let specifiers = ["./a", "./b", "./c", "./main1", "./main2", "./main3"];
+
+    let stat = Resolver::new(ResolveOptions {
+        extensions: extensions.clone(),
+        ..ResolveOptions::default()
+    });
+    let read_dir = Resolver::new(ResolveOptions {
+        extensions,
+        extensions_read_dir_threshold: Some(8),
+        ..ResolveOptions::default()
+    });
+
+    let mut group = c.benchmark_group("extensions_read_dir");
+
+    group.bench_with_input(BenchmarkId::from_parameter("stat"), &specifiers, |b, data| {
+        b.iter(|| {
+            stat.clear_cache();
+            for specifier in data {
+                assert!(stat.resolve(&dir, specifier).is_ok());
+            }
+        });
+    });
+
+    group.bench_with_input(BenchmarkId::from_parameter("read_dir"), &specifiers, |b, data| {
+        b.iter(|| {
+            read_dir.clear_cache();
+            for specifier in data {
+                assert!(read_dir.resolve(&dir, specifier).is_ok());
+            }
+        });
+    });
+}
+
+fn bench_lazy_symlinks(c: &mut Criterion) {
+    use oxc_resolver::{ResolveOptions, Resolver};
+
+    let symlink_test_dir = create_symlinks().expect("Create symlink fixtures failed");
+    let symlinks_range = 0u32..1000;
+
+    let eager = Resolver::default();
+    let lazy = Resolver::new(ResolveOptions { lazy_symlinks: true, ..ResolveOptions::default() });
+
+    let mut group = c.benchmark_group("lazy_symlinks");
+
+    group.bench_with_input(BenchmarkId::from_parameter("eager"), &symlinks_range, |b, data| {
+        b.iter(|| {
+            eager.clear_cache();
+            for i in data.clone() {
+                assert!(eager.resolve(&symlink_test_dir, &format!("./file{i}")).is_ok());
+            }
+        });
+    });
+
+    group.bench_with_input(BenchmarkId::from_parameter("lazy"), &symlinks_range, |b, data| {
+        b.iter(|| {
+            lazy.clear_cache();
+            for i in data.clone() {
+                assert!(lazy.resolve(&symlink_test_dir, &format!("./file{i}")).is_ok());
+            }
+        });
+    });
+}
+
+fn bench_node_modules_read_dir(c: &mut Criterion) {
+    use oxc_resolver::{ResolveOptions, Resolver};
+
+    let app = create_node_modules().expect("Create node_modules fixtures failed");
+    let packages_range = 0u32..1000;
+
+    let stat = Resolver::default();
+    let read_dir =
+        Resolver::new(ResolveOptions { node_modules_read_dir: true, ..ResolveOptions::default() });
+
+    let mut group = c.benchmark_group("node_modules_read_dir");
+
+    group.bench_with_input(BenchmarkId::from_parameter("stat"), &packages_range, |b, data| {
+        b.iter(|| {
+            stat.clear_cache();
+            for i in data.clone() {
+                assert!(stat.resolve(&app, &format!("package{i}")).is_ok());
+            }
+        });
+    });
+
+    group.bench_with_input(BenchmarkId::from_parameter("read_dir"), &packages_range, |b, data| {
+        b.iter(|| {
+            read_dir.clear_cache();
+            for i in data.clone() {
+                assert!(read_dir.resolve(&app, &format!("package{i}")).is_ok());
+            }
+        });
+    });
+}
+
+criterion_group!(
+    resolver,
+    bench_resolver,
+    bench_tsconfig_paths,
+    bench_extensions_read_dir,
+    bench_lazy_symlinks,
+    bench_node_modules_read_dir
+);
 criterion_main!(resolver);
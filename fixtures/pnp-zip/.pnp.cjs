@@ -0,0 +1,27 @@
+#!/usr/bin/env node
+/* eslint-disable */
+// A minimal Yarn PnP manifest for the zipped dependency in `cache`, only the runtime state is read by the resolver.
+"use strict";
+
+const RAW_RUNTIME_STATE =
+'{\
+  "__info": [],\
+  "dependencyTreeRoots": [\
+    {"name": "pnp-zip", "reference": "workspace:."}\
+  ],\
+  "enableTopLevelFallback": true,\
+  "ignorePatternData": null,\
+  "fallbackExclusionList": [],\
+  "fallbackPool": [],\
+  "packageRegistryData": [\
+    [null, [\
+      [null, {"packageLocation": "./", "packageDependencies": [["dep", "npm:1.0.0"]], "linkType": "SOFT"}]\
+    ]],\
+    ["dep", [\
+      ["npm:1.0.0", {"packageLocation": "./cache/dep-npm-1.0.0.zip/node_modules/dep/", "packageDependencies": [["dep", "npm:1.0.0"]], "linkType": "HARD"}]\
+    ]],\
+    ["pnp-zip", [\
+      ["workspace:.", {"packageLocation": "./", "packageDependencies": [["dep", "npm:1.0.0"], ["pnp-zip", "workspace:."]], "linkType": "SOFT"}]\
+    ]]\
+  ]\
+}';
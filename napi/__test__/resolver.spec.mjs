@@ -260,3 +260,42 @@ test('resolve pnpm package', (t) => {
     },
   );
 });
+
+test('sync many', (t) => {
+  const resolver = new ResolverFactory();
+  t.deepEqual(
+    resolver.syncMany(enhancedResolveRoot, ['./main1.js', 'm1/a', './missing']),
+    [
+      { path: join(enhancedResolveRoot, 'main1.js') },
+      { path: join(enhancedResolveRoot, 'node_modules/m1/a.js') },
+      {
+        error: "Cannot find module './missing'",
+        errorInfo: {
+          kind: 'NotFound',
+          specifier: './missing',
+          message: "Cannot find module './missing'",
+        },
+      },
+    ],
+  );
+  t.deepEqual(resolver.syncMany(enhancedResolveRoot, []), []);
+});
+
+test('sync with dependencies', (t) => {
+  const resolver = new ResolverFactory();
+  const result = resolver.syncWithDependencies(enhancedResolveRoot, './main1');
+  t.is(result.path, join(enhancedResolveRoot, 'main1.js'));
+  t.true(result.fileDependencies.includes(join(enhancedResolveRoot, 'main1.js')));
+  t.true(result.missingDependencies.includes(join(enhancedResolveRoot, 'main1')));
+  t.deepEqual(result.fileDependencies, [...result.fileDependencies].sort());
+});
+
+test('structured errors', (t) => {
+  const resolver = new ResolverFactory({ builtinModules: true });
+  t.is(resolver.sync(enhancedResolveRoot, './missing').errorInfo.kind, 'NotFound');
+  t.like(resolver.sync(enhancedResolveRoot, 'fs').errorInfo, {
+    kind: 'Builtin',
+    specifier: 'node:fs',
+  });
+  t.is(resolver.sync(enhancedResolveRoot, './main1.js').errorInfo, undefined);
+});
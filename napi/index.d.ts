@@ -9,10 +9,26 @@ export declare class ResolverFactory {
   clearCache(): void;
   /** Synchronously resolve `specifier` at an absolute path to a `directory`. */
   sync(directory: string, request: string): ResolveResult;
+  /**
+   * Synchronously resolve `specifier` at an absolute path to a `directory`,
+   * returning the files the resolution depends on for watching.
+   */
+  syncWithDependencies(directory: string, request: string): ResolveResultWithDependencies;
+  /**
+   * Synchronously resolve a batch of `requests` at an absolute path to a `directory` in one call.
+   *
+   * Results are in the same order as `requests`.
+   */
+  syncMany(directory: string, requests: Array<string>): Array<ResolveResult>;
   /** Asynchronously resolve `specifier` at an absolute path to a `directory`. */
   async(directory: string, request: string): Promise<ResolveResult>;
 }
 
+export declare const enum BuildMode {
+  Development = 0,
+  Production = 1,
+}
+
 export declare const enum EnforceExtension {
   Auto = 0,
   Enabled = 1,
@@ -54,6 +70,9 @@ export interface NapiResolveOptions {
    * Condition names for exports field which defines entry points of a package.
    * The key order in the exports field is significant. During condition matching, earlier entries have higher priority and take precedence over later entries.
    *
+   * A condition name containing a single `*` matches any key with the same prefix and suffix,
+   * e.g. `worker*` matches `worker` and `workerd`.
+   *
    * Default `[]`
    */
   conditionNames?: Array<string>;
@@ -183,13 +202,291 @@ export interface NapiResolveOptions {
    * Default `false`
    */
   builtinModules?: boolean;
+  /**
+   * Prefer files with [ResolveOptions::extensions] over an extensionless file of the same name.
+   *
+   * When enabled, `require('./foo')` resolves to `./foo.js` even when an extensionless `./foo` file exists.
+   *
+   * Default `false`
+   */
+  preferExtensions?: boolean;
+  /**
+   * Resolve the package root with the main fields instead of "exports" when both are defined.
+   *
+   * This is for legacy interop, where `require('pkg')` should load "main" even when the package defines "exports".
+   * Subpaths such as `pkg/foo` are still resolved with "exports".
+   *
+   * Default `false`
+   */
+  preferMainOverExports?: boolean;
+  /**
+   * A list of workspace package directories used for resolving `workspace:` protocol specifiers.
+   *
+   * `workspace:pkg/sub` resolves `./sub` in the workspace package whose package.json "name" is `pkg`.
+   *
+   * Default `[]`
+   */
+  workspaces?: Array<string>;
+  /**
+   * Skip a malformed package.json found while looking up the closest package scope of a file, instead of failing the resolution.
+   *
+   * The skipped package.json is reported as [crate::ResolveDiagnostic::InvalidPackageJsonSkipped].
+   * A malformed package.json of a package being resolved, e.g. `node_modules/foo/package.json` for `require('foo')`, is still an error.
+   *
+   * Default `false`
+   */
+  skipInvalidPackageJson?: boolean;
+  /**
+   * Apply [ResolveOptions::fully_specified] to relative and absolute specifiers only, matching Node.js ESM.
+   *
+   * `import './foo'` must be written as `import './foo.js'`,
+   * while bare specifiers such as `import 'pkg/feature'` are still resolved with extensions and main files.
+   *
+   * Default `false`
+   */
+  strictEsm?: boolean;
+  /**
+   * Check the resolved file against the "files" field of its `package.json`,
+   * erroring with [crate::ResolveError::NotPublished] when the file would not be published.
+   *
+   * Useful for catching imports into files that only exist in the source repository of a package.
+   * Only packages inside [ResolveOptions::modules] are checked, the project importing its own files is not.
+   *
+   * <https://docs.npmjs.com/cli/configuring-npm/package-json#files>
+   *
+   * Default `false`
+   */
+  honorFilesField?: boolean;
+  /**
+   * Use the `paths` of the nearest `tsconfig.json` from the importing directory when [ResolveOptions::tsconfig] is not set.
+   *
+   * Useful for monorepos where each package has its own `tsconfig.json`.
+   * Project references of the discovered tsconfig are loaded as with [TsconfigReferences::Auto].
+   *
+   * Default `false`
+   */
+  tsconfigAutoDiscovery?: boolean;
+  /**
+   * Prefer the index file matching the "type" of the closest `package.json` when resolving a directory.
+   *
+   * `index.mjs` is tried before [ResolveOptions::extensions] in a `"type": "module"` package,
+   * and `index.cjs` otherwise.
+   *
+   * <https://nodejs.org/api/packages.html#type>
+   *
+   * Default `false`
+   */
+  typeAwareIndex?: boolean;
+  /**
+   * List the directory once instead of checking every extension when [ResolveOptions::extensions] has at least this many entries.
+   *
+   * Only candidates present in the directory listing are checked, trading one `read_dir` for a metadata call per extension.
+   * Not applied when [ResolveOptions::alias_fields] is set, because aliased files are not required to exist.
+   *
+   * Default `None`
+   */
+  extensionsReadDirThreshold?: number;
+  /**
+   * Add the `development` or `production` condition to [ResolveOptions::condition_names].
+   *
+   * The condition is only added when it is not already listed.
+   * As with any condition, the key order in the exports field decides its priority, so it is matched before a later `default` key.
+   *
+   * Default `None`
+   */
+  mode?: BuildMode;
+  /**
+   * Defer resolving symlinks to the final resolved path when [ResolveOptions::symlinks] is enabled.
+   *
+   * By default the directory of every `package.json` read during resolution is canonicalized.
+   * When enabled, `package.json` files keep the path they were found at,
+   * and only the resolved path is canonicalized before looking up its package scope.
+   *
+   * Default `false`
+   */
+  lazySymlinks?: boolean;
+  /**
+   * Allow the deprecated folder mapping of keys ending with `/` in "exports" and "imports", e.g. `"./": "./dist/"`.
+   *
+   * Node.js removed folder mappings in favor of subpath patterns (`"./*": "./dist/*"`), webpack still supports them.
+   * When disabled, a specifier matching a folder mapping fails with [crate::ResolveError::FolderMappingDisabled].
+   *
+   * <https://nodejs.org/api/deprecations.html#DEP0148>
+   *
+   * Default `true`
+   */
+  folderMapping?: boolean;
+  /**
+   * Replace [crate::NODEJS_BUILTINS] with another list of builtin modules for [ResolveOptions::builtin_modules],
+   * e.g. for a specific Node.js version or a runtime such as Bun or Deno.
+   *
+   * Specifiers with the `node:` prefix are always builtin.
+   * Only [crate::NODEJS_BUILTINS] are resolved with the `node:` prefix, other entries are resolved verbatim.
+   *
+   * Default `None`
+   */
+  builtinModulesList?: Array<string>;
+  /**
+   * An absolute path to a flat dependency directory, e.g. a vendored dependencies folder.
+   *
+   * Bare specifiers are resolved directly inside this directory without walking the ancestor directories for [ResolveOptions::modules].
+   * Set [ResolveOptions::flat_modules_fallback] to walk [ResolveOptions::modules] when the package is not found in it.
+   *
+   * Default `None`
+   */
+  flatModules?: string;
+  /**
+   * Walk [ResolveOptions::modules] when a bare specifier is not found in [ResolveOptions::flat_modules].
+   *
+   * Default `false`
+   */
+  flatModulesFallback?: boolean;
+  /**
+   * Resolve targets of "exports" and "imports" strictly as Node.js does, e.g. `"./feature": "./dist/feature"`.
+   *
+   * When enabled, a target must be an existing file, [ResolveOptions::extensions] are not tried and directories are not loaded.
+   * enhanced-resolve is lenient and completes the target like a relative request.
+   *
+   * Default `false`
+   */
+  strictExportsTargets?: boolean;
+}
+
+/** Structured [ResolveError] for branching on `kind` without parsing `message`. */
+export interface ResolveErrorInfo {
+  /** Name of the [ResolveError] variant, e.g. "NotFound" or "PackagePathNotExported" */
+  kind: string;
+  /** The specifier being resolved, when the error has one */
+  specifier?: string;
+  /** The file or package.json the error refers to, when the error has one */
+  path?: string;
+  /** Same as `ResolveResult.error` */
+  message: string;
+  /**
+   * List each `node_modules` directory once and look up packages in the listing instead of checking each package directory.
+   *
+   * Trades one `read_dir` per `node_modules` for a metadata call per missing package,
+   * which pays off for deep hierarchies of `node_modules` with many packages.
+   * Lookups are case-sensitive even on case-insensitive file systems.
+   *
+   * Default `false`
+   */
+  nodeModulesReadDir?: boolean;
+  /**
+   * Check the "engines.node" range in the `package.json` of the resolved file against this Node.js version, e.g. `"18.19.0"`,
+   * erroring with [crate::ResolveError::IncompatibleEngine] when the version does not satisfy the range.
+   *
+   * Ranges that cannot be parsed are ignored.
+   *
+   * <https://docs.npmjs.com/cli/configuring-npm/package-json#engines>
+   *
+   * Default `None`
+   */
+  targetNodeVersion?: string;
+  /**
+   * Infer [crate::Resolution::module_type] from the "import" or "require" condition that selected the file in "exports" or "imports".
+   *
+   * This is more accurate than the "type" field for `.js` files of dual packages.
+   * Explicit `.mjs` / `.cjs` extensions still take precedence.
+   *
+   * Default `false`
+   */
+  moduleTypeFromConditions?: boolean;
+  /**
+   * Extensions attempted in order as a last resort, when nothing resolves with [ResolveOptions::extensions],
+   * e.g. `[".vue", ".coffee"]` while keeping the common case fast.
+   *
+   * The specifier is resolved again with these extensions in place of [ResolveOptions::extensions]
+   * when the first attempt fails with [crate::ResolveError::NotFound].
+   *
+   * All extensions must have a leading dot.
+   *
+   * Default `[]`
+   */
+  fallbackExtensions?: Array<string>;
+  /**
+   * Fall back to [ResolveOptions::main_fields] and loading files directly when a package subpath is not exported,
+   * for interop with bundlers resolving packages as before "exports" existed.
+   *
+   * Node.js ignores "main" and fails with [crate::ResolveError::PackagePathNotExported] when the package has "exports".
+   * The package root also falls back when none of its conditions match, or when its "exports" target does not exist on disk,
+   * reported as [crate::ResolveDiagnostic::ExportsTargetNotFound].
+   * Only packages found in [ResolveOptions::modules] fall back, other errors from "exports" are returned as is.
+   *
+   * Default `false`
+   */
+  exportsFallbackToMain?: boolean;
+  /**
+   * Only resolve packages found in [ResolveOptions::modules] through their "exports" field.
+   *
+   * When the package has a package.json, "main", [ResolveOptions::main_fields], the index file and subpaths without a matching export
+   * fail with [crate::ResolveError::PackagePathNotExported] instead of being loaded from the package directory.
+   * Packages without a package.json are not affected. Takes precedence over [ResolveOptions::exports_fallback_to_main].
+   *
+   * Default `false`
+   */
+  exportsOnly?: boolean;
+  /**
+   * Remove leading and trailing whitespace from the specifier before resolving, e.g. `" ./x "` resolves as `"./x"`.
+   *
+   * Whitespace is significant by default, as in Node.js. Whitespace inside the specifier is always kept.
+   *
+   * Default `false`
+   */
+  trimSpecifierWhitespace?: boolean;
+  /**
+   * Force bare specifiers of a package to resolve from a fixed package directory,
+   * like npm's "overrides" and yarn's "resolutions" fields.
+   *
+   * A pattern is a package name such as `"lodash"`, redirecting imports from every package,
+   * or `"parent>name"`, only redirecting imports from files in the `parent` package.
+   * Patterns with a parent take precedence, [ResolveOptions::modules] are not searched for overridden packages.
+   * The package directory is resolved as a package found in `node_modules`, including its "exports" field.
+   *
+   * Default `[]`
+   */
+  overrides?: Record<string, string>;
+  /**
+   * Resolve an extensionless specifier naming an existing directory only as that directory,
+   * as if it ended with `/`, e.g. `./dir` resolves to `./dir/index.js` and never to `./dir.js`.
+   *
+   * By default a file is tried before the directory, as in Node.js.
+   *
+   * Default `false`
+   */
+  directoryIndexOnly?: boolean;
+  /**
+   * Prefer the ESM build of packages without "exports" by prepending "module" to [ResolveOptions::main_fields].
+   *
+   * Has no effect when [ResolveOptions::main_fields] already contains "module", its position is kept.
+   *
+   * Default `false`
+   */
+  preferEsm?: boolean;
 }
 
 export interface ResolveResult {
   path?: string;
   error?: string;
-  /** "type" field in the package.json file */
+  /** Structured `error` for branching on its kind */
+  errorInfo?: ResolveErrorInfo;
+  /** Module type of the resolved file, "module" or "commonjs" */
+  moduleType?: string;
+}
+
+export interface ResolveResultWithDependencies {
+  path?: string;
+  error?: string;
+  /** Structured `error` for branching on its kind */
+  errorInfo?: ResolveErrorInfo;
+  /** Module type of the resolved file, "module" or "commonjs" */
   moduleType?: string;
+  /** Files that were found on the file system, sorted */
+  fileDependencies: Array<string>;
+  /** Files that were not found on the file system, sorted */
+  missingDependencies: Array<string>;
+  /** Directories that were found on the file system, sorted */
+  contextDependencies: Array<string>;
 }
 
 /**
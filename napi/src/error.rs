@@ -0,0 +1,94 @@
+use std::path::Path;
+
+use napi_derive::napi;
+use oxc_resolver::ResolveError;
+
+/// Structured [ResolveError] for branching on `kind` without parsing `message`.
+#[napi(object)]
+pub struct ResolveErrorInfo {
+    /// Name of the [ResolveError] variant, e.g. "NotFound" or "PackagePathNotExported"
+    pub kind: String,
+    /// The specifier being resolved, when the error has one
+    pub specifier: Option<String>,
+    /// The file or package.json the error refers to, when the error has one
+    pub path: Option<String>,
+    /// Same as `ResolveResult.error`
+    pub message: String,
+}
+
+impl From<&ResolveError> for ResolveErrorInfo {
+    fn from(error: &ResolveError) -> Self {
+        let (kind, specifier, path) = match error {
+            ResolveError::Ignored(path) => ("Ignored", None, Some(path.as_path())),
+            ResolveError::NotFound(specifier) => ("NotFound", Some(specifier), None),
+            ResolveError::WorkspacesNotConfigured(specifier) => {
+                ("WorkspacesNotConfigured", Some(specifier), None)
+            }
+            ResolveError::NotFoundInAny(specifier, _) => ("NotFoundInAny", Some(specifier), None),
+            ResolveError::MatchedAliasNotFound(specifier, _) => {
+                ("MatchedAliasNotFound", Some(specifier), None)
+            }
+            ResolveError::TsconfigNotFound(path) => {
+                ("TsconfigNotFound", None, Some(path.as_path()))
+            }
+            ResolveError::TooManySymlinks(path) => ("TooManySymlinks", None, Some(path.as_path())),
+            ResolveError::TsconfigSelfReference(path) => {
+                ("TsconfigSelfReference", None, Some(path.as_path()))
+            }
+            ResolveError::IOError(_) => ("IOError", None, None),
+            ResolveError::Builtin { resolved, .. } => ("Builtin", Some(resolved), None),
+            ResolveError::ExtensionAlias(specifier, _, path) => {
+                ("ExtensionAlias", Some(specifier), Some(path.as_path()))
+            }
+            ResolveError::Specifier(_) => ("Specifier", None, None),
+            ResolveError::JSON(error) => ("JSON", None, Some(error.path.as_path())),
+            ResolveError::Aborted => ("Aborted", None, None),
+            ResolveError::FolderMappingDisabled(_, specifier, path) => {
+                ("FolderMappingDisabled", Some(specifier), Some(path.as_path()))
+            }
+            ResolveError::Rejected(path, _) => ("Rejected", None, Some(path.as_path())),
+            ResolveError::SelfReferenceCycle(specifier, path) => {
+                ("SelfReferenceCycle", Some(specifier), Some(path.as_path()))
+            }
+            ResolveError::NotPublished { file, .. } => ("NotPublished", None, Some(file.as_path())),
+            ResolveError::Restriction(path, _) => ("Restriction", None, Some(path.as_path())),
+            ResolveError::InvalidRestriction(..) => ("InvalidRestriction", None, None),
+            ResolveError::InvalidModuleSpecifier(specifier, path) => {
+                ("InvalidModuleSpecifier", Some(specifier), Some(path.as_path()))
+            }
+            ResolveError::InvalidPackageTarget(_, specifier, path) => {
+                ("InvalidPackageTarget", Some(specifier), Some(path.as_path()))
+            }
+            ResolveError::PackagePathNotExported(specifier, path) => {
+                ("PackagePathNotExported", Some(specifier), Some(path.as_path()))
+            }
+            ResolveError::PackageConditionNotMatched { package, .. } => {
+                ("PackageConditionNotMatched", None, Some(package.as_path()))
+            }
+            ResolveError::IncompatibleEngine { package, .. } => {
+                ("IncompatibleEngine", None, Some(package.as_path()))
+            }
+            ResolveError::InvalidPackageConfig(path) => {
+                ("InvalidPackageConfig", None, Some(path.as_path()))
+            }
+            ResolveError::InvalidPackageConfigDefault(path) => {
+                ("InvalidPackageConfigDefault", None, Some(path.as_path()))
+            }
+            ResolveError::InvalidPackageConfigDirectory(path) => {
+                ("InvalidPackageConfigDirectory", None, Some(path.as_path()))
+            }
+            ResolveError::PackageImportNotDefined(specifier, path) => {
+                ("PackageImportNotDefined", Some(specifier), Some(path.as_path()))
+            }
+            ResolveError::Unimplemented(_) => ("Unimplemented", None, None),
+            ResolveError::Recursion => ("Recursion", None, None),
+            _ => ("Unknown", None, None),
+        };
+        Self {
+            kind: kind.to_string(),
+            specifier: specifier.cloned(),
+            path: path.map(Path::to_string_lossy).map(|path| path.to_string()),
+            message: error.to_string(),
+        }
+    }
+}
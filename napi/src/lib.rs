@@ -9,13 +9,15 @@ use std::{
 
 use napi::{bindgen_prelude::AsyncTask, Task};
 use napi_derive::napi;
-use oxc_resolver::{PackageJson, ResolveOptions, Resolver};
+use oxc_resolver::{ResolveContext, ResolveOptions, Resolver};
 
 use self::{
+    error::ResolveErrorInfo,
     options::{NapiResolveOptions, StrOrStrList},
     tracing::init_tracing,
 };
 
+mod error;
 mod options;
 mod tracing;
 
@@ -23,21 +25,76 @@ mod tracing;
 pub struct ResolveResult {
     pub path: Option<String>,
     pub error: Option<String>,
-    /// "type" field in the package.json file
+    /// Structured `error` for branching on its kind
+    pub error_info: Option<ResolveErrorInfo>,
+    /// Module type of the resolved file, "module" or "commonjs"
     pub module_type: Option<String>,
 }
 
+#[napi(object)]
+pub struct ResolveResultWithDependencies {
+    pub path: Option<String>,
+    pub error: Option<String>,
+    /// Structured `error` for branching on its kind
+    pub error_info: Option<ResolveErrorInfo>,
+    /// Module type of the resolved file, "module" or "commonjs"
+    pub module_type: Option<String>,
+    /// Files that were found on the file system, sorted
+    pub file_dependencies: Vec<String>,
+    /// Files that were not found on the file system, sorted
+    pub missing_dependencies: Vec<String>,
+    /// Directories that were found on the file system, sorted
+    pub context_dependencies: Vec<String>,
+}
+
 fn resolve(resolver: &Resolver, path: &Path, request: &str) -> ResolveResult {
     match resolver.resolve(path, request) {
         Ok(resolution) => ResolveResult {
             path: Some(resolution.full_path().to_string_lossy().to_string()),
             error: None,
-            module_type: resolution.package_json().and_then(|p| p.r#type()).map(|t| t.to_string()),
+            error_info: None,
+            module_type: resolution.module_type().map(|t| t.to_string()),
         },
-        Err(err) => ResolveResult { path: None, module_type: None, error: Some(err.to_string()) },
+        Err(err) => ResolveResult {
+            path: None,
+            module_type: None,
+            error: Some(err.to_string()),
+            error_info: Some((&err).into()),
+        },
+    }
+}
+
+fn resolve_with_dependencies(
+    resolver: &Resolver,
+    path: &Path,
+    request: &str,
+) -> ResolveResultWithDependencies {
+    let mut ctx = ResolveContext::default();
+    let (path, error, module_type) = match resolver.resolve_with_context(path, request, &mut ctx) {
+        Ok(resolution) => (
+            Some(resolution.full_path().to_string_lossy().to_string()),
+            None,
+            resolution.module_type().map(|t| t.to_string()),
+        ),
+        Err(err) => (None, Some(err), None),
+    };
+    ResolveResultWithDependencies {
+        path,
+        error: error.as_ref().map(ToString::to_string),
+        error_info: error.as_ref().map(Into::into),
+        module_type,
+        file_dependencies: sorted_paths(ctx.file_dependencies),
+        missing_dependencies: sorted_paths(ctx.missing_dependencies),
+        context_dependencies: sorted_paths(ctx.context_dependencies),
     }
 }
 
+fn sorted_paths<I: IntoIterator<Item = PathBuf>>(paths: I) -> Vec<String> {
+    let mut paths = paths.into_iter().map(|p| p.to_string_lossy().to_string()).collect::<Vec<_>>();
+    paths.sort_unstable();
+    paths
+}
+
 #[allow(clippy::needless_pass_by_value)]
 #[napi]
 pub fn sync(path: String, request: String) -> ResolveResult {
@@ -108,6 +165,29 @@ impl ResolverFactory {
         resolve(&self.resolver, &path, &request)
     }
 
+    /// Synchronously resolve `specifier` at an absolute path to a `directory`,
+    /// returning the files the resolution depends on for watching.
+    #[allow(clippy::needless_pass_by_value)]
+    #[napi]
+    pub fn sync_with_dependencies(
+        &self,
+        directory: String,
+        request: String,
+    ) -> ResolveResultWithDependencies {
+        let path = PathBuf::from(directory);
+        resolve_with_dependencies(&self.resolver, &path, &request)
+    }
+
+    /// Synchronously resolve a batch of `requests` at an absolute path to a `directory` in one call.
+    ///
+    /// Results are in the same order as `requests`.
+    #[allow(clippy::needless_pass_by_value)]
+    #[napi]
+    pub fn sync_many(&self, directory: String, requests: Vec<String>) -> Vec<ResolveResult> {
+        let path = PathBuf::from(directory);
+        requests.iter().map(|request| resolve(&self.resolver, &path, request)).collect()
+    }
+
     /// Asynchronously resolve `specifier` at an absolute path to a `directory`.
     #[allow(clippy::needless_pass_by_value)]
     #[napi(js_name = "async")]
@@ -206,6 +286,69 @@ impl ResolverFactory {
                 .unwrap_or(default.roots),
             symlinks: op.symlinks.unwrap_or(default.symlinks),
             builtin_modules: op.builtin_modules.unwrap_or(default.builtin_modules),
+            typescript: default.typescript,
+            prefer_extensions: op.prefer_extensions.unwrap_or(default.prefer_extensions),
+            prefer_main_over_exports: op
+                .prefer_main_over_exports
+                .unwrap_or(default.prefer_main_over_exports),
+            workspaces: op
+                .workspaces
+                .map(|workspaces| workspaces.into_iter().map(PathBuf::from).collect::<Vec<_>>())
+                .unwrap_or(default.workspaces),
+            skip_invalid_package_json: op
+                .skip_invalid_package_json
+                .unwrap_or(default.skip_invalid_package_json),
+            strict_esm: op.strict_esm.unwrap_or(default.strict_esm),
+            honor_files_field: op.honor_files_field.unwrap_or(default.honor_files_field),
+            tsconfig_auto_discovery: op
+                .tsconfig_auto_discovery
+                .unwrap_or(default.tsconfig_auto_discovery),
+            type_aware_index: op.type_aware_index.unwrap_or(default.type_aware_index),
+            post_resolve: default.post_resolve,
+            extensions_read_dir_threshold: op
+                .extensions_read_dir_threshold
+                .map(|n| n as usize)
+                .or(default.extensions_read_dir_threshold),
+            abort_signal: default.abort_signal,
+            mode: op.mode.map(|mode| mode.into()).or(default.mode),
+            lazy_symlinks: op.lazy_symlinks.unwrap_or(default.lazy_symlinks),
+            folder_mapping: op.folder_mapping.unwrap_or(default.folder_mapping),
+            builtin_modules_list: op.builtin_modules_list.or(default.builtin_modules_list),
+            flat_modules: op.flat_modules.map(PathBuf::from).or(default.flat_modules),
+            flat_modules_fallback: op
+                .flat_modules_fallback
+                .unwrap_or(default.flat_modules_fallback),
+            symlinks_filter: default.symlinks_filter,
+            strict_exports_targets: op
+                .strict_exports_targets
+                .unwrap_or(default.strict_exports_targets),
+            node_modules_read_dir: op
+                .node_modules_read_dir
+                .unwrap_or(default.node_modules_read_dir),
+            target_node_version: op.target_node_version,
+            module_type_from_conditions: op
+                .module_type_from_conditions
+                .unwrap_or(default.module_type_from_conditions),
+            fallback_extensions: op.fallback_extensions.unwrap_or(default.fallback_extensions),
+            entry_selector: default.entry_selector,
+            exports_fallback_to_main: op
+                .exports_fallback_to_main
+                .unwrap_or(default.exports_fallback_to_main),
+            exports_only: op.exports_only.unwrap_or(default.exports_only),
+            trim_specifier_whitespace: op
+                .trim_specifier_whitespace
+                .unwrap_or(default.trim_specifier_whitespace),
+            overrides: op
+                .overrides
+                .map(|overrides| {
+                    overrides
+                        .into_iter()
+                        .map(|(pattern, path)| (pattern, PathBuf::from(path)))
+                        .collect()
+                })
+                .unwrap_or(default.overrides),
+            directory_index_only: op.directory_index_only.unwrap_or(default.directory_index_only),
+            prefer_esm: op.prefer_esm.unwrap_or(default.prefer_esm),
         }
     }
 }
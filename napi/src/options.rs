@@ -35,6 +35,9 @@ pub struct NapiResolveOptions {
     /// Condition names for exports field which defines entry points of a package.
     /// The key order in the exports field is significant. During condition matching, earlier entries have higher priority and take precedence over later entries.
     ///
+    /// A condition name containing a single `*` matches any key with the same prefix and suffix,
+    /// e.g. `worker*` matches `worker` and `workerd`.
+    ///
     /// Default `[]`
     pub condition_names: Option<Vec<String>>,
 
@@ -149,6 +152,229 @@ pub struct NapiResolveOptions {
     ///
     /// Default `false`
     pub builtin_modules: Option<bool>,
+
+    /// Prefer files with [ResolveOptions::extensions] over an extensionless file of the same name.
+    ///
+    /// When enabled, `require('./foo')` resolves to `./foo.js` even when an extensionless `./foo` file exists.
+    ///
+    /// Default `false`
+    pub prefer_extensions: Option<bool>,
+
+    /// Resolve the package root with the main fields instead of "exports" when both are defined.
+    ///
+    /// This is for legacy interop, where `require('pkg')` should load "main" even when the package defines "exports".
+    /// Subpaths such as `pkg/foo` are still resolved with "exports".
+    ///
+    /// Default `false`
+    pub prefer_main_over_exports: Option<bool>,
+
+    /// A list of workspace package directories used for resolving `workspace:` protocol specifiers.
+    ///
+    /// `workspace:pkg/sub` resolves `./sub` in the workspace package whose package.json "name" is `pkg`.
+    ///
+    /// Default `[]`
+    pub workspaces: Option<Vec<String>>,
+
+    /// Skip a malformed package.json found while looking up the closest package scope of a file, instead of failing the resolution.
+    ///
+    /// The skipped package.json is reported as [crate::ResolveDiagnostic::InvalidPackageJsonSkipped].
+    /// A malformed package.json of a package being resolved, e.g. `node_modules/foo/package.json` for `require('foo')`, is still an error.
+    ///
+    /// Default `false`
+    pub skip_invalid_package_json: Option<bool>,
+
+    /// Apply [ResolveOptions::fully_specified] to relative and absolute specifiers only, matching Node.js ESM.
+    ///
+    /// `import './foo'` must be written as `import './foo.js'`,
+    /// while bare specifiers such as `import 'pkg/feature'` are still resolved with extensions and main files.
+    ///
+    /// Default `false`
+    pub strict_esm: Option<bool>,
+
+    /// Check the resolved file against the "files" field of its `package.json`,
+    /// erroring with [crate::ResolveError::NotPublished] when the file would not be published.
+    ///
+    /// Useful for catching imports into files that only exist in the source repository of a package.
+    /// Only packages inside [ResolveOptions::modules] are checked, the project importing its own files is not.
+    ///
+    /// <https://docs.npmjs.com/cli/configuring-npm/package-json#files>
+    ///
+    /// Default `false`
+    pub honor_files_field: Option<bool>,
+
+    /// Use the `paths` of the nearest `tsconfig.json` from the importing directory when [ResolveOptions::tsconfig] is not set.
+    ///
+    /// Useful for monorepos where each package has its own `tsconfig.json`.
+    /// Project references of the discovered tsconfig are loaded as with [TsconfigReferences::Auto].
+    ///
+    /// Default `false`
+    pub tsconfig_auto_discovery: Option<bool>,
+
+    /// Prefer the index file matching the "type" of the closest `package.json` when resolving a directory.
+    ///
+    /// `index.mjs` is tried before [ResolveOptions::extensions] in a `"type": "module"` package,
+    /// and `index.cjs` otherwise.
+    ///
+    /// <https://nodejs.org/api/packages.html#type>
+    ///
+    /// Default `false`
+    pub type_aware_index: Option<bool>,
+
+    /// List the directory once instead of checking every extension when [ResolveOptions::extensions] has at least this many entries.
+    ///
+    /// Only candidates present in the directory listing are checked, trading one `read_dir` for a metadata call per extension.
+    /// Not applied when [ResolveOptions::alias_fields] is set, because aliased files are not required to exist.
+    ///
+    /// Default `None`
+    pub extensions_read_dir_threshold: Option<u32>,
+
+    /// Add the `development` or `production` condition to [ResolveOptions::condition_names].
+    ///
+    /// The condition is only added when it is not already listed.
+    /// As with any condition, the key order in the exports field decides its priority, so it is matched before a later `default` key.
+    ///
+    /// Default `None`
+    pub mode: Option<BuildMode>,
+
+    /// Defer resolving symlinks to the final resolved path when [ResolveOptions::symlinks] is enabled.
+    ///
+    /// By default the directory of every `package.json` read during resolution is canonicalized.
+    /// When enabled, `package.json` files keep the path they were found at,
+    /// and only the resolved path is canonicalized before looking up its package scope.
+    ///
+    /// Default `false`
+    pub lazy_symlinks: Option<bool>,
+
+    /// Allow the deprecated folder mapping of keys ending with `/` in "exports" and "imports", e.g. `"./": "./dist/"`.
+    ///
+    /// Node.js removed folder mappings in favor of subpath patterns (`"./*": "./dist/*"`), webpack still supports them.
+    /// When disabled, a specifier matching a folder mapping fails with [crate::ResolveError::FolderMappingDisabled].
+    ///
+    /// <https://nodejs.org/api/deprecations.html#DEP0148>
+    ///
+    /// Default `true`
+    pub folder_mapping: Option<bool>,
+
+    /// Replace [crate::NODEJS_BUILTINS] with another list of builtin modules for [ResolveOptions::builtin_modules],
+    /// e.g. for a specific Node.js version or a runtime such as Bun or Deno.
+    ///
+    /// Specifiers with the `node:` prefix are always builtin.
+    /// Only [crate::NODEJS_BUILTINS] are resolved with the `node:` prefix, other entries are resolved verbatim.
+    ///
+    /// Default `None`
+    pub builtin_modules_list: Option<Vec<String>>,
+
+    /// An absolute path to a flat dependency directory, e.g. a vendored dependencies folder.
+    ///
+    /// Bare specifiers are resolved directly inside this directory without walking the ancestor directories for [ResolveOptions::modules].
+    /// Set [ResolveOptions::flat_modules_fallback] to walk [ResolveOptions::modules] when the package is not found in it.
+    ///
+    /// Default `None`
+    pub flat_modules: Option<String>,
+
+    /// Walk [ResolveOptions::modules] when a bare specifier is not found in [ResolveOptions::flat_modules].
+    ///
+    /// Default `false`
+    pub flat_modules_fallback: Option<bool>,
+
+    /// Resolve targets of "exports" and "imports" strictly as Node.js does, e.g. `"./feature": "./dist/feature"`.
+    ///
+    /// When enabled, a target must be an existing file, [ResolveOptions::extensions] are not tried and directories are not loaded.
+    /// enhanced-resolve is lenient and completes the target like a relative request.
+    ///
+    /// Default `false`
+    pub strict_exports_targets: Option<bool>,
+
+    /// List each `node_modules` directory once and look up packages in the listing instead of checking each package directory.
+    ///
+    /// Trades one `read_dir` per `node_modules` for a metadata call per missing package,
+    /// which pays off for deep hierarchies of `node_modules` with many packages.
+    /// Lookups are case-sensitive even on case-insensitive file systems.
+    ///
+    /// Default `false`
+    pub node_modules_read_dir: Option<bool>,
+
+    /// Check the "engines.node" range in the `package.json` of the resolved file against this Node.js version, e.g. `"18.19.0"`,
+    /// erroring with [crate::ResolveError::IncompatibleEngine] when the version does not satisfy the range.
+    ///
+    /// Ranges that cannot be parsed are ignored.
+    ///
+    /// <https://docs.npmjs.com/cli/configuring-npm/package-json#engines>
+    ///
+    /// Default `None`
+    pub target_node_version: Option<String>,
+
+    /// Infer [crate::Resolution::module_type] from the "import" or "require" condition that selected the file in "exports" or "imports".
+    ///
+    /// This is more accurate than the "type" field for `.js` files of dual packages.
+    /// Explicit `.mjs` / `.cjs` extensions still take precedence.
+    ///
+    /// Default `false`
+    pub module_type_from_conditions: Option<bool>,
+
+    /// Extensions attempted in order as a last resort, when nothing resolves with [ResolveOptions::extensions],
+    /// e.g. `[".vue", ".coffee"]` while keeping the common case fast.
+    ///
+    /// The specifier is resolved again with these extensions in place of [ResolveOptions::extensions]
+    /// when the first attempt fails with [crate::ResolveError::NotFound].
+    ///
+    /// All extensions must have a leading dot.
+    ///
+    /// Default `[]`
+    pub fallback_extensions: Option<Vec<String>>,
+
+    /// Fall back to [ResolveOptions::main_fields] and loading files directly when a package subpath is not exported,
+    /// for interop with bundlers resolving packages as before "exports" existed.
+    ///
+    /// Node.js ignores "main" and fails with [crate::ResolveError::PackagePathNotExported] when the package has "exports".
+    /// The package root also falls back when none of its conditions match, or when its "exports" target does not exist on disk,
+    /// reported as [crate::ResolveDiagnostic::ExportsTargetNotFound].
+    /// Only packages found in [ResolveOptions::modules] fall back, other errors from "exports" are returned as is.
+    ///
+    /// Default `false`
+    pub exports_fallback_to_main: Option<bool>,
+
+    /// Only resolve packages found in [ResolveOptions::modules] through their "exports" field.
+    ///
+    /// When the package has a package.json, "main", [ResolveOptions::main_fields], the index file and subpaths without a matching export
+    /// fail with [crate::ResolveError::PackagePathNotExported] instead of being loaded from the package directory.
+    /// Packages without a package.json are not affected. Takes precedence over [ResolveOptions::exports_fallback_to_main].
+    ///
+    /// Default `false`
+    pub exports_only: Option<bool>,
+
+    /// Remove leading and trailing whitespace from the specifier before resolving, e.g. `" ./x "` resolves as `"./x"`.
+    ///
+    /// Whitespace is significant by default, as in Node.js. Whitespace inside the specifier is always kept.
+    ///
+    /// Default `false`
+    pub trim_specifier_whitespace: Option<bool>,
+
+    /// Force bare specifiers of a package to resolve from a fixed package directory,
+    /// like npm's "overrides" and yarn's "resolutions" fields.
+    ///
+    /// A pattern is a package name such as `"lodash"`, redirecting imports from every package,
+    /// or `"parent>name"`, only redirecting imports from files in the `parent` package.
+    /// Patterns with a parent take precedence, [ResolveOptions::modules] are not searched for overridden packages.
+    /// The package directory is resolved as a package found in `node_modules`, including its "exports" field.
+    ///
+    /// Default `[]`
+    pub overrides: Option<HashMap<String, String>>,
+
+    /// Resolve an extensionless specifier naming an existing directory only as that directory,
+    /// as if it ended with `/`, e.g. `./dir` resolves to `./dir/index.js` and never to `./dir.js`.
+    ///
+    /// By default a file is tried before the directory, as in Node.js.
+    ///
+    /// Default `false`
+    pub directory_index_only: Option<bool>,
+
+    /// Prefer the ESM build of packages without "exports" by prepending "module" to [ResolveOptions::main_fields].
+    ///
+    /// Has no effect when [ResolveOptions::main_fields] already contains "module", its position is kept.
+    ///
+    /// Default `false`
+    pub prefer_esm: Option<bool>,
 }
 
 #[napi]
@@ -173,6 +399,13 @@ impl EnforceExtension {
     }
 }
 
+#[napi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildMode {
+    Development,
+    Production,
+}
+
 /// Alias Value for [ResolveOptions::alias] and [ResolveOptions::fallback].
 /// Use struct because napi don't support structured union now
 #[napi(object)]
@@ -227,6 +460,15 @@ impl Into<oxc_resolver::EnforceExtension> for EnforceExtension {
     }
 }
 
+impl Into<oxc_resolver::BuildMode> for BuildMode {
+    fn into(self) -> oxc_resolver::BuildMode {
+        match self {
+            BuildMode::Development => oxc_resolver::BuildMode::Development,
+            BuildMode::Production => oxc_resolver::BuildMode::Production,
+        }
+    }
+}
+
 impl Into<oxc_resolver::TsconfigOptions> for TsconfigOptions {
     fn into(self) -> oxc_resolver::TsconfigOptions {
         oxc_resolver::TsconfigOptions {
@@ -56,4 +56,59 @@ describe('option', () => {
     it('should allow field string', createTest('main'));
     it('should allow field array', createTest(['main']));
   });
+
+  describe('moduleTypeFromConditions', () => {
+    const conditionsDir = new URL(
+      '../../fixtures/misc/module-type-conditions',
+      import.meta.url,
+    ).pathname;
+    it('should report the module type of the matched condition', () => {
+      const resolver = new ResolverFactory({
+        conditionNames: ['require'],
+        moduleTypeFromConditions: true,
+      });
+      assert.strictEqual(
+        resolver.sync(conditionsDir, 'dual-module').moduleType,
+        'commonjs',
+      );
+      assert.strictEqual(
+        resolver.syncWithDependencies(conditionsDir, 'dual-module').moduleType,
+        'commonjs',
+      );
+    });
+    it('should use the "type" field by default', () => {
+      const resolver = new ResolverFactory({ conditionNames: ['require'] });
+      assert.strictEqual(
+        resolver.sync(conditionsDir, 'dual-module').moduleType,
+        'module',
+      );
+    });
+    it('should prefer explicit extensions', () => {
+      const moduleTypeDir = new URL(
+        '../../fixtures/misc/module-type/commonjs',
+        import.meta.url,
+      ).pathname;
+      const resolver = new ResolverFactory({});
+      assert.strictEqual(
+        resolver.sync(moduleTypeDir, './index.mjs').moduleType,
+        'module',
+      );
+      assert.strictEqual(
+        resolver.syncWithDependencies(moduleTypeDir, './index.mjs').moduleType,
+        'module',
+      );
+    });
+  });
+
+  describe('restrictions', () => {
+    it('should return an error for an unsupported regex', () => {
+      // Lookaheads are valid in JavaScript but not supported by the resolver.
+      const resolver = new ResolverFactory({
+        restrictions: [{ regex: '^(?!.*node_modules).*$' }],
+      });
+      const result = resolver.sync(fixtureDir, './main1.js');
+      assert.strictEqual(result.path, undefined);
+      assert.strictEqual(result.errorInfo.kind, 'InvalidRestriction');
+    });
+  });
 });
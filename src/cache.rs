@@ -1,5 +1,7 @@
 use std::{
+    ffi::{OsStr, OsString},
     fmt::Debug,
+    io,
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -27,6 +29,27 @@ pub trait Cache: Sized {
     /// Returns whether the given `path` points to a file.
     fn is_dir(&self, path: &Self::Cp, ctx: &mut Ctx) -> bool;
 
+    /// Returns the entries of the given directory.
+    ///
+    /// The default implementation returns [io::ErrorKind::Unsupported].
+    fn read_dir(&self, _path: &Self::Cp) -> io::Result<Vec<Self::Cp>> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "read_dir is not supported"))
+    }
+
+    /// Returns the sorted file names of the given directory, `None` if it cannot be listed.
+    ///
+    /// See [ResolveOptions::extensions_read_dir_threshold].
+    fn read_dir_names(&self, path: &Self::Cp, _ctx: &mut Ctx) -> Option<Arc<[OsString]>> {
+        let mut names = self
+            .read_dir(path)
+            .ok()?
+            .iter()
+            .filter_map(|entry| entry.path().file_name().map(OsStr::to_os_string))
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        Some(names.into())
+    }
+
     /// Returns the package.json stored in the given directory, if one exists.
     ///
     /// `path` is the path to a directory from which the `package.json` will be
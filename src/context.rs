@@ -1,6 +1,11 @@
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
-use crate::error::ResolveError;
+use crate::{
+    dependency::DependencyEdge, diagnostic::ResolveDiagnostic, error::ResolveError, PackageType,
+};
 
 #[derive(Debug, Default, Clone)]
 pub struct ResolveContext {
@@ -16,9 +21,37 @@ pub struct ResolveContext {
     /// Files that was found on file system
     pub missing_dependencies: Option<Vec<PathBuf>>,
 
+    /// Directories that was found on file system
+    pub context_dependencies: Option<Vec<PathBuf>>,
+
+    /// Non-fatal findings reported while resolving.
+    pub diagnostics: Option<Vec<ResolveDiagnostic>>,
+
+    /// Package dependency graph edges, only recorded when initialized.
+    pub dependency_edges: Option<Vec<DependencyEdge>>,
+
+    /// Condition keys encountered in "exports" and "imports", only recorded when initialized.
+    pub available_conditions: Option<Vec<String>>,
+
+    /// Module type of the innermost "import" or "require" condition that selected the target,
+    /// see [crate::ResolveOptions::module_type_from_conditions].
+    pub(crate) condition_module_type: Option<PackageType>,
+
+    /// The "exports" subpath that produced the target, see [crate::Resolution::matched_export_subpath].
+    pub(crate) export_subpath: Option<String>,
+
     /// The current resolving alias for bailing recursion alias.
     pub resolving_alias: Option<String>,
 
+    /// Overrides [crate::ResolveOptions::extensions] for a single resolve call.
+    pub(crate) extensions: Option<Arc<[String]>>,
+
+    /// Package self-references currently being resolved, for detecting cycles.
+    self_references: Vec<(PathBuf, String)>,
+
+    /// Whether a cache lookup required fresh IO, see [ResolveContext::record_io].
+    pub(crate) io: bool,
+
     /// For avoiding infinite recursion, which will cause stack overflow.
     depth: u8,
 }
@@ -37,9 +70,14 @@ impl ResolveContext {
         }
     }
 
+    pub fn with_extensions(&mut self, extensions: &[&str]) {
+        self.extensions.replace(extensions.iter().map(ToString::to_string).collect());
+    }
+
     pub fn init_file_dependencies(&mut self) {
         self.file_dependencies.replace(vec![]);
         self.missing_dependencies.replace(vec![]);
+        self.context_dependencies.replace(vec![]);
     }
 
     pub fn add_file_dependency(&mut self, dep: &Path) {
@@ -54,10 +92,70 @@ impl ResolveContext {
         }
     }
 
+    pub fn add_context_dependency(&mut self, dep: &Path) {
+        if let Some(deps) = &mut self.context_dependencies {
+            deps.push(dep.to_path_buf());
+        }
+    }
+
+    pub fn init_diagnostics(&mut self) {
+        self.diagnostics.replace(vec![]);
+    }
+
+    pub fn add_diagnostic(&mut self, diagnostic: ResolveDiagnostic) {
+        if let Some(diagnostics) = &mut self.diagnostics {
+            if !diagnostics.contains(&diagnostic) {
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+
+    pub fn init_dependency_edges(&mut self) {
+        self.dependency_edges.replace(vec![]);
+    }
+
+    pub fn add_dependency_edge(&mut self, edge: DependencyEdge) {
+        if let Some(edges) = &mut self.dependency_edges {
+            edges.push(edge);
+        }
+    }
+
+    pub fn init_available_conditions(&mut self) {
+        self.available_conditions.replace(vec![]);
+    }
+
+    pub fn add_available_conditions<'a, I: Iterator<Item = &'a str>>(&mut self, keys: I) {
+        if let Some(conditions) = &mut self.available_conditions {
+            for key in keys {
+                if !conditions.iter().any(|condition| condition == key) {
+                    conditions.push(key.to_string());
+                }
+            }
+        }
+    }
+
+    /// Records that a cache lookup was not served from the cache and required IO.
+    pub fn record_io(&mut self) {
+        self.io = true;
+    }
+
     pub fn with_resolving_alias(&mut self, alias: String) {
         self.resolving_alias = Some(alias);
     }
 
+    /// Returns `false` when the self-reference `specifier` of the package is already being resolved.
+    pub(crate) fn enter_self_reference(&mut self, package_json: &Path, specifier: &str) -> bool {
+        if self.self_references.iter().any(|(path, s)| path == package_json && s == specifier) {
+            return false;
+        }
+        self.self_references.push((package_json.to_path_buf(), specifier.to_string()));
+        true
+    }
+
+    pub(crate) fn exit_self_reference(&mut self) {
+        self.self_references.pop();
+    }
+
     /// Increases the context's depth in order to detect recursion.
     ///
     /// ### Errors
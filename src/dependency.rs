@@ -0,0 +1,17 @@
+/// An edge in the package dependency graph, recorded when a bare specifier resolves into another package.
+///
+/// Edges are collected by [crate::ResolverGeneric::resolve_with_context]
+/// into [crate::ResolveContext::dependency_edges].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyEdge {
+    /// "name" of the closest package.json of the importing directory.
+    ///
+    /// `None` when the importer is not inside a package or its package.json has no "name".
+    pub importer: Option<String>,
+
+    /// Name of the imported package, as written in the specifier.
+    pub imported: String,
+
+    /// "version" of the imported package.
+    pub version: Option<String>,
+}
@@ -0,0 +1,61 @@
+use std::{fmt, path::PathBuf};
+
+use crate::JSONError;
+
+/// Non-fatal findings reported while resolving.
+///
+/// Diagnostics are collected by [crate::ResolverGeneric::resolve_with_context]
+/// into [crate::ResolveContext::diagnostics].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ResolveDiagnostic {
+    /// The package defines both "exports" and a main field,
+    /// the main field is ignored because "exports" takes precedence.
+    ///
+    /// <https://nodejs.org/api/packages.html#main>
+    MainFieldIgnored(/* path to package.json */ PathBuf),
+
+    /// A malformed package.json was skipped while looking up the closest package scope.
+    ///
+    /// See [crate::ResolveOptions::skip_invalid_package_json].
+    InvalidPackageJsonSkipped(JSONError),
+
+    /// The "browser" field is ignored because it is neither an object nor an array of objects.
+    InvalidBrowserField(/* path to package.json */ PathBuf),
+
+    /// The "exports" target of the package root does not exist, the main field is loaded instead.
+    ///
+    /// See [crate::ResolveOptions::exports_fallback_to_main].
+    ExportsTargetNotFound(/* path to package.json */ PathBuf),
+}
+
+impl fmt::Display for ResolveDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MainFieldIgnored(path) => {
+                write!(
+                    f,
+                    r#"The main field is ignored because "exports" is defined in {}"#,
+                    path.display()
+                )
+            }
+            Self::InvalidBrowserField(path) => {
+                write!(
+                    f,
+                    r#"The "browser" field is ignored because it is not an object in {}"#,
+                    path.display()
+                )
+            }
+            Self::ExportsTargetNotFound(path) => {
+                write!(
+                    f,
+                    r#"The "exports" target of "." does not exist in {}, the main field is loaded instead"#,
+                    path.display()
+                )
+            }
+            Self::InvalidPackageJsonSkipped(error) => {
+                write!(f, "Skipped invalid {}: {}", error.path.display(), error.message)
+            }
+        }
+    }
+}
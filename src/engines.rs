@@ -0,0 +1,245 @@
+//! Matching of the `package.json` "engines" field.
+//!
+//! Supports the subset of [node-semver](https://github.com/npm/node-semver#ranges) ranges used in practice:
+//! comparators, X-ranges, tilde, caret and hyphen ranges joined by whitespace and `||`.
+//! Prerelease and build tags are ignored.
+//!
+//! <https://docs.npmjs.com/cli/configuring-npm/package-json#engines>
+
+type Version = (u64, u64, u64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+}
+
+/// A version with omitted or wildcard components, e.g. `16`, `16.x` or `16.1.*`.
+#[derive(Debug, Clone, Copy)]
+struct Partial {
+    major: Option<u64>,
+    minor: Option<u64>,
+    patch: Option<u64>,
+}
+
+impl Partial {
+    fn parse(s: &str) -> Option<Self> {
+        let s = s.strip_prefix(['v', '=']).unwrap_or(s);
+        // Ignore prerelease and build tags.
+        let s = s.split(['-', '+']).next()?;
+        let mut parts = s.split('.');
+        let mut next = || -> Option<Option<u64>> {
+            match parts.next() {
+                None | Some("x" | "X" | "*") => Some(None),
+                Some(n) => n.parse().ok().map(Some),
+            }
+        };
+        let (major, minor, patch) = (next()?, next()?, next()?);
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self { major, minor: major.and(minor), patch: major.and(minor).and(patch) })
+    }
+
+    fn floor(self) -> Version {
+        (self.major.unwrap_or(0), self.minor.unwrap_or(0), self.patch.unwrap_or(0))
+    }
+
+    /// The smallest version above every version matched by the partial.
+    ///
+    /// Returns `None` when there is none, e.g. for `*` or a `u64::MAX` major.
+    fn ceil(self) -> Option<Version> {
+        let major = self.major?;
+        // A component at `u64::MAX` carries over to the next one.
+        let patch = self
+            .minor
+            .zip(self.patch)
+            .and_then(|(minor, patch)| Some((major, minor, patch.checked_add(1)?)));
+        let minor = || self.minor.and_then(|minor| Some((major, minor.checked_add(1)?, 0)));
+        patch.or_else(minor).or_else(|| Some((major.checked_add(1)?, 0, 0)))
+    }
+}
+
+/// Returns whether `version` satisfies the semver `range`.
+///
+/// Returns `None` when the range or the version cannot be parsed.
+pub fn satisfies(range: &str, version: &str) -> Option<bool> {
+    let version = Partial::parse(version.trim())?;
+    let version = (version.major?, version.minor?, version.patch?);
+    let mut satisfied = false;
+    for range in range.split("||") {
+        let comparators = parse_range(range)?;
+        satisfied |= comparators.iter().all(|(op, bound)| match op {
+            Op::Lt => version < *bound,
+            Op::Le => version <= *bound,
+            Op::Gt => version > *bound,
+            Op::Ge => version >= *bound,
+            Op::Eq => version == *bound,
+        });
+    }
+    Some(satisfied)
+}
+
+fn parse_range(range: &str) -> Option<Vec<(Op, Version)>> {
+    let mut comparators = vec![];
+    let tokens = range.split_whitespace().collect::<Vec<_>>();
+    if let [from, "-", to] = tokens[..] {
+        comparators.push((Op::Ge, Partial::parse(from)?.floor()));
+        if let Some(ceil) = Partial::parse(to)?.ceil() {
+            comparators.push((Op::Lt, ceil));
+        }
+        return Some(comparators);
+    }
+    let mut tokens = tokens.into_iter();
+    while let Some(token) = tokens.next() {
+        // Operators may be separated from their version, e.g. `>= 16`.
+        let token = if token.chars().all(|c| matches!(c, '<' | '>' | '=' | '~' | '^')) {
+            format!("{token}{}", tokens.next()?)
+        } else {
+            token.to_string()
+        };
+        parse_comparator(&token, &mut comparators)?;
+    }
+    Some(comparators)
+}
+
+fn parse_comparator(token: &str, comparators: &mut Vec<(Op, Version)>) -> Option<()> {
+    let (op, partial) = [">=", "<=", ">", "<", "~", "^"]
+        .iter()
+        .find_map(|op| token.strip_prefix(op).map(|partial| (*op, partial)))
+        .unwrap_or(("", token));
+    let partial = Partial::parse(partial)?;
+    let Some(major) = partial.major else {
+        // `*` matches everything, `<*` and `>*` nothing.
+        if matches!(op, "<" | ">") {
+            comparators.push((Op::Lt, (0, 0, 0)));
+        }
+        return Some(());
+    };
+    let floor = partial.floor();
+    // Without a ceil the range has no upper bound.
+    let ceil = partial.ceil();
+    match op {
+        ">=" => comparators.push((Op::Ge, floor)),
+        ">" if partial.patch.is_some() => comparators.push((Op::Gt, floor)),
+        ">" => comparators.push(ceil.map_or((Op::Lt, (0, 0, 0)), |ceil| (Op::Ge, ceil))),
+        "<" => comparators.push((Op::Lt, floor)),
+        "<=" if partial.patch.is_some() => comparators.push((Op::Le, floor)),
+        "<=" => comparators.extend(ceil.map(|ceil| (Op::Lt, ceil))),
+        "~" => {
+            comparators.push((Op::Ge, floor));
+            let ceil = Partial { patch: None, ..partial }.ceil();
+            comparators.extend(ceil.map(|ceil| (Op::Lt, ceil)));
+        }
+        "^" => {
+            comparators.push((Op::Ge, floor));
+            let ceil = match (major, partial.minor, partial.patch) {
+                (0, Some(0), Some(_)) => ceil,
+                (0, Some(_), _) => Partial { patch: None, ..partial }.ceil(),
+                _ => Partial { minor: None, patch: None, ..partial }.ceil(),
+            };
+            comparators.extend(ceil.map(|ceil| (Op::Lt, ceil)));
+        }
+        _ if partial.patch.is_some() => comparators.push((Op::Eq, floor)),
+        _ => {
+            comparators.push((Op::Ge, floor));
+            comparators.extend(ceil.map(|ceil| (Op::Lt, ceil)));
+        }
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::satisfies;
+
+    #[test]
+    fn ranges() {
+        let pass = [
+            ("*", "18.0.0"),
+            ("", "18.0.0"),
+            (">=14", "18.0.0"),
+            (">= 14.17.0", "14.17.0"),
+            (">14", "15.0.0"),
+            (">14.1", "14.2.0"),
+            ("<20", "19.9.9"),
+            ("<=18", "18.9.0"),
+            ("18", "18.2.0"),
+            ("18.x", "18.2.0"),
+            ("18.2.0", "18.2.0"),
+            ("v18.2.0", "v18.2.0"),
+            ("~18.2.0", "18.2.9"),
+            ("~18", "18.9.0"),
+            ("^18.2.0", "18.9.0"),
+            ("^0.2.3", "0.2.9"),
+            ("^0.0.3", "0.0.3"),
+            ("14 - 16", "16.9.0"),
+            ("14.0.0 - 16.1.0", "16.1.0"),
+            (">=12 <16", "14.0.0"),
+            ("^12.22.0 || ^14.17.0 || >=16.0.0", "14.18.0"),
+            ("^12.22.0 || ^14.17.0 || >=16.0.0", "20.0.0"),
+            (">=18.0.0-0", "18.0.0"),
+        ];
+        for (range, version) in pass {
+            assert_eq!(satisfies(range, version), Some(true), "{range} {version}");
+        }
+
+        let fail = [
+            (">=20", "18.0.0"),
+            (">14", "14.9.0"),
+            ("<20", "20.0.0"),
+            ("<=18", "19.0.0"),
+            ("18", "19.0.0"),
+            ("18.2.0", "18.2.1"),
+            ("~18.2.0", "18.3.0"),
+            ("^18.2.0", "19.0.0"),
+            ("^18.2.0", "18.1.0"),
+            ("^0.2.3", "0.3.0"),
+            ("^0.0.3", "0.0.4"),
+            ("14 - 16", "17.0.0"),
+            (">=12 <16", "16.0.0"),
+            ("^12.22.0 || ^14.17.0 || >=16.0.0", "14.16.0"),
+        ];
+        for (range, version) in fail {
+            assert_eq!(satisfies(range, version), Some(false), "{range} {version}");
+        }
+
+        assert_eq!(satisfies("not a range", "18.0.0"), None);
+        assert_eq!(satisfies(">=18", "18"), None);
+    }
+
+    #[test]
+    fn u64_max_components() {
+        const MAX: &str = "18446744073709551615";
+
+        let pass = [
+            (format!("<={MAX}"), format!("{MAX}.{MAX}.{MAX}")),
+            (format!("^{MAX}"), format!("{MAX}.1.0")),
+            (format!("~{MAX}"), format!("{MAX}.1.0")),
+            (format!("{MAX}.x"), format!("{MAX}.1.0")),
+            (format!("<=1.{MAX}"), format!("1.{MAX}.9")),
+            (format!("~1.{MAX}.0"), format!("1.{MAX}.9")),
+            (format!("^0.{MAX}"), format!("0.{MAX}.9")),
+            (format!("^0.0.{MAX}"), format!("0.0.{MAX}")),
+            (format!("1 - {MAX}"), format!("{MAX}.0.0")),
+        ];
+        for (range, version) in pass {
+            assert_eq!(satisfies(&range, &version), Some(true), "{range} {version}");
+        }
+
+        let fail = [
+            (format!(">{MAX}"), format!("{MAX}.{MAX}.{MAX}")),
+            (format!("<=1.{MAX}"), "2.0.0".to_string()),
+            (format!("~1.{MAX}.0"), "2.0.0".to_string()),
+            (format!("^0.{MAX}"), "1.0.0".to_string()),
+            (format!("^0.0.{MAX}"), "0.1.0".to_string()),
+            (format!("<=1.2.{MAX}"), "1.3.0".to_string()),
+        ];
+        for (range, version) in fail {
+            assert_eq!(satisfies(&range, &version), Some(false), "{range} {version}");
+        }
+    }
+}
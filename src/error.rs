@@ -26,6 +26,16 @@ pub enum ResolveError {
     #[error("Cannot find module '{0}'")]
     NotFound(/* specifier */ String),
 
+    /// A `workspace:` protocol specifier was requested without configuring [crate::ResolveOptions::workspaces]
+    #[error("Cannot resolve '{0}', workspaces are not configured")]
+    WorkspacesNotConfigured(/* specifier */ String),
+
+    /// Module not found in any of the directories given to [crate::ResolverGeneric::resolve_from_any]
+    ///
+    /// Contains the error from each directory, in the order they were tried.
+    #[error("Cannot find module '{0}' in any of the given directories")]
+    NotFoundInAny(/* specifier */ String, Vec<ResolveError>),
+
     /// Matched alias value  not found
     #[error("Cannot find module '{0}' for matched aliased key '{1}'")]
     MatchedAliasNotFound(/* specifier */ String, /* alias key */ String),
@@ -34,6 +44,12 @@ pub enum ResolveError {
     #[error("Tsconfig not found {0}")]
     TsconfigNotFound(PathBuf),
 
+    /// Too many levels of symbolic links, mirrors `ELOOP`.
+    ///
+    /// See [crate::FsCache::with_max_symlink_depth].
+    #[error("Too many levels of symbolic links {0}")]
+    TooManySymlinks(PathBuf),
+
     /// Tsconfig's project reference path points to it self
     #[error("Tsconfig's project reference path points to this tsconfig {0}")]
     TsconfigSelfReference(PathBuf),
@@ -46,7 +62,8 @@ pub enum ResolveError {
     /// `is_runtime_module` can be used to determine whether the request
     /// was prefixed with `node:` or not.
     ///
-    /// `resolved` is always prefixed with "node:" in compliance with the ESM specification.
+    /// `resolved` is prefixed with "node:" in compliance with the ESM specification,
+    /// except for builtins of other runtimes in [crate::ResolveOptions::builtin_modules_list].
     #[error("Builtin module {resolved}")]
     Builtin { resolved: String, is_runtime_module: bool },
 
@@ -68,10 +85,40 @@ pub enum ResolveError {
     #[error("{0:?}")]
     JSON(JSONError),
 
+    /// Cancelled by [crate::ResolveOptions::abort_signal]
+    #[error("Resolution aborted")]
+    Aborted,
+
+    /// The specifier only matches a deprecated folder mapping, see [crate::ResolveOptions::folder_mapping].
+    #[error(r#"Deprecated folder mapping "{0}" is not allowed for '{1}' in {2}"#)]
+    FolderMappingDisabled(
+        /* key */ String,
+        /* specifier */ String,
+        /* path to package.json */ PathBuf,
+    ),
+
+    /// The resolved path is rejected by [crate::ResolveOptions::post_resolve]
+    #[error(r#"Path "{0}" rejected: {1}"#)]
+    Rejected(PathBuf, /* reason */ String),
+
+    /// A package self-reference resolves back to itself, e.g. through the "browser" field.
+    #[error("Self-reference cycle detected while resolving '{0}' in {1}")]
+    SelfReferenceCycle(/* specifier */ String, /* path to package.json */ PathBuf),
+
+    /// The resolved file is excluded by the "files" field of its package.
+    ///
+    /// See [crate::ResolveOptions::honor_files_field].
+    #[error(r#"File "{file}" is not published by the "files" field in {package}"#)]
+    NotPublished { file: PathBuf, /* path to package.json */ package: PathBuf },
+
     /// Restricted by `ResolveOptions::restrictions`
     #[error(r#"Path "{0}" restricted by {0}"#)]
     Restriction(PathBuf, PathBuf),
 
+    /// A [crate::Restriction::RegExp] pattern is not supported by the regex crate, e.g. a lookahead.
+    #[error(r#"Invalid restriction pattern "{0}": {1}"#)]
+    InvalidRestriction(/* pattern */ String, /* error */ String),
+
     #[error(r#"Invalid module "{0}" specifier is not a valid subpath for the "exports" resolution of {1}"#)]
     InvalidModuleSpecifier(String, PathBuf),
 
@@ -81,6 +128,30 @@ pub enum ResolveError {
     #[error(r#"Package subpath '{0}' is not defined by "exports" in {1}"#)]
     PackagePathNotExported(String, PathBuf),
 
+    /// The package root `.` is defined by "exports" but none of its conditions match
+    /// [crate::ResolveOptions::condition_names].
+    ///
+    /// A matched condition with a null target is reported as [ResolveError::PackagePathNotExported].
+    #[error(r#"No "exports" condition matched for the package root in {package}, available conditions: {}"#, conditions.join(", "))]
+    PackageConditionNotMatched {
+        /// Path to package.json
+        package: PathBuf,
+        /// Condition keys of the root export
+        conditions: Vec<String>,
+    },
+
+    /// The package of the resolved file requires a Node.js version that
+    /// [crate::ResolveOptions::target_node_version] does not satisfy.
+    #[error(r#"Package {package} requires node "{required}", the target is {actual}"#)]
+    IncompatibleEngine {
+        /// Path to package.json
+        package: PathBuf,
+        /// The "engines.node" range
+        required: String,
+        /// [crate::ResolveOptions::target_node_version]
+        actual: String,
+    },
+
     #[error(r#"Invalid package config "{0}", "exports" cannot contain some keys starting with '.' and some not. The exports object must either be an object of package subpath keys or an object of main entry condition name keys only."#)]
     InvalidPackageConfig(PathBuf),
 
@@ -101,12 +172,101 @@ pub enum ResolveError {
     Recursion,
 }
 
+/// Coarse category of a [ResolveError], see [ResolveError::kind].
+///
+/// Unlike [ResolveError], new variants are not expected to be added,
+/// so it can be matched exhaustively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResolveErrorKind {
+    /// The module, alias target, extension alias or tsconfig is not found.
+    NotFound,
+    /// The subpath or conditions are not exported by the "exports" field of the package.
+    NotExported,
+    /// The specifier is not defined by the "imports" field of the package.
+    ImportNotDefined,
+    /// Invalid package.json, tsconfig or options.
+    InvalidConfig,
+    /// The resolved path is refused by the options, e.g. [crate::ResolveOptions::restrictions].
+    Restriction,
+    /// [ResolveError::Builtin]
+    Builtin,
+    /// [ResolveError::Ignored]
+    Ignored,
+    /// Aliases or a package self-reference resolve back to themselves.
+    Recursion,
+    /// File system errors.
+    Io,
+    /// Everything else, e.g. an empty specifier or an aborted resolution.
+    Other,
+}
+
 impl ResolveError {
+    /// Returns the category of the error.
+    #[must_use]
+    pub const fn kind(&self) -> ResolveErrorKind {
+        match self {
+            Self::NotFound(_)
+            | Self::NotFoundInAny(..)
+            | Self::MatchedAliasNotFound(..)
+            | Self::TsconfigNotFound(_)
+            | Self::ExtensionAlias(..) => ResolveErrorKind::NotFound,
+            Self::PackagePathNotExported(..)
+            | Self::PackageConditionNotMatched { .. }
+            | Self::FolderMappingDisabled(..) => ResolveErrorKind::NotExported,
+            Self::PackageImportNotDefined(..) => ResolveErrorKind::ImportNotDefined,
+            Self::WorkspacesNotConfigured(_)
+            | Self::TsconfigSelfReference(_)
+            | Self::JSON(_)
+            | Self::InvalidRestriction(..)
+            | Self::InvalidModuleSpecifier(..)
+            | Self::InvalidPackageTarget(..)
+            | Self::InvalidPackageConfig(_)
+            | Self::InvalidPackageConfigDefault(_)
+            | Self::InvalidPackageConfigDirectory(_) => ResolveErrorKind::InvalidConfig,
+            Self::Restriction(..)
+            | Self::Rejected(..)
+            | Self::NotPublished { .. }
+            | Self::IncompatibleEngine { .. } => ResolveErrorKind::Restriction,
+            Self::Builtin { .. } => ResolveErrorKind::Builtin,
+            Self::Ignored(_) => ResolveErrorKind::Ignored,
+            Self::Recursion | Self::SelfReferenceCycle(..) => ResolveErrorKind::Recursion,
+            Self::IOError(_) | Self::TooManySymlinks(_) => ResolveErrorKind::Io,
+            Self::Specifier(_) | Self::Aborted | Self::Unimplemented(_) => ResolveErrorKind::Other,
+        }
+    }
+
     #[must_use]
     pub const fn is_ignore(&self) -> bool {
         matches!(self, Self::Ignored(_))
     }
 
+    /// Whether the error is [ResolveError::Ignored], same as [ResolveError::is_ignore].
+    #[must_use]
+    pub const fn is_ignored(&self) -> bool {
+        self.is_ignore()
+    }
+
+    /// Whether the error is [ResolveError::NotFound].
+    #[must_use]
+    pub const fn is_not_found(&self) -> bool {
+        matches!(self, Self::NotFound(_))
+    }
+
+    /// Whether the error is [ResolveError::Builtin].
+    #[must_use]
+    pub const fn is_builtin(&self) -> bool {
+        matches!(self, Self::Builtin { .. })
+    }
+
+    /// Returns the resolved name of a [ResolveError::Builtin], e.g. `node:fs`.
+    #[must_use]
+    pub fn as_builtin(&self) -> Option<&str> {
+        match self {
+            Self::Builtin { resolved, .. } => Some(resolved),
+            _ => None,
+        }
+    }
+
     #[must_use]
     #[cfg(feature = "fs_cache")]
     pub fn from_serde_json_error(path: PathBuf, error: &serde_json::Error) -> Self {
@@ -193,3 +353,75 @@ fn test_coverage() {
     assert_eq!(format!("{error:?}"), r#"Specifier(Empty("x"))"#);
     assert_eq!(error.clone(), error);
 }
+
+#[test]
+fn test_predicates() {
+    let not_found = ResolveError::NotFound("x".into());
+    let builtin = ResolveError::Builtin { resolved: "node:fs".into(), is_runtime_module: false };
+    let ignored = ResolveError::Ignored(PathBuf::from("x"));
+    let other = ResolveError::Recursion;
+
+    assert!(not_found.is_not_found());
+    assert!(!builtin.is_not_found());
+    assert!(!ignored.is_not_found());
+    assert!(!other.is_not_found());
+
+    assert!(builtin.is_builtin());
+    assert!(!not_found.is_builtin());
+    assert!(!other.is_builtin());
+
+    assert_eq!(builtin.as_builtin(), Some("node:fs"));
+    assert_eq!(not_found.as_builtin(), None);
+    assert_eq!(other.as_builtin(), None);
+
+    assert!(ignored.is_ignored());
+    assert!(ignored.is_ignore());
+    assert!(!not_found.is_ignored());
+    assert!(!builtin.is_ignored());
+}
+
+#[test]
+fn test_kind() {
+    use std::path::Path;
+
+    let path = || Path::new("/package.json").to_path_buf();
+    let string = || String::from("foo");
+    let json = || JSONError { path: path(), message: String::new(), line: 0, column: 0 };
+    #[rustfmt::skip]
+    let data = [
+        (ResolveError::Ignored(path()), ResolveErrorKind::Ignored),
+        (ResolveError::NotFound(string()), ResolveErrorKind::NotFound),
+        (ResolveError::WorkspacesNotConfigured(string()), ResolveErrorKind::InvalidConfig),
+        (ResolveError::NotFoundInAny(string(), vec![]), ResolveErrorKind::NotFound),
+        (ResolveError::MatchedAliasNotFound(string(), string()), ResolveErrorKind::NotFound),
+        (ResolveError::TsconfigNotFound(path()), ResolveErrorKind::NotFound),
+        (ResolveError::TooManySymlinks(path()), ResolveErrorKind::Io),
+        (ResolveError::TsconfigSelfReference(path()), ResolveErrorKind::InvalidConfig),
+        (ResolveError::from(io::Error::from(io::ErrorKind::NotFound)), ResolveErrorKind::Io),
+        (ResolveError::Builtin { resolved: string(), is_runtime_module: false }, ResolveErrorKind::Builtin),
+        (ResolveError::ExtensionAlias(string(), string(), path()), ResolveErrorKind::NotFound),
+        (ResolveError::Specifier(SpecifierError::Empty(String::new())), ResolveErrorKind::Other),
+        (ResolveError::JSON(json()), ResolveErrorKind::InvalidConfig),
+        (ResolveError::Aborted, ResolveErrorKind::Other),
+        (ResolveError::FolderMappingDisabled(string(), string(), path()), ResolveErrorKind::NotExported),
+        (ResolveError::Rejected(path(), string()), ResolveErrorKind::Restriction),
+        (ResolveError::SelfReferenceCycle(string(), path()), ResolveErrorKind::Recursion),
+        (ResolveError::NotPublished { file: path(), package: path() }, ResolveErrorKind::Restriction),
+        (ResolveError::Restriction(path(), path()), ResolveErrorKind::Restriction),
+        (ResolveError::InvalidRestriction(string(), string()), ResolveErrorKind::InvalidConfig),
+        (ResolveError::InvalidModuleSpecifier(string(), path()), ResolveErrorKind::InvalidConfig),
+        (ResolveError::InvalidPackageTarget(string(), string(), path()), ResolveErrorKind::InvalidConfig),
+        (ResolveError::PackagePathNotExported(string(), path()), ResolveErrorKind::NotExported),
+        (ResolveError::PackageConditionNotMatched { package: path(), conditions: vec![] }, ResolveErrorKind::NotExported),
+        (ResolveError::IncompatibleEngine { package: path(), required: string(), actual: string() }, ResolveErrorKind::Restriction),
+        (ResolveError::InvalidPackageConfig(path()), ResolveErrorKind::InvalidConfig),
+        (ResolveError::InvalidPackageConfigDefault(path()), ResolveErrorKind::InvalidConfig),
+        (ResolveError::InvalidPackageConfigDirectory(path()), ResolveErrorKind::InvalidConfig),
+        (ResolveError::PackageImportNotDefined(string(), path()), ResolveErrorKind::ImportNotDefined),
+        (ResolveError::Unimplemented("foo"), ResolveErrorKind::Other),
+        (ResolveError::Recursion, ResolveErrorKind::Recursion),
+    ];
+    for (error, kind) in data {
+        assert_eq!(error.kind(), kind, "{error:?}");
+    }
+}
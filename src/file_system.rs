@@ -1,6 +1,7 @@
 use std::{
     fs, io,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 
 use cfg_if::cfg_if;
@@ -50,20 +51,64 @@ pub trait FileSystem: Send + Sync {
     ///
     /// See [std::fs::read_link]
     fn read_link(&self, path: &Path) -> io::Result<PathBuf>;
+
+    /// Returns the paths of the entries within a directory.
+    ///
+    /// Used for expanding wildcards in [crate::ResolverGeneric::resolve_package_entries],
+    /// and during resolution when [crate::ResolveOptions::extensions_read_dir_threshold]
+    /// or [crate::ResolveOptions::node_modules_read_dir] is enabled.
+    /// The default implementation returns [io::ErrorKind::Unsupported], which those
+    /// options treat as a directory that cannot be listed.
+    ///
+    /// # Errors
+    ///
+    /// See [std::fs::read_dir]
+    fn read_dir(&self, _path: &Path) -> io::Result<Vec<PathBuf>> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "read_dir is not supported"))
+    }
+
+    /// Writes `contents` to the file at `path`, replacing it.
+    ///
+    /// This is only used by [crate::ResolverGeneric::save_persistent_cache].
+    /// Implementations should replace the file atomically so concurrent readers never see a partial file.
+    /// The default implementation returns [io::ErrorKind::Unsupported].
+    ///
+    /// # Errors
+    ///
+    /// See [std::fs::write]
+    fn write(&self, _path: &Path, _contents: &[u8]) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "write is not supported"))
+    }
 }
 
 /// Metadata information about a file
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct FileMetadata {
     pub(crate) is_file: bool,
     pub(crate) is_dir: bool,
     pub(crate) is_symlink: bool,
+    pub(crate) len: Option<u64>,
+    pub(crate) modified: Option<SystemTime>,
 }
 
 impl FileMetadata {
     #[must_use]
     pub const fn new(is_file: bool, is_dir: bool, is_symlink: bool) -> Self {
-        Self { is_file, is_dir, is_symlink }
+        Self { is_file, is_dir, is_symlink, len: None, modified: None }
+    }
+
+    /// Sets the size of the file in bytes.
+    #[must_use]
+    pub const fn with_len(mut self, len: u64) -> Self {
+        self.len = Some(len);
+        self
+    }
+
+    /// Sets the last modification time of the file.
+    #[must_use]
+    pub const fn with_modified(mut self, modified: SystemTime) -> Self {
+        self.modified = Some(modified);
+        self
     }
 
     #[must_use]
@@ -80,6 +125,19 @@ impl FileMetadata {
     pub const fn is_symlink(self) -> bool {
         self.is_symlink
     }
+
+    /// Size of the file in bytes, `None` when the file system does not report it.
+    #[must_use]
+    #[allow(clippy::len_without_is_empty)] // `len` is the size reported by the file system, not a collection length
+    pub const fn len(self) -> Option<u64> {
+        self.len
+    }
+
+    /// Last modification time of the file, `None` when the file system does not report it.
+    #[must_use]
+    pub const fn modified(self) -> Option<SystemTime> {
+        self.modified
+    }
 }
 
 #[cfg(feature = "yarn_pnp")]
@@ -91,7 +149,9 @@ impl From<pnp::fs::FileType> for FileMetadata {
 
 impl From<fs::Metadata> for FileMetadata {
     fn from(metadata: fs::Metadata) -> Self {
-        Self::new(metadata.is_file(), metadata.is_dir(), metadata.is_symlink())
+        let meta = Self::new(metadata.is_file(), metadata.is_dir(), metadata.is_symlink())
+            .with_len(metadata.len());
+        metadata.modified().map_or(meta, |modified| meta.with_modified(modified))
     }
 }
 
@@ -124,6 +184,10 @@ impl FileSystemOs {
         // `simdutf8` is faster than `std::str::from_utf8` which `fs::read_to_string` uses internally
         let bytes = std::fs::read(path)?;
         if simdutf8::basic::from_utf8(&bytes).is_err() {
+            // Files saved as UTF-16 with a BOM, e.g. by Windows tools.
+            if let Some(string) = Self::decode_utf16(&bytes) {
+                return Ok(string);
+            }
             // Same error as `fs::read_to_string` produces (`io::Error::INVALID_UTF8`)
             return Err(io::Error::new(
                 io::ErrorKind::InvalidData,
@@ -134,6 +198,20 @@ impl FileSystemOs {
         Ok(unsafe { String::from_utf8_unchecked(bytes) })
     }
 
+    /// Decodes UTF-16 LE or BE bytes starting with a byte order mark, the BOM is kept.
+    fn decode_utf16(bytes: &[u8]) -> Option<String> {
+        let from_bytes: fn([u8; 2]) -> u16 = match bytes {
+            [0xFF, 0xFE, ..] => u16::from_le_bytes,
+            [0xFE, 0xFF, ..] => u16::from_be_bytes,
+            _ => return None,
+        };
+        if bytes.len() % 2 != 0 {
+            return None;
+        }
+        let units = bytes.chunks_exact(2).map(|chunk| from_bytes([chunk[0], chunk[1]]));
+        char::decode_utf16(units).collect::<Result<String, _>>().ok()
+    }
+
     /// # Errors
     ///
     /// See [std::fs::metadata]
@@ -165,6 +243,28 @@ impl FileSystemOs {
         }
     }
 
+    /// # Errors
+    ///
+    /// See [std::fs::read_dir]
+    #[inline]
+    pub fn read_dir(path: &Path) -> io::Result<Vec<PathBuf>> {
+        fs::read_dir(path)?.map(|entry| entry.map(|entry| entry.path())).collect()
+    }
+
+    /// Writes to a temporary file next to `path` first and renames it over `path`,
+    /// so concurrent readers never see a partial file.
+    ///
+    /// # Errors
+    ///
+    /// See [std::fs::write] and [std::fs::rename]
+    pub fn write(path: &Path, contents: &[u8]) -> io::Result<()> {
+        let nanos =
+            SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map_or(0, |d| d.as_nanos());
+        let temp = path.with_extension(format!("{nanos}.tmp"));
+        fs::write(&temp, contents)?;
+        fs::rename(&temp, path)
+    }
+
     pub fn strip_windows_prefix<P: AsRef<Path>>(path: P) -> PathBuf {
         const UNC_PATH_PREFIX: &[u8] = b"\\\\?\\UNC\\";
         const LONG_PATH_PREFIX: &[u8] = b"\\\\?\\";
@@ -203,10 +303,16 @@ impl FileSystem for FileSystemOs {
         cfg_if! {
             if #[cfg(feature = "yarn_pnp")] {
                 match VPath::from(path)? {
-                    VPath::Zip(info) => self
-                        .pnp_lru
-                        .file_type(info.physical_base_path(), info.zip_path)
-                        .map(FileMetadata::from),
+                    VPath::Zip(info) => {
+                        let base_path = info.physical_base_path();
+                        self.pnp_lru
+                            .file_type(&base_path, &info.zip_path)
+                            // Directories are listed with a trailing slash in archives.
+                            .or_else(|_| {
+                                self.pnp_lru.file_type(&base_path, format!("{}/", info.zip_path))
+                            })
+                            .map(FileMetadata::from)
+                    }
                     VPath::Virtual(info) => {
                         Self::metadata(&info.physical_base_path()).map(FileMetadata::from)
                     }
@@ -235,14 +341,46 @@ impl FileSystem for FileSystemOs {
             }
         }
     }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        cfg_if! {
+            if #[cfg(feature = "yarn_pnp")] {
+                match VPath::from(path)? {
+                    VPath::Zip(_) => Err(io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        "read_dir is not supported in zip archives",
+                    )),
+                    VPath::Virtual(info) => Self::read_dir(&info.physical_base_path()),
+                    VPath::Native(path) => Self::read_dir(&path),
+                }
+            } else {
+                Self::read_dir(path)
+            }
+        }
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        Self::write(path, contents)
+    }
 }
 
 #[test]
 fn metadata() {
-    let meta = FileMetadata { is_file: true, is_dir: true, is_symlink: true };
+    let meta =
+        FileMetadata { is_file: true, is_dir: true, is_symlink: true, len: None, modified: None };
     assert_eq!(
         format!("{meta:?}"),
-        "FileMetadata { is_file: true, is_dir: true, is_symlink: true }"
+        "FileMetadata { is_file: true, is_dir: true, is_symlink: true, len: None, modified: None }"
     );
     let _ = meta;
 }
+
+#[test]
+fn metadata_len() {
+    let path =
+        std::env::current_dir().unwrap().join("fixtures/enhanced_resolve/test/fixtures/abc.txt");
+    let meta = FileSystemOs::metadata(&path).unwrap();
+    assert!(meta.is_file());
+    assert_eq!(meta.len(), Some(3));
+    assert_eq!(FileMetadata::new(true, false, false).len(), None);
+}
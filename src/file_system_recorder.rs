@@ -0,0 +1,152 @@
+//! Recording and replaying of [FileSystem] calls, for reproducing resolver bugs.
+//!
+//! Wrap the file system with [RecordingFileSystem], resolve the failing specifier,
+//! serialize the recorded [FsCall]s and replay them elsewhere with [ReplayFileSystem].
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{FileMetadata, FileSystem};
+
+/// A [FileSystem] call and its result, `None` when the call failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum FsCall {
+    ReadToString { path: PathBuf, content: Option<String> },
+    Metadata { path: PathBuf, metadata: Option<FileMetadata> },
+    SymlinkMetadata { path: PathBuf, metadata: Option<FileMetadata> },
+    ReadLink { path: PathBuf, target: Option<PathBuf> },
+    ReadDir { path: PathBuf, entries: Option<Vec<PathBuf>> },
+}
+
+/// A [FileSystem] that records the sequence of calls made to the wrapped file system.
+pub struct RecordingFileSystem<Fs> {
+    fs: Fs,
+    calls: Arc<Mutex<Vec<FsCall>>>,
+}
+
+impl<Fs: FileSystem> RecordingFileSystem<Fs> {
+    pub fn new(fs: Fs) -> Self {
+        Self { fs, calls: Arc::default() }
+    }
+
+    /// The recorded calls in order, shared with the file system so they can be read after
+    /// the file system is moved into a cache.
+    #[must_use]
+    pub fn calls(&self) -> Arc<Mutex<Vec<FsCall>>> {
+        Arc::clone(&self.calls)
+    }
+
+    fn record(&self, call: FsCall) {
+        self.calls.lock().unwrap_or_else(std::sync::PoisonError::into_inner).push(call);
+    }
+}
+
+impl<Fs: FileSystem> FileSystem for RecordingFileSystem<Fs> {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        let result = self.fs.read_to_string(path);
+        let content = result.as_ref().ok().cloned();
+        self.record(FsCall::ReadToString { path: path.to_path_buf(), content });
+        result
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        let result = self.fs.metadata(path);
+        let metadata = result.as_ref().ok().copied();
+        self.record(FsCall::Metadata { path: path.to_path_buf(), metadata });
+        result
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        let result = self.fs.symlink_metadata(path);
+        let metadata = result.as_ref().ok().copied();
+        self.record(FsCall::SymlinkMetadata { path: path.to_path_buf(), metadata });
+        result
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        let result = self.fs.read_link(path);
+        let target = result.as_ref().ok().cloned();
+        self.record(FsCall::ReadLink { path: path.to_path_buf(), target });
+        result
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let result = self.fs.read_dir(path);
+        let entries = result.as_ref().ok().cloned();
+        self.record(FsCall::ReadDir { path: path.to_path_buf(), entries });
+        result
+    }
+}
+
+/// An in-memory [FileSystem] answering from calls recorded by [RecordingFileSystem].
+///
+/// Calls that were not recorded, and recorded failures, return [io::ErrorKind::NotFound].
+#[derive(Default)]
+pub struct ReplayFileSystem {
+    contents: FxHashMap<PathBuf, String>,
+    metadata: FxHashMap<PathBuf, FileMetadata>,
+    symlink_metadata: FxHashMap<PathBuf, FileMetadata>,
+    links: FxHashMap<PathBuf, PathBuf>,
+    entries: FxHashMap<PathBuf, Vec<PathBuf>>,
+}
+
+impl ReplayFileSystem {
+    #[must_use]
+    pub fn new(calls: Vec<FsCall>) -> Self {
+        let mut fs = Self::default();
+        for call in calls {
+            match call {
+                FsCall::ReadToString { path, content: Some(content) } => {
+                    fs.contents.insert(path, content);
+                }
+                FsCall::Metadata { path, metadata: Some(metadata) } => {
+                    fs.metadata.insert(path, metadata);
+                }
+                FsCall::SymlinkMetadata { path, metadata: Some(metadata) } => {
+                    fs.symlink_metadata.insert(path, metadata);
+                }
+                FsCall::ReadLink { path, target: Some(target) } => {
+                    fs.links.insert(path, target);
+                }
+                FsCall::ReadDir { path, entries: Some(entries) } => {
+                    fs.entries.insert(path, entries);
+                }
+                _ => {}
+            }
+        }
+        fs
+    }
+
+    fn not_found(path: &Path) -> io::Error {
+        io::Error::new(io::ErrorKind::NotFound, format!("{} is not recorded", path.display()))
+    }
+}
+
+impl FileSystem for ReplayFileSystem {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.contents.get(path).cloned().ok_or_else(|| Self::not_found(path))
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.metadata.get(path).copied().ok_or_else(|| Self::not_found(path))
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.symlink_metadata.get(path).copied().ok_or_else(|| Self::not_found(path))
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        self.links.get(path).cloned().ok_or_else(|| Self::not_found(path))
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        self.entries.get(path).cloned().ok_or_else(|| Self::not_found(path))
+    }
+}
@@ -0,0 +1,102 @@
+//! Matching of the `package.json` "files" field.
+//!
+//! Patterns are matched with [fast_glob], which supports `*`, `?`, `**`, `[...]` and `{a,b}`.
+//!
+//! <https://docs.npmjs.com/cli/configuring-npm/package-json#files>
+
+use std::path::Path;
+
+use crate::PackageJson;
+
+/// Returns whether `path` inside the package would be published according to its "files" field.
+///
+/// Packages without a "files" field publish everything.
+pub fn is_published<P: PackageJson>(package_json: &P, path: &Path) -> bool {
+    let Some(files) = package_json.files() else {
+        return true;
+    };
+    let Ok(relative) = path.strip_prefix(package_json.directory()) else {
+        return true;
+    };
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    if is_always_published(package_json, &relative) {
+        return true;
+    }
+    let mut published = false;
+    for pattern in files {
+        let (negated, pattern) =
+            pattern.strip_prefix('!').map_or((false, pattern), |pattern| (true, pattern));
+        let pattern =
+            pattern.trim_start_matches("./").trim_start_matches('/').trim_end_matches('/');
+        if pattern.is_empty() {
+            continue;
+        }
+        if matches_path_or_ancestor(pattern, &relative) {
+            published = !negated;
+        }
+    }
+    published
+}
+
+/// npm always publishes `package.json`, the readme, the license and the "main" file.
+fn is_always_published<P: PackageJson>(package_json: &P, relative: &str) -> bool {
+    if relative == "package.json" {
+        return true;
+    }
+    if !relative.contains('/') {
+        let name = relative.to_ascii_uppercase();
+        if ["README", "LICENSE", "LICENCE"].iter().any(|prefix| name.starts_with(prefix)) {
+            return true;
+        }
+    }
+    package_json
+        .main_fields(&["main".to_string()])
+        .any(|main| main.trim_start_matches("./") == relative)
+}
+
+/// A pattern matching a directory includes everything inside it.
+fn matches_path_or_ancestor(pattern: &str, relative: &str) -> bool {
+    relative
+        .match_indices('/')
+        .map(|(index, _)| &relative[..index])
+        .chain(std::iter::once(relative))
+        .any(|path| fast_glob::glob_match(pattern, path))
+}
+
+#[cfg(test)]
+mod test {
+    use super::matches_path_or_ancestor;
+
+    #[test]
+    fn patterns() {
+        let pass = [
+            ("dist", "dist"),
+            ("dist", "dist/index.js"),
+            ("*.js", "index.js"),
+            ("lib/*.js", "lib/a.js"),
+            ("lib/?.js", "lib/a.js"),
+            ("**/*.js", "a.js"),
+            ("**/*.js", "lib/nested/a.js"),
+            ("lib/**", "lib/nested/a.js"),
+            ("lib/*.{js,mjs}", "lib/a.mjs"),
+            ("lib/[ab].js", "lib/b.js"),
+        ];
+        for (pattern, path) in pass {
+            assert!(matches_path_or_ancestor(pattern, path), "{pattern} {path}");
+        }
+
+        let fail = [
+            ("dist", "dist2"),
+            ("dist", "lib/dist"),
+            ("*.js", "lib/a.ts"),
+            ("lib/*.js", "lib/nested/a.ts"),
+            ("lib/?.js", "lib/ab.js"),
+            ("**/*.js", "a.ts"),
+            ("lib/*.{js,mjs}", "lib/a.cjs"),
+            ("lib/[ab].js", "lib/c.js"),
+        ];
+        for (pattern, path) in fail {
+            assert!(!matches_path_or_ancestor(pattern, path), "{pattern} {path}");
+        }
+    }
+}
@@ -2,6 +2,7 @@ use std::{
     borrow::Cow,
     cell::RefCell,
     convert::AsRef,
+    ffi::{OsStr, OsString},
     hash::{BuildHasherDefault, Hash, Hasher},
     io,
     ops::Deref,
@@ -21,8 +22,8 @@ use crate::{
     cache::{Cache, CachedPath},
     context::ResolveContext as Ctx,
     path::PathUtil,
-    FileMetadata, FileSystem, PackageJsonSerde, ResolveError, ResolveOptions, TsConfig,
-    TsConfigSerde,
+    FileMetadata, FileSystem, PackageJsonSerde, ResolveDiagnostic, ResolveError, ResolveOptions,
+    TsConfig, TsConfigSerde,
 };
 
 static THREAD_COUNT: AtomicU64 = AtomicU64::new(1);
@@ -34,12 +35,30 @@ thread_local! {
   pub static THREAD_ID: u64 = THREAD_COUNT.fetch_add(1, Ordering::SeqCst);
 }
 
+/// Maximum number of symbolic links followed while canonicalizing a path, same as Linux's `MAXSYMLINKS`.
+const DEFAULT_MAX_SYMLINK_DEPTH: usize = 40;
+
 /// Cache implementation used for caching filesystem access.
-#[derive(Default)]
 pub struct FsCache<Fs> {
-    pub(crate) fs: Fs,
+    pub(crate) fs: Arc<Fs>,
     paths: HashSet<FsCachedPath, BuildHasherDefault<IdentityHasher>>,
     tsconfigs: HashMap<PathBuf, Arc<TsConfigSerde>, BuildHasherDefault<FxHasher>>,
+    /// Parsed [ResolveOptions::description_files] other than package.json, by file path.
+    description_files:
+        HashMap<PathBuf, Option<Arc<PackageJsonSerde>>, BuildHasherDefault<FxHasher>>,
+    max_symlink_depth: usize,
+}
+
+impl<Fs: Default> Default for FsCache<Fs> {
+    fn default() -> Self {
+        Self {
+            fs: Arc::new(Fs::default()),
+            paths: HashSet::default(),
+            tsconfigs: HashMap::default(),
+            description_files: HashMap::default(),
+            max_symlink_depth: DEFAULT_MAX_SYMLINK_DEPTH,
+        }
+    }
 }
 
 impl<Fs: FileSystem> Cache for FsCache<Fs> {
@@ -50,6 +69,7 @@ impl<Fs: FileSystem> Cache for FsCache<Fs> {
     fn clear(&self) {
         self.paths.pin().clear();
         self.tsconfigs.pin().clear();
+        self.description_files.pin().clear();
     }
 
     #[allow(clippy::cast_possible_truncation)]
@@ -87,7 +107,10 @@ impl<Fs: FileSystem> Cache for FsCache<Fs> {
     }
 
     fn is_file(&self, path: &Self::Cp, ctx: &mut Ctx) -> bool {
-        if let Some(meta) = path.meta(&self.fs) {
+        if path.meta.get().is_none() {
+            ctx.record_io();
+        }
+        if let Some(meta) = path.meta(self.fs.as_ref()) {
             ctx.add_file_dependency(path.path());
             meta.is_file
         } else {
@@ -97,13 +120,44 @@ impl<Fs: FileSystem> Cache for FsCache<Fs> {
     }
 
     fn is_dir(&self, path: &Self::Cp, ctx: &mut Ctx) -> bool {
-        path.meta(&self.fs).map_or_else(
-            || {
+        if path.meta.get().is_none() {
+            ctx.record_io();
+        }
+        match path.meta(self.fs.as_ref()) {
+            Some(meta) if meta.is_dir => {
+                ctx.add_context_dependency(path.path());
+                true
+            }
+            Some(_) => false,
+            None => {
                 ctx.add_missing_dependency(path.path());
                 false
-            },
-            |meta| meta.is_dir,
-        )
+            }
+        }
+    }
+
+    fn read_dir(&self, path: &Self::Cp) -> io::Result<Vec<Self::Cp>> {
+        let entries = self.fs.read_dir(path.path())?;
+        Ok(entries.iter().map(|entry| self.value(entry)).collect())
+    }
+
+    fn read_dir_names(&self, path: &Self::Cp, ctx: &mut Ctx) -> Option<Arc<[OsString]>> {
+        if path.dir_names.get().is_none() {
+            ctx.record_io();
+        }
+        path.dir_names
+            .get_or_init(|| {
+                let mut names = self
+                    .fs
+                    .read_dir(path.path())
+                    .ok()?
+                    .iter()
+                    .filter_map(|entry| entry.file_name().map(OsStr::to_os_string))
+                    .collect::<Vec<_>>();
+                names.sort_unstable();
+                Some(names.into())
+            })
+            .clone()
     }
 
     fn get_package_json(
@@ -112,42 +166,60 @@ impl<Fs: FileSystem> Cache for FsCache<Fs> {
         options: &ResolveOptions,
         ctx: &mut Ctx,
     ) -> Result<Option<(Self::Cp, Arc<PackageJsonSerde>)>, ResolveError> {
-        // Change to `std::sync::OnceLock::get_or_try_init` when it is stable.
-        let result = path
-            .package_json
-            .get_or_try_init(|| {
-                let package_json_path = path.path.join("package.json");
-                let Ok(package_json_string) = self.fs.read_to_string(&package_json_path) else {
-                    return Ok(None);
-                };
-                let real_path = if options.symlinks {
-                    self.canonicalize(path)?.join("package.json")
-                } else {
-                    package_json_path.clone()
-                };
-                PackageJsonSerde::parse(package_json_path.clone(), real_path, &package_json_string)
-                    .map(|package_json| Some((path.clone(), (Arc::new(package_json)))))
-                    .map_err(|error| ResolveError::from_serde_json_error(package_json_path, &error))
-            })
-            .cloned();
-        // https://github.com/webpack/enhanced-resolve/blob/58464fc7cb56673c9aa849e68e6300239601e615/lib/DescriptionFileUtils.js#L68-L82
-        match &result {
-            Ok(Some((_, package_json))) => {
-                ctx.add_file_dependency(&package_json.path);
-            }
-            Ok(None) => {
-                // Avoid an allocation by making this lazy
-                if let Some(deps) = &mut ctx.missing_dependencies {
-                    deps.push(path.path.join("package.json"));
+        // package.json is still read for "exports" when no description files are configured.
+        let names = options
+            .description_files
+            .iter()
+            .map(String::as_str)
+            .chain(options.description_files.is_empty().then_some("package.json"));
+        let mut first_error = None;
+        for name in names {
+            let result = if name == "package.json" {
+                if path.package_json.get().is_none() {
+                    ctx.record_io();
                 }
-            }
-            Err(_) => {
-                if let Some(deps) = &mut ctx.file_dependencies {
-                    deps.push(path.path.join("package.json"));
+                // Change to `std::sync::OnceLock::get_or_try_init` when it is stable.
+                path.package_json
+                    .get_or_try_init(|| self.read_description_file(path, name, options))
+                    .cloned()
+            } else {
+                let description_file_path = path.path.join(name);
+                let description_files = self.description_files.pin();
+                description_files.get(&description_file_path).cloned().map_or_else(
+                    || {
+                        ctx.record_io();
+                        let result = self.read_description_file(path, name, options);
+                        if let Ok(package_json) = &result {
+                            description_files
+                                .insert(description_file_path.clone(), package_json.clone());
+                        }
+                        result
+                    },
+                    Ok,
+                )
+            };
+            // https://github.com/webpack/enhanced-resolve/blob/58464fc7cb56673c9aa849e68e6300239601e615/lib/DescriptionFileUtils.js#L68-L82
+            match result {
+                Ok(Some(package_json)) => {
+                    ctx.add_file_dependency(&package_json.path);
+                    return Ok(Some((path.clone(), package_json)));
+                }
+                Ok(None) => {
+                    // Avoid an allocation by making this lazy
+                    if let Some(deps) = &mut ctx.missing_dependencies {
+                        deps.push(path.path.join(name));
+                    }
+                }
+                Err(error) => {
+                    if let Some(deps) = &mut ctx.file_dependencies {
+                        deps.push(path.path.join(name));
+                    }
+                    // A later description file that parses is used instead.
+                    first_error.get_or_insert(error);
                 }
             }
         }
-        result
+        first_error.map_or(Ok(None), Err)
     }
 
     fn get_tsconfig<F: FnOnce(&mut TsConfigSerde) -> Result<(), ResolveError>>(
@@ -179,7 +251,7 @@ impl<Fs: FileSystem> Cache for FsCache<Fs> {
                 ResolveError::from_serde_json_error(tsconfig_path.to_path_buf(), &error)
             })?;
         callback(&mut tsconfig)?;
-        tsconfig.expand_template_variables();
+        tsconfig.build();
         let tsconfig = Arc::new(tsconfig);
         tsconfigs.insert(path.to_path_buf(), Arc::clone(&tsconfig));
         Ok(tsconfig)
@@ -189,7 +261,7 @@ impl<Fs: FileSystem> Cache for FsCache<Fs> {
 impl<Fs: FileSystem> FsCache<Fs> {
     pub fn new(fs: Fs) -> Self {
         Self {
-            fs,
+            fs: Arc::new(fs),
             paths: HashSet::builder()
                 .hasher(BuildHasherDefault::default())
                 .resize_mode(papaya::ResizeMode::Blocking)
@@ -200,59 +272,122 @@ impl<Fs: FileSystem> FsCache<Fs> {
                 .resize_mode(papaya::ResizeMode::Blocking)
                 .collector(seize::Collector::new().epoch_frequency(None))
                 .build(),
+            description_files: HashMap::builder()
+                .hasher(BuildHasherDefault::default())
+                .resize_mode(papaya::ResizeMode::Blocking)
+                .collector(seize::Collector::new().epoch_frequency(None))
+                .build(),
+            max_symlink_depth: DEFAULT_MAX_SYMLINK_DEPTH,
         }
     }
 
+    /// Sets the maximum number of symbolic links followed while canonicalizing a path.
+    ///
+    /// [ResolveError::TooManySymlinks] is returned when a chain of symbolic links exceeds this depth.
+    ///
+    /// Defaults to `40`.
+    #[must_use]
+    pub const fn with_max_symlink_depth(mut self, depth: usize) -> Self {
+        self.max_symlink_depth = depth;
+        self
+    }
+
+    /// Reads and parses the description file `name` in the directory `path`.
+    fn read_description_file(
+        &self,
+        path: &FsCachedPath,
+        name: &str,
+        options: &ResolveOptions,
+    ) -> Result<Option<Arc<PackageJsonSerde>>, ResolveError> {
+        let package_json_path = path.path.join(name);
+        let Ok(package_json_string) = self.fs.read_to_string(&package_json_path) else {
+            return Ok(None);
+        };
+        let real_path = if options.follow_symlinks(path.path()) && !options.lazy_symlinks {
+            self.canonicalize(path)?.join(name)
+        } else {
+            package_json_path.clone()
+        };
+        let package_json =
+            PackageJsonSerde::parse(package_json_path, real_path, &package_json_string)?;
+        Ok(Some(Arc::new(package_json)))
+    }
+
     /// Returns the canonical path, resolving all symbolic links.
     ///
     /// <https://github.com/parcel-bundler/parcel/blob/4d27ec8b8bd1792f536811fef86e74a31fa0e704/crates/parcel-resolver/src/cache.rs#L232>
     fn canonicalize_impl(&self, path: &FsCachedPath) -> Result<FsCachedPath, ResolveError> {
+        self.canonicalize_with_depth(path, 0)
+    }
+
+    /// `depth` is the number of symbolic links followed so far.
+    fn canonicalize_with_depth(
+        &self,
+        path: &FsCachedPath,
+        depth: usize,
+    ) -> Result<FsCachedPath, ResolveError> {
         // Check if this thread is already canonicalizing. If so, we have found a circular symlink.
-        // If a different thread is canonicalizing, OnceLock will queue this thread to wait for the result.
+        // A different thread canonicalizing the same path computes the same result.
         let tid = THREAD_ID.with(|t| *t);
         if path.canonicalizing.load(Ordering::Acquire) == tid {
             return Err(io::Error::new(io::ErrorKind::NotFound, "Circular symlink").into());
         }
 
-        path.canonicalized
-            .get_or_init(|| {
-                path.canonicalizing.store(tid, Ordering::Release);
-
-                let res = path.parent().map_or_else(
-                    || Ok(path.normalize_root(self)),
-                    |parent| {
-                        self.canonicalize_impl(parent).and_then(|parent_canonical| {
-                            let normalized = parent_canonical.normalize_with(
-                                path.path().strip_prefix(parent.path()).unwrap(),
-                                self,
+        // Mirrors `ELOOP`, checked before the memoized result because it depends on the chain
+        // of links leading here rather than on the path.
+        if depth >= self.max_symlink_depth
+            && self.fs.symlink_metadata(path.path()).is_ok_and(|m| m.is_symlink)
+        {
+            return Err(ResolveError::TooManySymlinks(path.to_path_buf()));
+        }
+
+        if let Some(canonicalized) = path.canonicalized.get() {
+            return canonicalized.clone();
+        }
+
+        path.canonicalizing.store(tid, Ordering::Release);
+
+        let res = path.parent().map_or_else(
+            || Ok(path.normalize_root(self)),
+            |parent| {
+                self.canonicalize_with_depth(parent, depth).and_then(|parent_canonical| {
+                    let normalized = parent_canonical
+                        .normalize_with(path.path().strip_prefix(parent.path()).unwrap(), self);
+
+                    if self.fs.symlink_metadata(path.path()).is_ok_and(|m| m.is_symlink) {
+                        let link = self.fs.read_link(normalized.path())?;
+                        if link.is_absolute() {
+                            return self.canonicalize_with_depth(
+                                &self.value(&link.normalize()),
+                                depth + 1,
+                            );
+                        } else if let Some(dir) = normalized.parent() {
+                            // Symlink is relative `../../foo.js`, use the path directory
+                            // to resolve this symlink.
+                            return self.canonicalize_with_depth(
+                                &dir.normalize_with(&link, self),
+                                depth + 1,
                             );
+                        }
+                        debug_assert!(
+                            false,
+                            "Failed to get path parent for {:?}.",
+                            normalized.path()
+                        );
+                    }
 
-                            if self.fs.symlink_metadata(path.path()).is_ok_and(|m| m.is_symlink) {
-                                let link = self.fs.read_link(normalized.path())?;
-                                if link.is_absolute() {
-                                    return self.canonicalize_impl(&self.value(&link.normalize()));
-                                } else if let Some(dir) = normalized.parent() {
-                                    // Symlink is relative `../../foo.js`, use the path directory
-                                    // to resolve this symlink.
-                                    return self
-                                        .canonicalize_impl(&dir.normalize_with(&link, self));
-                                }
-                                debug_assert!(
-                                    false,
-                                    "Failed to get path parent for {:?}.",
-                                    normalized.path()
-                                );
-                            }
+                    Ok(normalized)
+                })
+            },
+        );
 
-                            Ok(normalized)
-                        })
-                    },
-                );
+        path.canonicalizing.store(0, Ordering::Release);
 
-                path.canonicalizing.store(0, Ordering::Release);
-                res
-            })
-            .clone()
+        // Not memoized, the same path can be reached by a shorter chain of links.
+        if matches!(res, Err(ResolveError::TooManySymlinks(_))) {
+            return res;
+        }
+        path.canonicalized.get_or_init(|| res).clone()
     }
 }
 
@@ -267,7 +402,8 @@ pub struct CachedPathImpl {
     canonicalized: OnceLock<Result<FsCachedPath, ResolveError>>,
     canonicalizing: AtomicU64,
     node_modules: OnceLock<Option<FsCachedPath>>,
-    package_json: OnceLock<Option<(FsCachedPath, Arc<PackageJsonSerde>)>>,
+    package_json: OnceLock<Option<Arc<PackageJsonSerde>>>,
+    dir_names: OnceLock<Option<Arc<[OsString]>>>,
 }
 
 impl CachedPathImpl {
@@ -281,6 +417,7 @@ impl CachedPathImpl {
             canonicalizing: AtomicU64::new(0),
             node_modules: OnceLock::new(),
             package_json: OnceLock::new(),
+            dir_names: OnceLock::new(),
         }
     }
 }
@@ -317,7 +454,14 @@ impl CachedPath for FsCachedPath {
     }
 
     fn cached_node_modules<C: Cache<Cp = Self>>(&self, cache: &C, ctx: &mut Ctx) -> Option<Self> {
-        self.node_modules.get_or_init(|| self.module_directory("node_modules", cache, ctx)).clone()
+        let node_modules =
+            self.node_modules.get_or_init(|| self.module_directory("node_modules", cache, ctx));
+        // The memoized lookup skips `is_dir`, record the dependency for every resolve.
+        match node_modules {
+            Some(node_modules) => ctx.add_context_dependency(node_modules.path()),
+            None => ctx.add_missing_dependency(&self.path.join("node_modules")),
+        }
+        node_modules.clone()
     }
 
     /// Find package.json of a path by traversing parent directories.
@@ -342,8 +486,13 @@ impl CachedPath for FsCachedPath {
         }
         let mut cache_value = Some(cache_value);
         while let Some(cv) = cache_value {
-            if let Some(package_json) = cache.get_package_json(cv, options, ctx)? {
-                return Ok(Some(package_json));
+            match cache.get_package_json(cv, options, ctx) {
+                Ok(Some(package_json)) => return Ok(Some(package_json)),
+                Ok(None) => {}
+                Err(ResolveError::JSON(error)) if options.skip_invalid_package_json => {
+                    ctx.add_diagnostic(ResolveDiagnostic::InvalidPackageJsonSkipped(error));
+                }
+                Err(error) => return Err(error),
             }
             cache_value = cv.parent.as_ref();
         }
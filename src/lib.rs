@@ -50,16 +50,24 @@
 mod builtins;
 mod cache;
 pub mod context;
+mod dependency;
+mod diagnostic;
+mod engines;
 mod error;
 #[cfg(feature = "fs_cache")]
 mod file_system;
 #[cfg(feature = "fs_cache")]
+mod file_system_recorder;
+mod files_field;
+#[cfg(feature = "fs_cache")]
 mod fs_cache;
 mod options;
 mod package_json;
 #[cfg(feature = "fs_cache")]
 mod package_json_serde;
 mod path;
+#[cfg(feature = "fs_cache")]
+mod persistent_cache;
 mod resolution;
 mod specifier;
 mod tsconfig;
@@ -72,17 +80,20 @@ mod tests;
 use std::{
     borrow::Cow,
     cmp::Ordering,
-    ffi::OsStr,
+    collections::BTreeSet,
+    ffi::{OsStr, OsString},
     fmt,
     path::{Component, Path, PathBuf},
     sync::Arc,
 };
 
+use regex::Regex;
 use rustc_hash::FxHashSet;
 
 #[cfg(feature = "fs_cache")]
 pub use crate::{
     file_system::{FileMetadata, FileSystem, FileSystemOs},
+    file_system_recorder::{FsCall, RecordingFileSystem, ReplayFileSystem},
     fs_cache::{FsCache, FsCachedPath},
     package_json_serde::PackageJsonSerde,
     tsconfig_serde::{CompilerOptionsSerde, ExtendsField, ProjectReferenceSerde, TsConfigSerde},
@@ -94,17 +105,20 @@ pub type FsResolution = Resolution<FsCache<FileSystemOs>>;
 pub use crate::{
     builtins::NODEJS_BUILTINS,
     cache::{Cache, CachedPath},
-    error::{JSONError, ResolveError, SpecifierError},
+    dependency::DependencyEdge,
+    diagnostic::ResolveDiagnostic,
+    error::{JSONError, ResolveError, ResolveErrorKind, SpecifierError},
     options::{
-        Alias, AliasValue, EnforceExtension, ResolveOptions, Restriction, TsconfigOptions,
-        TsconfigReferences,
+        Alias, AliasValue, BuildMode, EnforceExtension, EntrySelector, PackageEntry, PostResolve,
+        PostResolveAction, ResolveOptions, Restriction, SymlinksFilter, TsconfigOptions,
+        TsconfigReferences, TypeScriptOptions,
     },
     package_json::{
         ImportsExportsArray, ImportsExportsEntry, ImportsExportsKind, ImportsExportsMap,
         PackageJson, PackageType,
     },
     path::PathUtil,
-    resolution::Resolution,
+    resolution::{Resolution, ResolveSource},
     tsconfig::{CompilerOptions, CompilerOptionsPathsMap, ProjectReference, TsConfig},
 };
 use crate::{context::ResolveContext as Ctx, path::SLASH_START, specifier::Specifier};
@@ -119,6 +133,25 @@ pub struct ResolveContext {
 
     /// Dependencies that was not found on file system
     pub missing_dependencies: FxHashSet<PathBuf>,
+
+    /// Directories that was found on file system, for watching directories being removed or replaced
+    pub context_dependencies: FxHashSet<PathBuf>,
+
+    /// Non-fatal findings reported while resolving, such as an ignored "main" field.
+    pub diagnostics: Vec<ResolveDiagnostic>,
+
+    /// Package dependency graph edges, recorded for each bare specifier resolved into another package.
+    ///
+    /// Recording is opt-in, set this to `Some(vec![])` before calling [ResolverGeneric::resolve_with_context].
+    pub dependency_edges: Option<Vec<DependencyEdge>>,
+
+    /// Every condition key found in the condition objects of "exports" and "imports" that were visited,
+    /// including the ones that did not match [ResolveOptions::condition_names].
+    ///
+    /// Useful for reporting dual package hazards, e.g. the `require` build was resolved while `import` is also available.
+    ///
+    /// Recording is opt-in, set this to `Some(vec![])` before calling [ResolverGeneric::resolve_with_context].
+    pub available_conditions: Option<Vec<String>>,
 }
 
 /// Resolver with the current operating system as the file system
@@ -129,6 +162,11 @@ pub type Resolver = ResolverGeneric<FsCache<FileSystemOs>>;
 pub struct ResolverGeneric<C: Cache> {
     options: ResolveOptions,
     cache: Arc<C>,
+    /// Compiled [Restriction::RegExp] patterns, by index into [ResolveOptions::restrictions].
+    /// Compile errors are returned by resolves.
+    restriction_regexes: Vec<Option<Result<Regex, String>>>,
+    #[cfg(feature = "fs_cache")]
+    persistent_cache: Option<Arc<persistent_cache::PersistentCache>>,
 }
 
 impl<C: Cache> fmt::Debug for ResolverGeneric<C> {
@@ -146,19 +184,87 @@ impl<C: Cache + Default> Default for ResolverGeneric<C> {
 impl<C: Cache + Default> ResolverGeneric<C> {
     #[must_use]
     pub fn new(options: ResolveOptions) -> Self {
-        Self { options: options.sanitize(), cache: Arc::new(C::default()) }
+        Self::new_with_cache(Arc::new(C::default()), options)
+    }
+}
+
+#[cfg(feature = "fs_cache")]
+impl<Fs: FileSystem + Default + 'static> ResolverGeneric<FsCache<Fs>> {
+    /// Create a resolver backed by the on-disk cache file at `path`, for repeating the same
+    /// resolutions across processes such as CI runs.
+    ///
+    /// Resolutions are looked up in the file before running the resolution algorithm,
+    /// the checks after resolving such as [ResolveOptions::restrictions] still run.
+    /// An entry is discarded when a file it was resolved from has a different modification time,
+    /// a file that was missing exists or a directory that was found is gone.
+    /// The whole file is discarded when it was written with other options.
+    /// The file and the dependencies are accessed through the [FileSystem] of the resolver.
+    ///
+    /// The file is not used when [ResolveOptions::post_resolve], [ResolveOptions::entry_selector] or
+    /// [ResolveOptions::symlinks_filter] is set, because hooks cannot be fingerprinted and
+    /// resolvers with different hooks would serve each other's resolutions.
+    ///
+    /// Diagnostics are not reported for resolutions served from the file.
+    /// Calls recording [ResolveContext::dependency_edges] or [ResolveContext::available_conditions]
+    /// always run the resolution algorithm.
+    ///
+    /// Call [ResolverGeneric::save_persistent_cache] to write new resolutions back.
+    #[must_use]
+    pub fn with_persistent_cache<P: AsRef<Path>>(path: P, options: ResolveOptions) -> Self {
+        let mut resolver = Self::new(options);
+        if resolver.options.has_hooks() {
+            return resolver;
+        }
+        let fs: Arc<dyn FileSystem> = Arc::clone(&resolver.cache.fs) as _;
+        let cache = persistent_cache::PersistentCache::load(
+            path.as_ref().to_path_buf(),
+            &resolver.options,
+            fs,
+        );
+        resolver.persistent_cache = Some(Arc::new(cache));
+        resolver
     }
 }
 
 impl<C: Cache> ResolverGeneric<C> {
     pub fn new_with_cache(cache: Arc<C>, options: ResolveOptions) -> Self {
-        Self { cache, options: options.sanitize() }
+        let options = options.sanitize();
+        let restriction_regexes = options
+            .restrictions
+            .iter()
+            .map(|restriction| match restriction {
+                Restriction::Path(_) => None,
+                Restriction::RegExp(pattern) => {
+                    Some(Regex::new(pattern).map_err(|error| error.to_string()))
+                }
+            })
+            .collect();
+        Self {
+            options,
+            cache,
+            restriction_regexes,
+            #[cfg(feature = "fs_cache")]
+            persistent_cache: None,
+        }
     }
 
     /// Clone the resolver using the same underlying cache.
+    ///
+    /// The persistent cache is not shared, because it only holds resolutions of the original options.
     #[must_use]
     pub fn clone_with_options(&self, options: ResolveOptions) -> Self {
-        Self { options: options.sanitize(), cache: Arc::clone(&self.cache) }
+        Self::new_with_cache(Arc::clone(&self.cache), options)
+    }
+
+    /// Clone the resolver using the same underlying cache, only replacing
+    /// [ResolveOptions::condition_names].
+    #[must_use]
+    pub fn clone_with_condition_names(&self, names: &[&str]) -> Self {
+        let options = ResolveOptions {
+            condition_names: names.iter().map(ToString::to_string).collect(),
+            ..self.options.clone()
+        };
+        self.clone_with_options(options)
     }
 
     /// Returns the options.
@@ -167,11 +273,37 @@ impl<C: Cache> ResolverGeneric<C> {
         &self.options
     }
 
+    /// Returns a fingerprint of the options for keying caches of resolutions,
+    /// so that resolvers with different options do not share entries.
+    ///
+    /// Every field of [ResolveOptions] participates, except [ResolveOptions::abort_signal].
+    /// Hooks such as [ResolveOptions::post_resolve], [ResolveOptions::entry_selector] and
+    /// [ResolveOptions::symlinks_filter] only participate by whether they are set,
+    /// so resolvers with different hooks can have the same fingerprint.
+    /// Include an identifier of the hooks in the key when caching their resolutions,
+    /// [ResolverGeneric::with_persistent_cache] is not used with hooks for this reason.
+    ///
+    /// The fingerprint is stable for the same crate version, it is not a persistent format.
+    #[must_use]
+    pub fn options_fingerprint(&self) -> u64 {
+        self.options.fingerprint()
+    }
+
     /// Clear the underlying cache.
     pub fn clear_cache(&self) {
         self.cache.clear();
     }
 
+    /// Write the resolutions of [ResolverGeneric::with_persistent_cache] to its file.
+    ///
+    /// # Errors
+    ///
+    /// * Failing to write the file.
+    #[cfg(feature = "fs_cache")]
+    pub fn save_persistent_cache(&self) -> std::io::Result<()> {
+        self.persistent_cache.as_ref().map_or(Ok(()), |cache| cache.save())
+    }
+
     /// Resolve `specifier` at an absolute path to a `directory`.
     ///
     /// A specifier is the string passed to require or import, i.e. `require("specifier")` or `import "specifier"`.
@@ -192,6 +324,236 @@ impl<C: Cache> ResolverGeneric<C> {
         self.resolve_tracing(directory.as_ref(), specifier, &mut ctx)
     }
 
+    /// Resolve each of `specifiers` at an absolute path to a `directory`, e.g. all imports of a file.
+    ///
+    /// Returns one result per specifier in the order of `specifiers`, each the same as [ResolverGeneric::resolve].
+    /// Builtin modules are reported before touching the file system when they cannot be aliased,
+    /// i.e. without [ResolveOptions::alias], [ResolveOptions::fallback] and tsconfig paths.
+    pub fn resolve_many<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        specifiers: &[&str],
+    ) -> Vec<Result<Resolution<C>, ResolveError>> {
+        let directory = directory.as_ref();
+        let builtins_first = self.options.builtin_modules
+            && self.options.alias.is_empty()
+            && self.options.fallback.is_empty()
+            && self.options.tsconfig.is_none()
+            && !self.options.tsconfig_auto_discovery
+            && self.options.typescript.is_none();
+        specifiers
+            .iter()
+            .map(|specifier| {
+                self.resolve_tracing_with(directory, specifier, || {
+                    if builtins_first {
+                        self.require_core(specifier)?;
+                    }
+                    self.resolve_persistent_or_impl(directory, specifier, &mut Ctx::default())
+                })
+            })
+            .collect()
+    }
+
+    /// Resolve `specifier` at an absolute path to a `directory` with `extensions` in place of
+    /// [ResolveOptions::extensions] for this call only.
+    ///
+    /// This is useful for resolving different file types, e.g. a CSS `@import` and a JavaScript `import`,
+    /// with the same resolver and cache.
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolveError]
+    pub fn resolve_with_extensions<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        specifier: &str,
+        extensions: &[&str],
+    ) -> Result<Resolution<C>, ResolveError> {
+        let mut ctx = Ctx::default();
+        ctx.with_extensions(extensions);
+        self.resolve_tracing(directory.as_ref(), specifier, &mut ctx)
+    }
+
+    /// Resolve `specifier` at an absolute path to a `directory`, also reporting whether the
+    /// resolution was served entirely from the cache.
+    ///
+    /// This is useful for measuring warm and cold resolves.
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolveError]
+    pub fn resolve_with_source<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        specifier: &str,
+    ) -> Result<(Resolution<C>, ResolveSource), ResolveError> {
+        let mut ctx = Ctx::default();
+        let resolution = self.resolve_tracing(directory.as_ref(), specifier, &mut ctx)?;
+        let source = if ctx.io { ResolveSource::Io } else { ResolveSource::Cached };
+        Ok((resolution, source))
+    }
+
+    /// Resolve `specifier` against each of `directories` in order, returning the first successful resolution.
+    ///
+    /// This is useful when a file may belong to multiple projects and the import is ambiguous.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::NotFoundInAny] with the error from each directory when none of them resolves.
+    pub fn resolve_from_any<P: AsRef<Path>>(
+        &self,
+        directories: &[P],
+        specifier: &str,
+    ) -> Result<Resolution<C>, ResolveError> {
+        let mut errors = Vec::with_capacity(directories.len());
+        for directory in directories {
+            match self.resolve(directory, specifier) {
+                Ok(resolution) => return Ok(resolution),
+                Err(error) => errors.push(error),
+            }
+        }
+        Err(ResolveError::NotFoundInAny(specifier.to_string(), errors))
+    }
+
+    /// Resolve `specifier` and return every candidate that resolves, in priority order.
+    ///
+    /// A specifier such as `./utils` may match both the file `utils.js` and the directory `utils/index.js`,
+    /// where the file takes precedence. Tooling can use this method to report such shadowing.
+    ///
+    /// The first resolution is always the one returned by [ResolverGeneric::resolve].
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolveError], the error is from resolving the highest priority candidate.
+    pub fn resolve_ambiguous<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        specifier: &str,
+    ) -> Result<Vec<Resolution<C>>, ResolveError> {
+        let directory = directory.as_ref();
+        let resolution = self.resolve(directory, specifier)?;
+        let parsed = Specifier::parse(specifier).map_err(ResolveError::Specifier)?;
+        if parsed.path().ends_with('/') {
+            return Ok(vec![resolution]);
+        }
+        // A trailing slash skips loading as a file, which yields the directory candidate.
+        let directory_specifier = format!(
+            "{}/{}{}",
+            parsed.path(),
+            parsed.query.unwrap_or_default(),
+            parsed.fragment.unwrap_or_default()
+        );
+        let mut resolutions = vec![resolution];
+        if let Ok(resolution) = self.resolve(directory, &directory_specifier) {
+            if resolution.path() != resolutions[0].path() {
+                resolutions.push(resolution);
+            }
+        }
+        Ok(resolutions)
+    }
+
+    /// Whether `specifier` at an absolute path to a `directory` resolves to the file at `expected`,
+    /// e.g. for verifying that a rewritten import still points to the same file.
+    ///
+    /// `expected` is canonicalized like the resolved path when [ResolveOptions::symlinks] is enabled,
+    /// only when the paths differ. The query and fragment of the resolution are not compared.
+    /// Returns `false` when the specifier cannot be resolved.
+    pub fn would_resolve_to<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        specifier: &str,
+        expected: &Path,
+    ) -> bool {
+        let Ok(resolution) = self.resolve(directory, specifier) else {
+            return false;
+        };
+        if resolution.path() == expected {
+            return true;
+        }
+        self.load_realpath(&self.cache.value(expected))
+            .is_ok_and(|expected| resolution.path() == expected)
+    }
+
+    /// Returns the canonical version of an absolute `path`, resolving all symbolic links,
+    /// e.g. for normalizing an entry point that is not the result of a resolution.
+    ///
+    /// The cache and symlink handling are shared with resolving, the `\\?\` prefix is stripped on Windows.
+    /// Unlike resolving, [ResolveOptions::symlinks] is not consulted.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::TooManySymlinks] when more symbolic links than the limit of the cache are followed.
+    /// * [ResolveError::IOError] for circular symbolic links.
+    pub fn canonicalize<P: AsRef<Path>>(&self, path: P) -> Result<PathBuf, ResolveError> {
+        self.cache.canonicalize(&self.cache.value(path.as_ref()))
+    }
+
+    /// Returns the module directories searched for bare specifiers from the directory `from`, in lookup order.
+    ///
+    /// This is the equivalent of Node.js's `require.resolve.paths`,
+    /// covering [ResolveOptions::flat_modules] and [ResolveOptions::modules].
+    /// Paths are computed without accessing the file system, so they may not exist.
+    /// Yarn Plug'n'Play lookups are not included.
+    pub fn module_search_paths<P: AsRef<Path>>(&self, from: P) -> Vec<PathBuf> {
+        let from = from.as_ref();
+        let walk = self.options.flat_modules.is_none() || self.options.flat_modules_fallback;
+        let mut paths: Vec<PathBuf> = self.options.flat_modules.iter().cloned().collect();
+        for module_name in self.options.modules.iter().filter(|_| walk) {
+            if Path::new(module_name).is_absolute() {
+                paths.push(PathBuf::from(module_name));
+                continue;
+            }
+            for dir in from.ancestors() {
+                // Same as `get_module_directory`: a module directory is not nested in itself.
+                let path = if dir.components().next_back()
+                    == Some(Component::Normal(OsStr::new(module_name)))
+                {
+                    dir.to_path_buf()
+                } else {
+                    dir.join(module_name)
+                };
+                paths.push(path);
+            }
+        }
+        let mut seen = FxHashSet::default();
+        paths.retain(|path| seen.insert(path.clone()));
+        paths
+    }
+
+    /// Splits a bare specifier into its package name and subpath with the same scoped package handling as the resolver.
+    ///
+    /// The subpath is empty or starts with `/`.
+    /// Returns `None` for specifiers that are not a valid package name, e.g. empty, relative, absolute,
+    /// `#` imports, a scope without a name, or a name containing `%` or `\`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::Resolver;
+    ///
+    /// assert_eq!(Resolver::parse_bare_specifier("lodash"), Some(("lodash", "")));
+    /// assert_eq!(Resolver::parse_bare_specifier("lodash/fp/map"), Some(("lodash", "/fp/map")));
+    /// assert_eq!(Resolver::parse_bare_specifier("@babel/core"), Some(("@babel/core", "")));
+    /// assert_eq!(Resolver::parse_bare_specifier("@babel/core/lib/index.js"), Some(("@babel/core", "/lib/index.js")));
+    /// assert_eq!(Resolver::parse_bare_specifier(""), None);
+    /// assert_eq!(Resolver::parse_bare_specifier("./lodash"), None);
+    /// assert_eq!(Resolver::parse_bare_specifier("@babel"), None);
+    /// ```
+    #[must_use]
+    pub fn parse_bare_specifier(specifier: &str) -> Option<(&str, &str)> {
+        let (package_name, subpath) = Self::parse_package_specifier(specifier);
+        // https://github.com/nodejs/node/blob/8f0f17e1e3b6c4e58ce748e06343c5304062c491/lib/internal/modules/esm/resolve.js#L705
+        let valid_name = package_name.strip_prefix('@').map_or_else(
+            || !package_name.is_empty() && !package_name.starts_with(['.', '/', '#']),
+            |scoped| {
+                scoped
+                    .split_once('/')
+                    .is_some_and(|(scope, name)| !scope.is_empty() && !name.is_empty())
+            },
+        );
+        (valid_name && !package_name.contains(['%', '\\'])).then_some((package_name, subpath))
+    }
+
     /// Resolve `tsconfig`.
     ///
     /// The path can be:
@@ -208,6 +570,46 @@ impl<C: Cache> ResolverGeneric<C> {
         self.load_tsconfig(true, path, &TsconfigReferences::Auto)
     }
 
+    /// Find the nearest `tsconfig.json` from `directory` and its ancestors.
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolveError]
+    pub fn find_tsconfig<P: AsRef<Path>>(
+        &self,
+        directory: P,
+    ) -> Result<Option<Arc<C::Tc>>, ResolveError> {
+        let cached_path = self.cache.value(directory.as_ref());
+        self.find_tsconfig_impl(&cached_path, &mut Ctx::default())
+    }
+
+    /// Find the nearest `package.json` from `directory` and its ancestors that has the top-level `field` set,
+    /// skipping the ones without it, e.g. the nearest `package.json` declaring "type".
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolveError]
+    pub fn find_package_json_with_field<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        field: &str,
+    ) -> Result<Option<Arc<C::Pj>>, ResolveError> {
+        let mut ctx = Ctx::default();
+        let mut cached_path = Some(self.cache.value(directory.as_ref()));
+        while let Some(path) = cached_path {
+            let Some((package_url, package_json)) =
+                path.find_package_json(&self.options, self.cache.as_ref(), &mut ctx)?
+            else {
+                return Ok(None);
+            };
+            if package_json.has_field(field) {
+                return Ok(Some(package_json));
+            }
+            cached_path = package_url.parent().cloned();
+        }
+        Ok(None)
+    }
+
     /// Resolve `specifier` at absolute `path` with [ResolveContext]
     ///
     /// # Errors
@@ -221,6 +623,13 @@ impl<C: Cache> ResolverGeneric<C> {
     ) -> Result<Resolution<C>, ResolveError> {
         let mut ctx = Ctx::default();
         ctx.init_file_dependencies();
+        ctx.init_diagnostics();
+        if resolve_context.dependency_edges.is_some() {
+            ctx.init_dependency_edges();
+        }
+        if resolve_context.available_conditions.is_some() {
+            ctx.init_available_conditions();
+        }
         let result = self.resolve_tracing(directory.as_ref(), specifier, &mut ctx);
         if let Some(deps) = &mut ctx.file_dependencies {
             resolve_context.file_dependencies.extend(deps.drain(..));
@@ -228,19 +637,272 @@ impl<C: Cache> ResolverGeneric<C> {
         if let Some(deps) = &mut ctx.missing_dependencies {
             resolve_context.missing_dependencies.extend(deps.drain(..));
         }
+        if let Some(deps) = &mut ctx.context_dependencies {
+            resolve_context.context_dependencies.extend(deps.drain(..));
+        }
+        if let Some(diagnostics) = &mut ctx.diagnostics {
+            resolve_context.diagnostics.append(diagnostics);
+        }
+        if let (Some(edges), Some(recorded)) =
+            (&mut resolve_context.dependency_edges, &mut ctx.dependency_edges)
+        {
+            edges.append(recorded);
+        }
+        if let (Some(conditions), Some(recorded)) =
+            (&mut resolve_context.available_conditions, &mut ctx.available_conditions)
+        {
+            for condition in recorded.drain(..) {
+                if !conditions.contains(&condition) {
+                    conditions.push(condition);
+                }
+            }
+        }
         result
     }
 
+    /// Resolve all entry points of the package `package_name` found from `directory`.
+    ///
+    /// Returns `(subpath, path)` pairs sorted by subpath, e.g. `(".", "/node_modules/foo/index.js")`
+    /// and `("./feature", "/node_modules/foo/feature.js")`.
+    ///
+    /// Every subpath of the "exports" field is resolved with the configured conditions,
+    /// wildcard subpaths are expanded by reading the target directory,
+    /// subpaths that fail to resolve are skipped.
+    /// The main entry is used when the package does not have an "exports" field.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::NotFound] when the package is not found.
+    /// * See [ResolveError]
+    pub fn resolve_package_entries<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        package_name: &str,
+    ) -> Result<Vec<(String, PathBuf)>, ResolveError> {
+        let directory = directory.as_ref();
+        let mut ctx = Ctx::default();
+        let cached_path = self.cache.value(directory);
+        let package_json = self
+            .find_package_directory(&cached_path, package_name, &mut ctx)
+            .map(|package_url| self.cache.get_package_json(&package_url, &self.options, &mut ctx))
+            .transpose()?
+            .flatten();
+        let Some((package_url, package_json)) = package_json else {
+            return Err(ResolveError::NotFound(package_name.to_string()));
+        };
+
+        let mut subpaths = BTreeSet::new();
+        let mut has_exports = false;
+        for exports in package_json.exports_fields(&self.options.exports_fields) {
+            has_exports = true;
+            let Some(map) =
+                exports.as_map().filter(|map| map.keys().any(|key| key.starts_with('.')))
+            else {
+                // String, array or conditions only exports
+                subpaths.insert(".".to_string());
+                continue;
+            };
+            for (key, target) in map.iter() {
+                if key.ends_with('/') {
+                    // Deprecated folder mappings cannot be enumerated.
+                    continue;
+                }
+                if key.contains('*') {
+                    self.expand_exports_pattern(
+                        &package_url,
+                        key,
+                        &target,
+                        &mut subpaths,
+                        &mut ctx,
+                    );
+                } else {
+                    subpaths.insert(key.to_string());
+                }
+            }
+        }
+        if !has_exports {
+            subpaths.insert(".".to_string());
+        }
+
+        let entries = subpaths
+            .into_iter()
+            .filter_map(|subpath| {
+                let specifier = format!("{package_name}{}", &subpath[1..]);
+                let resolution = self.resolve(directory, &specifier).ok()?;
+                Some((subpath, resolution.into_path_buf()))
+            })
+            .collect();
+        Ok(entries)
+    }
+
+    /// Returns the subpaths exposed by the "exports" field of the package in `package_dir`, sorted,
+    /// e.g. `[".", "./feature", "./utils/*"]`.
+    ///
+    /// Only the keys are listed, conditions and targets are not evaluated.
+    /// Wildcard and folder mapping keys are returned as is.
+    /// Exports without subpath keys, such as a string or a conditions object, expose `"."`.
+    /// A package without an "exports" field returns an empty list.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::NotFound] when `package_dir` does not have a package.json.
+    /// * [ResolveError::InvalidPackageConfig] when "exports" mixes keys starting with "." and other keys.
+    /// * See [ResolveError]
+    pub fn package_exports<P: AsRef<Path>>(
+        &self,
+        package_dir: P,
+    ) -> Result<Vec<String>, ResolveError> {
+        let package_dir = package_dir.as_ref();
+        let mut ctx = Ctx::default();
+        let cached_path = self.cache.value(package_dir);
+        let Some((package_url, package_json)) =
+            self.cache.get_package_json(&cached_path, &self.options, &mut ctx)?
+        else {
+            return Err(ResolveError::NotFound(package_dir.to_string_lossy().to_string()));
+        };
+        let mut subpaths = BTreeSet::new();
+        for exports in package_json.exports_fields(&self.options.exports_fields) {
+            Self::validate_exports_keys(package_url.path(), &exports)?;
+            match exports.as_map() {
+                Some(map) if map.keys().any(|key| key.starts_with('.')) => {
+                    subpaths.extend(map.keys().map(ToString::to_string));
+                }
+                Some(map) if map.keys().next().is_none() => {}
+                _ => {
+                    subpaths.insert(".".to_string());
+                }
+            }
+        }
+        Ok(subpaths.into_iter().collect())
+    }
+
+    /// Finds `DIR/node_modules/package_name` by walking up the parent directories.
+    fn find_package_directory(
+        &self,
+        cached_path: &C::Cp,
+        package_name: &str,
+        ctx: &mut Ctx,
+    ) -> Option<C::Cp> {
+        for module_name in &self.options.modules {
+            for cached_path in std::iter::successors(Some(cached_path), |p| p.parent()) {
+                let Some(cached_path) = self.get_module_directory(cached_path, module_name, ctx)
+                else {
+                    continue;
+                };
+                let cached_path = cached_path.normalize_with(package_name, self.cache.as_ref());
+                if self.cache.is_dir(&cached_path, ctx) {
+                    return Some(cached_path);
+                }
+            }
+        }
+        None
+    }
+
+    /// Expands the wildcard exports `key` by matching its targets against the files on disk.
+    fn expand_exports_pattern<'a, Io: ImportsExportsEntry<'a>>(
+        &self,
+        package_url: &C::Cp,
+        key: &str,
+        target: &Io,
+        subpaths: &mut BTreeSet<String>,
+        ctx: &mut Ctx,
+    ) {
+        fn collect_targets<'a, Io: ImportsExportsEntry<'a>>(
+            target: &Io,
+            targets: &mut Vec<&'a str>,
+        ) {
+            if let Some(target) = target.as_string() {
+                targets.push(target);
+            } else if let Some(array) = target.as_array() {
+                for target in array.iter() {
+                    collect_targets(&target, targets);
+                }
+            } else if let Some(map) = target.as_map() {
+                for (_, target) in map.iter() {
+                    collect_targets(&target, targets);
+                }
+            }
+        }
+
+        let mut targets = vec![];
+        collect_targets(target, &mut targets);
+        for target in targets {
+            let Some((prefix, suffix)) = target.split_once('*') else { continue };
+            if !prefix.starts_with("./") {
+                continue;
+            }
+            // List files under the directory leading up to the wildcard.
+            let base = &prefix[..=prefix.rfind('/').unwrap_or_default()];
+            let mut files = vec![];
+            self.read_files_recursively(
+                &package_url.normalize_with(base, self.cache.as_ref()),
+                &mut files,
+                ctx,
+            );
+            for file in files {
+                let Ok(relative) = file.path().strip_prefix(package_url.path()) else { continue };
+                let relative = format!("./{}", relative.to_string_lossy().replace('\\', "/"));
+                if relative.len() < prefix.len() + suffix.len()
+                    || !relative.starts_with(prefix)
+                    || !relative.ends_with(suffix)
+                {
+                    continue;
+                }
+                let pattern_match = &relative[prefix.len()..relative.len() - suffix.len()];
+                if target.replace('*', pattern_match) == relative {
+                    subpaths.insert(key.replace('*', pattern_match));
+                }
+            }
+        }
+    }
+
+    fn read_files_recursively(&self, cached_path: &C::Cp, files: &mut Vec<C::Cp>, ctx: &mut Ctx) {
+        let Ok(entries) = self.cache.read_dir(cached_path) else { return };
+        for entry in entries {
+            if self.cache.is_dir(&entry, ctx) {
+                self.read_files_recursively(&entry, files, ctx);
+            } else if self.cache.is_file(&entry, ctx) {
+                files.push(entry);
+            }
+        }
+    }
+
     /// Wrap `resolve_impl` with `tracing` information
     fn resolve_tracing(
         &self,
         directory: &Path,
         specifier: &str,
         ctx: &mut Ctx,
+    ) -> Result<Resolution<C>, ResolveError> {
+        self.resolve_tracing_with(directory, specifier, || {
+            self.resolve_persistent_or_impl(directory, specifier, ctx)
+        })
+    }
+
+    /// Resolves through [ResolverGeneric::with_persistent_cache] when it is enabled.
+    fn resolve_persistent_or_impl(
+        &self,
+        directory: &Path,
+        specifier: &str,
+        ctx: &mut Ctx,
+    ) -> Result<Resolution<C>, ResolveError> {
+        #[cfg(feature = "fs_cache")]
+        if let Some(persistent_cache) = &self.persistent_cache {
+            return self.resolve_persistent(persistent_cache, directory, specifier, ctx);
+        }
+        self.resolve_impl(directory, specifier, ctx)
+    }
+
+    /// Wrap `resolve` with `tracing` information
+    fn resolve_tracing_with<F: FnOnce() -> Result<Resolution<C>, ResolveError>>(
+        &self,
+        directory: &Path,
+        specifier: &str,
+        resolve: F,
     ) -> Result<Resolution<C>, ResolveError> {
         let span = tracing::debug_span!("resolve", path = ?directory, specifier = specifier);
         let _enter = span.enter();
-        let r = self.resolve_impl(directory, specifier, ctx);
+        let r = resolve();
         match &r {
             Ok(r) => {
                 tracing::debug!(options = ?self.options, path = ?directory, specifier = specifier, ret = ?r.path);
@@ -252,32 +914,218 @@ impl<C: Cache> ResolverGeneric<C> {
         r
     }
 
+    /// Looks up [ResolverGeneric::with_persistent_cache] before resolving,
+    /// recording the dependencies of new resolutions.
+    #[cfg(feature = "fs_cache")]
+    fn resolve_persistent(
+        &self,
+        persistent_cache: &persistent_cache::PersistentCache,
+        directory: &Path,
+        specifier: &str,
+        ctx: &mut Ctx,
+    ) -> Result<Resolution<C>, ResolveError> {
+        // Recorded edges and conditions are not stored.
+        if ctx.dependency_edges.is_some() || ctx.available_conditions.is_some() {
+            return self.resolve_impl(directory, specifier, ctx);
+        }
+        let key = persistent_cache::PersistentKey::new(directory, specifier, ctx);
+        if let Some(entry) = persistent_cache.get(&key) {
+            for file in entry.files() {
+                ctx.add_file_dependency(file);
+            }
+            for file in entry.missing() {
+                ctx.add_missing_dependency(file);
+            }
+            for directory in entry.directories() {
+                ctx.add_context_dependency(directory);
+            }
+            ctx.query.clone_from(&entry.query);
+            ctx.fragment.clone_from(&entry.fragment);
+            ctx.condition_module_type = entry.condition_module_type;
+            ctx.export_subpath.clone_from(&entry.export_subpath);
+            let cached_path = self.cache.value(&entry.path);
+            return self.finish_resolution(cached_path, entry.path.clone(), ctx);
+        }
+        if ctx.file_dependencies.is_none() {
+            ctx.init_file_dependencies();
+        }
+        let (cached_path, path) = self.resolve_path(directory, specifier, ctx)?;
+        persistent_cache.insert(
+            key,
+            persistent_cache::PersistentEntry::new(
+                persistent_cache.file_system(),
+                path.clone(),
+                ctx.query.clone(),
+                ctx.fragment.clone(),
+                ctx.condition_module_type,
+                ctx.export_subpath.clone(),
+                ctx.file_dependencies.iter().flatten().cloned(),
+                ctx.missing_dependencies.iter().flatten().cloned(),
+                ctx.context_dependencies.iter().flatten().cloned(),
+            ),
+        );
+        self.finish_resolution(cached_path, path, ctx)
+    }
+
     fn resolve_impl(
         &self,
         path: &Path,
         specifier: &str,
         ctx: &mut Ctx,
     ) -> Result<Resolution<C>, ResolveError> {
-        ctx.with_fully_specified(self.options.fully_specified);
+        let (cached_path, path) = self.resolve_path(path, specifier, ctx)?;
+        self.finish_resolution(cached_path, path, ctx)
+    }
+
+    /// Runs the resolution algorithm, returning the resolved path and its real path.
+    fn resolve_path(
+        &self,
+        path: &Path,
+        specifier: &str,
+        ctx: &mut Ctx,
+    ) -> Result<(C::Cp, PathBuf), ResolveError> {
+        // Trimmed before parsing so whitespace does not end up in the query or fragment.
+        let specifier = if self.options.trim_specifier_whitespace {
+            let trimmed = specifier.trim();
+            if trimmed.is_empty() {
+                return Err(ResolveError::Specifier(SpecifierError::Empty(specifier.to_string())));
+            }
+            trimmed
+        } else {
+            specifier
+        };
+        // Strict ESM: only relative and absolute specifiers must be fully specified.
+        let fully_specified = self.options.fully_specified
+            || (self.options.strict_esm
+                && matches!(
+                    Path::new(specifier).components().next(),
+                    Some(
+                        Component::RootDir
+                            | Component::Prefix(_)
+                            | Component::CurDir
+                            | Component::ParentDir
+                    )
+                ));
+        ctx.with_fully_specified(fully_specified);
         let cached_path = self.cache.value(path);
-        let cached_path = self.require(&cached_path, specifier, ctx)?;
+        let cached_path = self.require_with_fallback_extensions(&cached_path, specifier, ctx)?;
         let path = self.load_realpath(&cached_path)?;
+        Ok((cached_path, path))
+    }
+
+    /// Checks a resolved path and builds the [Resolution], also for [ResolverGeneric::with_persistent_cache] hits.
+    fn finish_resolution(
+        &self,
+        mut cached_path: C::Cp,
+        mut path: PathBuf,
+        ctx: &mut Ctx,
+    ) -> Result<Resolution<C>, ResolveError> {
         // enhanced-resolve: restrictions
         self.check_restrictions(&path)?;
+        if let Some(post_resolve) = &self.options.post_resolve {
+            match post_resolve.call(&path) {
+                PostResolveAction::Accept => {}
+                PostResolveAction::Reject(reason) => {
+                    return Err(ResolveError::Rejected(path, reason));
+                }
+                PostResolveAction::Redirect(redirect) => {
+                    cached_path = self.cache.value(&redirect);
+                    path = self.load_realpath(&cached_path)?;
+                    self.check_restrictions(&path)?;
+                }
+            }
+        }
+        if self.options.symlinks && self.options.lazy_symlinks {
+            // Package scopes were not canonicalized, look up the one of the real path instead.
+            cached_path = self.cache.value(&path);
+        }
         let package_json =
             cached_path.find_package_json(&self.options, self.cache.as_ref(), ctx)?;
         if let Some((_, package_json)) = &package_json {
             // path must be inside the package.
             debug_assert!(path.starts_with(package_json.directory()));
+            if self.options.honor_files_field
+                && self.is_in_modules_directory(package_json.directory())
+                && !files_field::is_published(package_json.as_ref(), &path)
+            {
+                return Err(ResolveError::NotPublished {
+                    file: path,
+                    package: package_json.path().to_path_buf(),
+                });
+            }
+            if let Some(actual) = &self.options.target_node_version {
+                Self::check_engine(package_json.as_ref(), actual)?;
+            }
         }
         Ok(Resolution {
             path,
             query: ctx.query.take(),
             fragment: ctx.fragment.take(),
             package_json: package_json.map(|(_, p)| p),
+            condition_module_type: ctx.condition_module_type.take(),
+            export_subpath: ctx.export_subpath.take(),
         })
     }
 
+    /// Whether `directory` is inside [ResolveOptions::modules] or [ResolveOptions::flat_modules],
+    /// i.e. an installed package rather than the project being built.
+    fn is_in_modules_directory(&self, directory: &Path) -> bool {
+        self.options.flat_modules.iter().any(|dir| directory.starts_with(dir))
+            || self.options.modules.iter().any(|module_name| {
+                let module_name = Path::new(module_name);
+                if module_name.is_absolute() {
+                    directory.starts_with(module_name)
+                } else {
+                    directory.components().any(|c| c == Component::Normal(module_name.as_os_str()))
+                }
+            })
+    }
+
+    /// See [ResolveOptions::module_type_from_conditions], an inner condition is recorded first.
+    fn record_condition_module_type(condition: &str, ctx: &mut Ctx) {
+        if ctx.condition_module_type.is_none() {
+            ctx.condition_module_type = match condition {
+                "import" => Some(PackageType::Module),
+                "require" => Some(PackageType::CommonJs),
+                _ => None,
+            };
+        }
+    }
+
+    /// Checks [ResolveOptions::target_node_version] against the "engines.node" range of the package.
+    fn check_engine(package_json: &C::Pj, actual: &str) -> Result<(), ResolveError> {
+        let Some((_, required)) = package_json
+            .engines()
+            .and_then(|mut engines| engines.find(|(name, _)| *name == "node"))
+        else {
+            return Ok(());
+        };
+        if engines::satisfies(required, actual) == Some(false) {
+            return Err(ResolveError::IncompatibleEngine {
+                package: package_json.path().to_path_buf(),
+                required: required.to_string(),
+                actual: actual.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Resolves again with [ResolveOptions::fallback_extensions] when nothing is found.
+    fn require_with_fallback_extensions(
+        &self,
+        cached_path: &C::Cp,
+        specifier: &str,
+        ctx: &mut Ctx,
+    ) -> Result<C::Cp, ResolveError> {
+        match self.require(cached_path, specifier, ctx) {
+            Err(ResolveError::NotFound(_)) if !self.options.fallback_extensions.is_empty() => {
+                ctx.extensions.replace(self.options.fallback_extensions.as_slice().into());
+                self.require(cached_path, specifier, ctx)
+            }
+            result => result,
+        }
+    }
+
     /// require(X) from module at path Y
     ///
     /// X: specifier
@@ -308,7 +1156,13 @@ impl<C: Cache> ResolverGeneric<C> {
         ctx: &mut Ctx,
     ) -> Result<C::Cp, ResolveError> {
         // tsconfig-paths
-        if let Some(path) = self.load_tsconfig_paths(cached_path, specifier, &mut Ctx::default())? {
+        let mut tsconfig_ctx = Ctx::default();
+        if let Some(path) = self.load_tsconfig_paths(cached_path, specifier, &mut tsconfig_ctx)? {
+            // The query and fragment of the specifier take precedence over the ones of the target.
+            if ctx.query.is_none() && ctx.fragment.is_none() {
+                ctx.query = tsconfig_ctx.query;
+                ctx.fragment = tsconfig_ctx.fragment;
+            }
             return Ok(path);
         }
 
@@ -330,7 +1184,14 @@ impl<C: Cache> ResolverGeneric<C> {
             Some(Component::Normal(_)) if specifier.as_bytes()[0] == b'#' => {
                 self.require_hash(cached_path, specifier, ctx)
             }
+            Some(Component::Normal(_)) if specifier.starts_with("workspace:") => {
+                self.require_workspace(specifier, ctx)
+            }
             _ => {
+                // TypeScript: resolve Node.js builtin modules to their declaration files.
+                if let Some(path) = self.load_node_builtin_types(cached_path, specifier, ctx) {
+                    return Ok(path);
+                }
                 // 1. If X is a core module,
                 //   a. return the core module
                 //   b. STOP
@@ -353,22 +1214,87 @@ impl<C: Cache> ResolverGeneric<C> {
         })
     }
 
+    /// Resolve `workspace:pkg/sub` to `./sub` in the workspace package named `pkg`.
+    fn require_workspace(&self, specifier: &str, ctx: &mut Ctx) -> Result<C::Cp, ResolveError> {
+        if self.options.workspaces.is_empty() {
+            return Err(ResolveError::WorkspacesNotConfigured(specifier.to_string()));
+        }
+        let (package_name, subpath) =
+            Self::parse_package_specifier(&specifier["workspace:".len()..]);
+        for workspace in &self.options.workspaces {
+            let cached_path = self.cache.value(workspace);
+            let Some((_, package_json)) =
+                self.cache.get_package_json(&cached_path, &self.options, ctx)?
+            else {
+                continue;
+            };
+            if package_json.name() != Some(package_name) {
+                continue;
+            }
+            if let Some(path) = self.load_package_exports(specifier, subpath, &cached_path, ctx)? {
+                return Ok(path);
+            }
+            let subpath = format!(".{subpath}");
+            ctx.with_fully_specified(false);
+            return self.require(&cached_path, &subpath, ctx);
+        }
+        Err(ResolveError::NotFound(specifier.to_string()))
+    }
+
     // PACKAGE_RESOLVE(packageSpecifier, parentURL)
     // 3. If packageSpecifier is a Node.js builtin module name, then
     //   1. Return the string "node:" concatenated with packageSpecifier.
     fn require_core(&self, specifier: &str) -> Result<(), ResolveError> {
         if self.options.builtin_modules {
             let is_runtime_module = specifier.starts_with("node:");
-            if is_runtime_module || NODEJS_BUILTINS.binary_search(&specifier).is_ok() {
-                let resolved = if is_runtime_module {
-                    specifier.to_string()
-                } else {
-                    format!("node:{specifier}")
-                };
+            if is_runtime_module || self.is_builtin(specifier) {
+                // Custom builtins of other runtimes, e.g. `bun`, are not Node.js modules.
+                let resolved =
+                    if !is_runtime_module && NODEJS_BUILTINS.binary_search(&specifier).is_ok() {
+                        format!("node:{specifier}")
+                    } else {
+                        specifier.to_string()
+                    };
                 return Err(ResolveError::Builtin { resolved, is_runtime_module });
             }
         }
-        Ok(())
+        Ok(())
+    }
+
+    /// Whether `specifier` is in [ResolveOptions::builtin_modules_list], or [NODEJS_BUILTINS] by default.
+    fn is_builtin(&self, specifier: &str) -> bool {
+        self.options.builtin_modules_list.as_ref().map_or_else(
+            || NODEJS_BUILTINS.binary_search(&specifier).is_ok(),
+            |list| list.binary_search_by(|module| module.as_str().cmp(specifier)).is_ok(),
+        )
+    }
+
+    /// Resolves `fs`, `node:fs` and `node:fs/promises` to `@types/node/fs.d.ts` and `@types/node/fs/promises.d.ts`
+    /// for [crate::TypeScriptOptions::node_builtin_types].
+    ///
+    /// Each of [crate::TypeScriptOptions::types_scopes] is searched in order.
+    fn load_node_builtin_types(
+        &self,
+        cached_path: &C::Cp,
+        specifier: &str,
+        ctx: &mut Ctx,
+    ) -> Option<C::Cp> {
+        let typescript = self.options.typescript.as_ref()?;
+        if !typescript.node_builtin_types {
+            return None;
+        }
+        let name = match specifier.strip_prefix("node:") {
+            Some(name) => name,
+            None if self.is_builtin(specifier) => specifier,
+            None => return None,
+        };
+        typescript.types_scopes.iter().find_map(|scope| {
+            let package_url =
+                self.find_package_directory(cached_path, &format!("{scope}/node"), ctx)?;
+            let cached_path =
+                package_url.normalize_with(format!("{name}.d.ts"), self.cache.as_ref());
+            self.cache.is_file(&cached_path, ctx).then_some(cached_path)
+        })
     }
 
     fn require_absolute(
@@ -451,7 +1377,38 @@ impl<C: Cache> ResolverGeneric<C> {
                 return Ok(path);
             }
         }
-        self.load_package_self_or_node_modules(cached_path, specifier, ctx)
+        let path = self.load_package_self_or_node_modules(cached_path, specifier, ctx)?;
+        if ctx.dependency_edges.is_some() {
+            self.add_dependency_edge(cached_path, specifier, &path, ctx)?;
+        }
+        Ok(path)
+    }
+
+    /// Records the dependency edge from the package of `cached_path` to the package of `resolved`.
+    fn add_dependency_edge(
+        &self,
+        cached_path: &C::Cp,
+        specifier: &str,
+        resolved: &C::Cp,
+        ctx: &mut Ctx,
+    ) -> Result<(), ResolveError> {
+        let Some((_, package_json)) =
+            resolved.find_package_json(&self.options, self.cache.as_ref(), ctx)?
+        else {
+            return Ok(());
+        };
+        let importer = cached_path.find_package_json(&self.options, self.cache.as_ref(), ctx)?;
+        // Self-references are not dependencies.
+        if importer.as_ref().is_some_and(|(_, importer)| importer.path() == package_json.path()) {
+            return Ok(());
+        }
+        let (package_name, _) = Self::parse_package_specifier(specifier);
+        ctx.add_dependency_edge(DependencyEdge {
+            importer: importer.and_then(|(_, importer)| importer.name().map(ToString::to_string)),
+            imported: package_name.to_string(),
+            version: package_json.version().map(ToString::to_string),
+        });
+        Ok(())
     }
 
     /// enhanced-resolve: ParsePlugin.
@@ -534,7 +1491,7 @@ impl<C: Cache> ResolverGeneric<C> {
         if let Some(path) = self.load_extension_alias(cached_path, ctx)? {
             return Ok(Some(path));
         }
-        if self.options.enforce_extension.is_disabled() {
+        if self.options.enforce_extension.is_disabled() && !self.options.prefer_extensions {
             // 1. If X is a file, load X as its file extension format. STOP
             if let Some(path) = self.load_alias_or_file(cached_path, ctx)? {
                 return Ok(Some(path));
@@ -543,14 +1500,21 @@ impl<C: Cache> ResolverGeneric<C> {
         // 2. If X.js is a file, load X.js as JavaScript text. STOP
         // 3. If X.json is a file, parse X.json to a JavaScript Object. STOP
         // 4. If X.node is a file, load X.node as binary addon. STOP
-        if let Some(path) = self.load_extensions(cached_path, &self.options.extensions, ctx)? {
+        let extensions = ctx.extensions.clone();
+        let extensions = extensions.as_deref().unwrap_or(&self.options.extensions);
+        if let Some(path) = self.load_extensions(cached_path, extensions, ctx)? {
             return Ok(Some(path));
         }
+        // Try the extensionless file last for `ResolveOptions::prefer_extensions`.
+        if self.options.enforce_extension.is_disabled() && self.options.prefer_extensions {
+            if let Some(path) = self.load_alias_or_file(cached_path, ctx)? {
+                return Ok(Some(path));
+            }
+        }
         Ok(None)
     }
 
     fn load_as_directory(&self, cached_path: &C::Cp, ctx: &mut Ctx) -> ResolveResult<C::Cp> {
-        // TODO: Only package.json is supported, so warn about having other values
         // Checking for empty files is needed for omitting checks on package.json
         // 1. If X/package.json is a file,
         if !self.options.description_files.is_empty() {
@@ -558,17 +1522,19 @@ impl<C: Cache> ResolverGeneric<C> {
             if let Some((_, package_json)) =
                 self.cache.get_package_json(cached_path, &self.options, ctx)?
             {
-                // b. If "main" is a falsy value, GOTO 2.
-                for main_field in package_json.main_fields(&self.options.main_fields) {
-                    // c. let M = X + (json main field)
-                    let cached_path = cached_path.normalize_with(main_field, self.cache.as_ref());
-                    // d. LOAD_AS_FILE(M)
-                    if let Some(path) = self.load_as_file(&cached_path, ctx)? {
-                        return Ok(Some(path));
+                if let Some(entry_selector) = &self.options.entry_selector {
+                    let entry = PackageEntry::new(package_json.as_ref(), &self.options.main_fields);
+                    if let Some(main_field) = entry_selector.call(&entry, ctx) {
+                        if let Some(path) = self.load_main_field(cached_path, &main_field, ctx)? {
+                            return Ok(Some(path));
+                        }
                     }
-                    // e. LOAD_INDEX(M)
-                    if let Some(path) = self.load_index(&cached_path, ctx)? {
-                        return Ok(Some(path));
+                } else {
+                    // b. If "main" is a falsy value, GOTO 2.
+                    for main_field in package_json.main_fields(&self.options.main_fields) {
+                        if let Some(path) = self.load_main_field(cached_path, main_field, ctx)? {
+                            return Ok(Some(path));
+                        }
                     }
                 }
                 // f. LOAD_INDEX(X) DEPRECATED
@@ -579,6 +1545,22 @@ impl<C: Cache> ResolverGeneric<C> {
         self.load_index(cached_path, ctx)
     }
 
+    fn load_main_field(
+        &self,
+        cached_path: &C::Cp,
+        main_field: &str,
+        ctx: &mut Ctx,
+    ) -> ResolveResult<C::Cp> {
+        // c. let M = X + (json main field)
+        let cached_path = cached_path.normalize_with(main_field, self.cache.as_ref());
+        // d. LOAD_AS_FILE(M)
+        if let Some(path) = self.load_as_file(&cached_path, ctx)? {
+            return Ok(Some(path));
+        }
+        // e. LOAD_INDEX(M)
+        self.load_index(&cached_path, ctx)
+    }
+
     fn load_as_file_or_directory(
         &self,
         cached_path: &C::Cp,
@@ -588,7 +1570,12 @@ impl<C: Cache> ResolverGeneric<C> {
         if self.options.resolve_to_context {
             return Ok(self.cache.is_dir(cached_path, ctx).then(|| cached_path.clone()));
         }
-        if !specifier.ends_with('/') {
+        // Strict: an extensionless directory is not loaded as a file.
+        let as_directory = specifier.ends_with('/')
+            || (self.options.directory_index_only
+                && cached_path.path().extension().is_none()
+                && self.cache.is_dir(cached_path, ctx));
+        if !as_directory {
             if let Some(path) = self.load_as_file(cached_path, ctx)? {
                 return Ok(Some(path));
             }
@@ -601,6 +1588,39 @@ impl<C: Cache> ResolverGeneric<C> {
         Ok(None)
     }
 
+    /// Returns the explicit extension for the "type" of the closest `package.json`,
+    /// `.mjs` for `"module"` and `.cjs` otherwise.
+    fn get_type_extension(
+        &self,
+        cached_path: &C::Cp,
+        ctx: &mut Ctx,
+    ) -> Result<Option<String>, ResolveError> {
+        let Some((_, package_json)) =
+            cached_path.find_package_json(&self.options, self.cache.as_ref(), ctx)?
+        else {
+            return Ok(None);
+        };
+        let extension = match package_json.r#type() {
+            Some(PackageType::Module) => ".mjs",
+            Some(PackageType::CommonJs) | None => ".cjs",
+        };
+        Ok(Some(extension.to_string()))
+    }
+
+    /// Lists the directory of `path` for [ResolveOptions::extensions_read_dir_threshold].
+    fn get_extensions_dir_names(
+        &self,
+        path: &C::Cp,
+        extensions: &[String],
+        ctx: &mut Ctx,
+    ) -> Option<Arc<[OsString]>> {
+        let threshold = self.options.extensions_read_dir_threshold?;
+        if extensions.len() < threshold || !self.options.alias_fields.is_empty() {
+            return None;
+        }
+        self.cache.read_dir_names(path.parent()?, ctx)
+    }
+
     fn load_extensions(
         &self,
         path: &C::Cp,
@@ -610,8 +1630,18 @@ impl<C: Cache> ResolverGeneric<C> {
         if ctx.fully_specified {
             return Ok(None);
         }
+        let dir_names = self.get_extensions_dir_names(path, extensions, ctx);
         for extension in extensions {
             let cached_path = path.add_extension(extension, self.cache.as_ref());
+            if let Some(dir_names) = &dir_names {
+                let exists = cached_path.path().file_name().is_some_and(|name| {
+                    dir_names.binary_search_by(|n| n.as_os_str().cmp(name)).is_ok()
+                });
+                if !exists {
+                    ctx.add_missing_dependency(cached_path.path());
+                    continue;
+                }
+            }
             if let Some(path) = self.load_alias_or_file(&cached_path, ctx)? {
                 return Ok(Some(path));
             }
@@ -620,13 +1650,27 @@ impl<C: Cache> ResolverGeneric<C> {
     }
 
     fn load_realpath(&self, cached_path: &C::Cp) -> Result<PathBuf, ResolveError> {
-        if self.options.symlinks {
+        if self.options.follow_symlinks(cached_path.path()) {
+            self.check_aborted()?;
             self.cache.canonicalize(cached_path)
         } else {
             Ok(cached_path.to_path_buf())
         }
     }
 
+    /// See [ResolveOptions::abort_signal].
+    fn check_aborted(&self) -> Result<(), ResolveError> {
+        if self
+            .options
+            .abort_signal
+            .as_ref()
+            .is_some_and(|abort_signal| abort_signal.load(std::sync::atomic::Ordering::Relaxed))
+        {
+            return Err(ResolveError::Aborted);
+        }
+        Ok(())
+    }
+
     fn check_restrictions(&self, path: &Path) -> Result<(), ResolveError> {
         // https://github.com/webpack/enhanced-resolve/blob/a998c7d218b7a9ec2461fc4fddd1ad5dd7687485/lib/RestrictionsPlugin.js#L19-L24
         fn is_inside(path: &Path, parent: &Path) -> bool {
@@ -638,7 +1682,8 @@ impl<C: Cache> ResolverGeneric<C> {
             }
             path.strip_prefix(parent).is_ok_and(|p| p == Path::new("./"))
         }
-        for restriction in &self.options.restrictions {
+        for (restriction, regex) in self.options.restrictions.iter().zip(&self.restriction_regexes)
+        {
             match restriction {
                 Restriction::Path(restricted_path) => {
                     if !is_inside(path, restricted_path) {
@@ -648,8 +1693,26 @@ impl<C: Cache> ResolverGeneric<C> {
                         ));
                     }
                 }
-                Restriction::RegExp(_) => {
-                    return Err(ResolveError::Unimplemented("Restriction with regex"));
+                Restriction::RegExp(pattern) => {
+                    // Matched with forward slashes on all platforms.
+                    let path_str = path.to_string_lossy();
+                    #[cfg(windows)]
+                    let path_str = path_str.replace('\\', "/");
+                    match regex {
+                        Some(Ok(regex)) if regex.is_match(&path_str) => {}
+                        Some(Err(error)) => {
+                            return Err(ResolveError::InvalidRestriction(
+                                pattern.clone(),
+                                error.clone(),
+                            ));
+                        }
+                        _ => {
+                            return Err(ResolveError::Restriction(
+                                path.to_path_buf(),
+                                PathBuf::from(pattern),
+                            ));
+                        }
+                    }
                 }
             }
         }
@@ -657,6 +1720,13 @@ impl<C: Cache> ResolverGeneric<C> {
     }
 
     fn load_index(&self, cached_path: &C::Cp, ctx: &mut Ctx) -> ResolveResult<C::Cp> {
+        let extensions = ctx.extensions.clone();
+        let extensions = extensions.as_deref().unwrap_or(&self.options.extensions);
+        let type_extension = if self.options.type_aware_index {
+            self.get_type_extension(cached_path, ctx)?
+        } else {
+            None
+        };
         for main_file in &self.options.main_files {
             let cached_path = cached_path.normalize_with(main_file, self.cache.as_ref());
             if self.options.enforce_extension.is_disabled() {
@@ -664,10 +1734,18 @@ impl<C: Cache> ResolverGeneric<C> {
                     return Ok(Some(path));
                 }
             }
+            // enhanced-resolve does not have this, see [ResolveOptions::type_aware_index].
+            if let Some(type_extension) = &type_extension {
+                if let Some(path) =
+                    self.load_extensions(&cached_path, std::slice::from_ref(type_extension), ctx)?
+                {
+                    return Ok(Some(path));
+                }
+            }
             // 1. If X/index.js is a file, load X/index.js as JavaScript text. STOP
             // 2. If X/index.json is a file, parse X/index.json to a JavaScript object. STOP
             // 3. If X/index.node is a file, load X/index.node as binary addon. STOP
-            if let Some(path) = self.load_extensions(&cached_path, &self.options.extensions, ctx)? {
+            if let Some(path) = self.load_extensions(&cached_path, extensions, ctx)? {
                 return Ok(Some(path));
             }
         }
@@ -719,6 +1797,13 @@ impl<C: Cache> ResolverGeneric<C> {
         specifier: &str,
         ctx: &mut Ctx,
     ) -> ResolveResult<C::Cp> {
+        let (package_name, subpath) = Self::parse_package_specifier(specifier);
+        if let Some(path) =
+            self.load_override(cached_path, specifier, package_name, subpath, ctx)?
+        {
+            return Ok(Some(path));
+        }
+
         #[cfg(feature = "yarn_pnp")]
         {
             if let Some(resolved_path) = self.load_pnp(cached_path, specifier, ctx)? {
@@ -726,13 +1811,24 @@ impl<C: Cache> ResolverGeneric<C> {
             }
         }
 
-        let (package_name, subpath) = Self::parse_package_specifier(specifier);
+        // A flat dependency directory is searched first, as an absolute module directory.
+        let flat_modules = self.options.flat_modules.as_ref().map(|dir| dir.to_string_lossy());
+        let walk = flat_modules.is_none() || self.options.flat_modules_fallback;
+        let modules = flat_modules
+            .iter()
+            .map(AsRef::as_ref)
+            .chain(self.options.modules.iter().map(String::as_str).filter(|_| walk));
         // 1. let DIRS = NODE_MODULES_PATHS(START)
         // 2. for each DIR in DIRS:
-        for module_name in &self.options.modules {
-            for cached_path in std::iter::successors(Some(cached_path), |p| p.parent()) {
+        for module_name in modules {
+            let is_absolute = Path::new(module_name).is_absolute();
+            // An absolute module directory is searched once instead of in every ancestor directory.
+            let depth = if is_absolute { 1 } else { usize::MAX };
+            for cached_path in std::iter::successors(Some(cached_path), |p| p.parent()).take(depth)
+            {
+                self.check_aborted()?;
                 // Skip if /path/to/node_modules does not exist
-                if !self.cache.is_dir(cached_path, ctx) {
+                if !is_absolute && !self.cache.is_dir(cached_path, ctx) {
                     continue;
                 }
 
@@ -740,6 +1836,9 @@ impl<C: Cache> ResolverGeneric<C> {
                 else {
                     continue;
                 };
+                if !self.module_directory_may_contain(&cached_path, package_name, ctx) {
+                    continue;
+                }
                 // Optimize node_modules lookup by inspecting whether the package exists
                 // From LOAD_PACKAGE_EXPORTS(X, DIR)
                 // 1. Try to interpret X as a combination of NAME and SUBPATH where the name
@@ -749,10 +1848,35 @@ impl<C: Cache> ResolverGeneric<C> {
                     // Try foo/node_modules/package_name
                     if self.cache.is_dir(&cached_path, ctx) {
                         // a. LOAD_PACKAGE_EXPORTS(X, DIR)
-                        if let Some(path) =
-                            self.load_package_exports(specifier, subpath, &cached_path, ctx)?
-                        {
-                            return Ok(Some(path));
+                        match self.load_package_exports(specifier, subpath, &cached_path, ctx) {
+                            Ok(Some(path)) => return Ok(Some(path)),
+                            Ok(None) => {}
+                            // Interop: load the package as a file or directory below.
+                            Err(
+                                ResolveError::PackagePathNotExported(..)
+                                | ResolveError::PackageConditionNotMatched { .. },
+                            ) if self.options.exports_fallback_to_main
+                                && !self.options.exports_only => {}
+                            // Interop: "exports" matched a missing file for the package root, load "main" below.
+                            Err(ResolveError::NotFound(_))
+                                if subpath.is_empty()
+                                    && self.options.exports_fallback_to_main
+                                    && !self.options.exports_only =>
+                            {
+                                self.report_exports_target_not_found(&cached_path, ctx)?;
+                            }
+                            Err(error) => return Err(error),
+                        }
+                        // Strict: nothing but "exports" is loaded from a package with a package.json.
+                        if self.options.exports_only {
+                            if let Some((_, package_json)) =
+                                self.cache.get_package_json(&cached_path, &self.options, ctx)?
+                            {
+                                return Err(ResolveError::PackagePathNotExported(
+                                    format!(".{subpath}"),
+                                    package_json.path().to_path_buf(),
+                                ));
+                            }
                         }
                     } else {
                         // foo/node_modules/package_name is not a directory, so useless to check inside it
@@ -800,6 +1924,46 @@ impl<C: Cache> ResolverGeneric<C> {
         Ok(None)
     }
 
+    /// Resolves packages redirected by [ResolveOptions::overrides] from their forced directory.
+    fn load_override(
+        &self,
+        cached_path: &C::Cp,
+        specifier: &str,
+        package_name: &str,
+        subpath: &str,
+        ctx: &mut Ctx,
+    ) -> ResolveResult<C::Cp> {
+        if self.options.overrides.is_empty() || package_name.is_empty() {
+            return Ok(None);
+        }
+        let mut directory = None;
+        for (pattern, path) in &self.options.overrides {
+            match pattern.split_once('>') {
+                Some((parent, name)) if name == package_name => {
+                    let importer =
+                        cached_path.find_package_json(&self.options, self.cache.as_ref(), ctx)?;
+                    if importer.is_some_and(|(_, package_json)| package_json.name() == Some(parent))
+                    {
+                        directory = Some(path);
+                        break;
+                    }
+                }
+                None if pattern == package_name && directory.is_none() => directory = Some(path),
+                _ => {}
+            }
+        }
+        let Some(directory) = directory else { return Ok(None) };
+        let package_url = self.cache.value(directory);
+        if let Some(path) = self.load_package_exports(specifier, subpath, &package_url, ctx)? {
+            return Ok(Some(path));
+        }
+        let cached_path = package_url.normalize_with(format!(".{subpath}"), self.cache.as_ref());
+        if let Some(path) = self.load_as_file_or_directory(&cached_path, specifier, ctx)? {
+            return Ok(Some(path));
+        }
+        Err(ResolveError::NotFound(specifier.to_string()))
+    }
+
     #[cfg(feature = "yarn_pnp")]
     fn load_pnp(
         &self,
@@ -811,17 +1975,20 @@ impl<C: Cache> ResolverGeneric<C> {
         let resolution =
             pnp::resolve_to_unqualified_via_manifest(pnp_manifest, specifier, cached_path.path());
         match resolution {
-            Ok(pnp::Resolution::Resolved(path, subpath)) => {
+            Ok(pnp::Resolution::Resolved(path, _)) => {
                 let cached_path = self.cache.value(&path);
-                let export_resolution = self.load_package_exports(
-                    specifier,
-                    &subpath.unwrap_or_default(),
-                    &cached_path,
-                    ctx,
-                )?;
+                // `path` is the package directory. The subpath returned by pnp only keeps
+                // the first segment after the package name, parse it from the specifier instead.
+                let (_, subpath) = Self::parse_package_specifier(specifier);
+                let export_resolution =
+                    self.load_package_exports(specifier, subpath, &cached_path, ctx)?;
                 if export_resolution.is_some() {
                     return Ok(export_resolution);
                 }
+                let cached_path = match subpath.strip_prefix('/') {
+                    Some(subpath) => cached_path.normalize_with(subpath, self.cache.as_ref()),
+                    None => cached_path,
+                };
                 let file_or_directory_resolution =
                     self.load_as_file_or_directory(&cached_path, specifier, ctx)?;
                 if file_or_directory_resolution.is_some() {
@@ -839,6 +2006,34 @@ impl<C: Cache> ResolverGeneric<C> {
         }
     }
 
+    /// Looks up the first segment of `package_name` in the listing of the module directory
+    /// for [ResolveOptions::node_modules_read_dir].
+    ///
+    /// Entries prefixed by the segment are accepted for files such as `node_modules/foo.js`.
+    fn module_directory_may_contain(
+        &self,
+        module_directory: &C::Cp,
+        package_name: &str,
+        ctx: &mut Ctx,
+    ) -> bool {
+        if !self.options.node_modules_read_dir || !self.options.alias_fields.is_empty() {
+            return true;
+        }
+        let Some(name) = package_name.split('/').next().filter(|name| !name.is_empty()) else {
+            return true;
+        };
+        let Some(names) = self.cache.read_dir_names(module_directory, ctx) else {
+            return true;
+        };
+        let index = names.partition_point(|n| n.as_os_str() < OsStr::new(name));
+        let exists =
+            names.get(index).is_some_and(|n| n.as_encoded_bytes().starts_with(name.as_bytes()));
+        if !exists {
+            ctx.add_missing_dependency(&module_directory.path().join(name));
+        }
+        exists
+    }
+
     fn get_module_directory(
         &self,
         cached_path: &C::Cp,
@@ -847,6 +2042,9 @@ impl<C: Cache> ResolverGeneric<C> {
     ) -> Option<C::Cp> {
         if module_name == "node_modules" {
             cached_path.cached_node_modules(self.cache.as_ref(), ctx)
+        } else if Path::new(module_name).is_absolute() {
+            let cached_path = self.cache.value(Path::new(module_name));
+            self.cache.is_dir(&cached_path, ctx).then_some(cached_path)
         } else if cached_path.path().components().next_back()
             == Some(Component::Normal(OsStr::new(module_name)))
         {
@@ -870,6 +2068,12 @@ impl<C: Cache> ResolverGeneric<C> {
         else {
             return Ok(None);
         };
+        if subpath.is_empty() {
+            if self.prefers_main_field(&package_json) {
+                return Ok(None);
+            }
+            self.check_main_field_ignored(&package_json, ctx);
+        }
         // 3. Parse DIR/NAME/package.json, and look for "exports" field.
         // 4. If "exports" is null or undefined, return.
         // 5. let MATCH = PACKAGE_EXPORTS_RESOLVE(pathToFileURL(DIR/NAME), "." + SUBPATH,
@@ -901,28 +2105,102 @@ impl<C: Cache> ResolverGeneric<C> {
         };
         // 3. If the SCOPE/package.json "exports" is null or undefined, return.
         // 4. If the SCOPE/package.json "name" is not the first segment of X, return.
-        if let Some(subpath) = package_json
+        let Some(subpath) = package_json
             .name()
             .and_then(|package_name| Self::strip_package_name(specifier, package_name))
+        else {
+            return self.load_browser_field(
+                cached_path,
+                Some(specifier),
+                &package_url,
+                &package_json,
+                ctx,
+            );
+        };
+        // Bail out of self-references that lead back to themselves, e.g. through the "browser" field.
+        if !ctx.enter_self_reference(package_json.path(), specifier) {
+            return Err(ResolveError::SelfReferenceCycle(
+                specifier.to_string(),
+                package_json.path().to_path_buf(),
+            ));
+        }
+        let resolved = self.load_package_self_subpath(
+            cached_path,
+            specifier,
+            subpath,
+            &package_url,
+            &package_json,
+            ctx,
+        );
+        ctx.exit_self_reference();
+        resolved
+    }
+
+    fn load_package_self_subpath(
+        &self,
+        cached_path: &C::Cp,
+        specifier: &str,
+        subpath: &str,
+        package_url: &C::Cp,
+        package_json: &C::Pj,
+        ctx: &mut Ctx,
+    ) -> ResolveResult<C::Cp> {
+        // 5. let MATCH = PACKAGE_EXPORTS_RESOLVE(pathToFileURL(SCOPE),
+        // "." + X.slice("name".length), `package.json` "exports", ["node", "require"])
+        // defined in the ESM resolver.
+        // Note: The subpath is not prepended with a dot on purpose
+        // because `package_exports_resolve` matches subpath without the leading dot.
+        for exports in package_json.exports_fields(&self.options.exports_fields) {
+            if let Some(cached_path) =
+                self.package_exports_resolve(package_url, &format!(".{subpath}"), &exports, ctx)?
+            {
+                // 6. RESOLVE_ESM_MATCH(MATCH)
+                return self.resolve_esm_match(specifier, &cached_path, ctx);
+            }
+        }
+        self.load_browser_field(cached_path, Some(specifier), package_url, package_json, ctx)
+    }
+
+    /// Whether the package root should be resolved with the main fields, see [ResolveOptions::prefer_main_over_exports].
+    fn prefers_main_field(&self, package_json: &C::Pj) -> bool {
+        self.options.prefer_main_over_exports
+            && package_json.main_fields(&self.options.main_fields).next().is_some()
+    }
+
+    /// Reports [ResolveDiagnostic::MainFieldIgnored] when the package defines both "exports" and a main field.
+    fn check_main_field_ignored(&self, package_json: &C::Pj, ctx: &mut Ctx) {
+        if ctx.diagnostics.is_some()
+            && !self.prefers_main_field(package_json)
+            && package_json.exports_fields(&self.options.exports_fields).next().is_some()
+            && package_json.main_fields(&self.options.main_fields).next().is_some()
         {
-            // 5. let MATCH = PACKAGE_EXPORTS_RESOLVE(pathToFileURL(SCOPE),
-            // "." + X.slice("name".length), `package.json` "exports", ["node", "require"])
-            // defined in the ESM resolver.
-            // Note: The subpath is not prepended with a dot on purpose
-            // because `package_exports_resolve` matches subpath without the leading dot.
-            for exports in package_json.exports_fields(&self.options.exports_fields) {
-                if let Some(cached_path) = self.package_exports_resolve(
-                    &package_url,
-                    &format!(".{subpath}"),
-                    &exports,
-                    ctx,
-                )? {
-                    // 6. RESOLVE_ESM_MATCH(MATCH)
-                    return self.resolve_esm_match(specifier, &cached_path, ctx);
-                }
+            ctx.add_diagnostic(ResolveDiagnostic::MainFieldIgnored(
+                package_json.path().to_path_buf(),
+            ));
+        }
+    }
+
+    /// Reports [ResolveDiagnostic::ExportsTargetNotFound] in place of [ResolveDiagnostic::MainFieldIgnored],
+    /// the main field is no longer ignored once the package falls back to it.
+    fn report_exports_target_not_found(
+        &self,
+        cached_path: &C::Cp,
+        ctx: &mut Ctx,
+    ) -> Result<(), ResolveError> {
+        if ctx.diagnostics.is_none() {
+            return Ok(());
+        }
+        if let Some((_, package_json)) =
+            self.cache.get_package_json(cached_path, &self.options, ctx)?
+        {
+            let path = package_json.path().to_path_buf();
+            if let Some(diagnostics) = &mut ctx.diagnostics {
+                diagnostics
+                    .retain(|d| !matches!(d, ResolveDiagnostic::MainFieldIgnored(p) if *p == path));
             }
+            ctx.add_diagnostic(ResolveDiagnostic::ExportsTargetNotFound(path));
         }
-        self.load_browser_field(cached_path, Some(specifier), &package_url, &package_json, ctx)
+        Ok(())
     }
 
     /// RESOLVE_ESM_MATCH(MATCH)
@@ -935,6 +2213,25 @@ impl<C: Cache> ResolverGeneric<C> {
         // 1. let RESOLVED_PATH = fileURLToPath(MATCH)
         // 2. If the file at RESOLVED_PATH exists, load RESOLVED_PATH as its extension format. STOP
         //
+        // enhanced-resolve: a context is the matched directory or the directory of the matched file.
+        if self.options.resolve_to_context {
+            if self.cache.is_dir(cached_path, ctx) {
+                return Ok(Some(cached_path.clone()));
+            }
+            if self.cache.is_file(cached_path, ctx) {
+                return Ok(cached_path.parent().cloned());
+            }
+            return Err(ResolveError::NotFound(specifier.to_string()));
+        }
+        if self.options.strict_exports_targets {
+            if let Some(path) = self.load_extension_alias(cached_path, ctx)? {
+                return Ok(Some(path));
+            }
+            if let Some(path) = self.load_alias_or_file(cached_path, ctx)? {
+                return Ok(Some(path));
+            }
+            return Err(ResolveError::NotFound(specifier.to_string()));
+        }
         // Non-compliant ESM can result in a directory, so directory is tried as well.
         if let Some(path) = self.load_as_file_or_directory(cached_path, "", ctx)? {
             return Ok(Some(path));
@@ -952,6 +2249,13 @@ impl<C: Cache> ResolverGeneric<C> {
         package_json: &C::Pj,
         ctx: &mut Ctx,
     ) -> ResolveResult<C::Cp> {
+        if ctx.diagnostics.is_some()
+            && package_json.has_invalid_browser_field(&self.options.alias_fields)
+        {
+            ctx.add_diagnostic(ResolveDiagnostic::InvalidBrowserField(
+                package_json.path().to_path_buf(),
+            ));
+        }
         let path = cached_path.path();
         let Some(new_specifier) = package_json.resolve_browser_field(
             path,
@@ -1169,22 +2473,9 @@ impl<C: Cache> ResolverGeneric<C> {
         references: &TsconfigReferences,
     ) -> Result<Arc<C::Tc>, ResolveError> {
         self.cache.get_tsconfig(root, path, |tsconfig| {
-            let directory = self.cache.value(tsconfig.directory());
             tracing::trace!(tsconfig = ?tsconfig, "load_tsconfig");
 
-            // Extend tsconfig
-            let extended_tsconfig_paths = tsconfig
-                .extends()
-                .map(|specifier| self.get_extended_tsconfig_path(&directory, tsconfig, specifier))
-                .collect::<Result<Vec<_>, _>>()?;
-            for extended_tsconfig_path in extended_tsconfig_paths {
-                let extended_tsconfig = self.load_tsconfig(
-                    /* root */ false,
-                    &extended_tsconfig_path,
-                    &TsconfigReferences::Disabled,
-                )?;
-                tsconfig.extend_tsconfig(&extended_tsconfig);
-            }
+            self.load_extended_tsconfigs(tsconfig)?;
 
             if tsconfig.load_references(references) {
                 let path = tsconfig.path().to_path_buf();
@@ -1200,7 +2491,9 @@ impl<C: Cache> ResolverGeneric<C> {
                                     reference_tsconfig.path().to_path_buf(),
                                 ));
                             }
-                            Ok(())
+                            // A referenced project is a root of its own, its `extends` are merged
+                            // before `${configDir}` is substituted with the referenced directory.
+                            self.load_extended_tsconfigs(reference_tsconfig)
                         },
                     )?;
                     reference.set_tsconfig(tsconfig);
@@ -1210,26 +2503,87 @@ impl<C: Cache> ResolverGeneric<C> {
         })
     }
 
+    /// Merges all tsconfigs listed in `extends` into `tsconfig`.
+    fn load_extended_tsconfigs(&self, tsconfig: &mut C::Tc) -> Result<(), ResolveError> {
+        let directory = self.cache.value(tsconfig.directory());
+        let extended_tsconfig_paths = tsconfig
+            .extends()
+            .map(|specifier| self.get_extended_tsconfig_path(&directory, tsconfig, specifier))
+            .collect::<Result<Vec<_>, _>>()?;
+        for extended_tsconfig_path in extended_tsconfig_paths {
+            let extended_tsconfig = self.load_tsconfig(
+                /* root */ false,
+                &extended_tsconfig_path,
+                &TsconfigReferences::Disabled,
+            )?;
+            tsconfig.extend_tsconfig(&extended_tsconfig);
+        }
+        Ok(())
+    }
+
     fn load_tsconfig_paths(
         &self,
         cached_path: &C::Cp,
         specifier: &str,
         ctx: &mut Ctx,
     ) -> ResolveResult<C::Cp> {
-        let Some(tsconfig_options) = &self.options.tsconfig else {
+        let tsconfig = if let Some(tsconfig_options) = &self.options.tsconfig {
+            self.load_tsconfig(
+                /* root */ true,
+                &tsconfig_options.config_file,
+                &tsconfig_options.references,
+            )?
+        } else if self.options.tsconfig_auto_discovery {
+            let Some(tsconfig) = self.find_tsconfig_impl(cached_path, ctx)? else {
+                return Ok(None);
+            };
+            tsconfig
+        } else {
             return Ok(None);
         };
-        let tsconfig = self.load_tsconfig(
-            /* root */ true,
-            &tsconfig_options.config_file,
-            &tsconfig_options.references,
-        )?;
         let paths = tsconfig.resolve(cached_path.path(), specifier);
         for path in paths {
             let cached_path = self.cache.value(&path);
             if let Ok(path) = self.require_relative(&cached_path, ".", ctx) {
                 return Ok(Some(path));
             }
+            // A target with a query or fragment such as `./src/x.js?inline`,
+            // unless it comes from the specifier while trying its fragment as a path.
+            let Some(target) = path
+                .to_str()
+                .filter(|path| path.contains(['?', '#']) && !specifier.contains(['?', '#']))
+            else {
+                continue;
+            };
+            let Ok(parsed) = Specifier::parse(target) else { continue };
+            let cached_path = self.cache.value(Path::new(parsed.path()));
+            if let Ok(path) = self.require_relative(&cached_path, ".", ctx) {
+                ctx.with_query_fragment(parsed.query, parsed.fragment);
+                return Ok(Some(path));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Finds the nearest `tsconfig.json` from `cached_path` and its ancestors.
+    fn find_tsconfig_impl(
+        &self,
+        cached_path: &C::Cp,
+        ctx: &mut Ctx,
+    ) -> Result<Option<Arc<C::Tc>>, ResolveError> {
+        let mut directory = Some(cached_path);
+        while let Some(cached_path) = directory {
+            let tsconfig_path = cached_path.normalize_with("tsconfig.json", self.cache.as_ref());
+            if self.cache.is_file(&tsconfig_path, ctx) {
+                return self
+                    .load_tsconfig(
+                        /* root */ true,
+                        tsconfig_path.path(),
+                        &TsconfigReferences::Auto,
+                    )
+                    .map(Some);
+            }
+            directory = cached_path.parent();
         }
         Ok(None)
     }
@@ -1283,6 +2637,9 @@ impl<C: Cache> ResolverGeneric<C> {
                 else {
                     continue;
                 };
+                if !self.module_directory_may_contain(&cached_path, package_name, ctx) {
+                    continue;
+                }
                 // 2. Set parentURL to the parent folder URL of parentURL.
                 let cached_path = cached_path.normalize_with(package_name, self.cache.as_ref());
                 // 3. If the folder at packageURL does not exist, then
@@ -1292,9 +2649,19 @@ impl<C: Cache> ResolverGeneric<C> {
                     if let Some((_, package_json)) =
                         self.cache.get_package_json(&cached_path, &self.options, ctx)?
                     {
+                        let prefers_main_field =
+                            subpath.is_empty() && self.prefers_main_field(&package_json);
+                        if subpath.is_empty() {
+                            self.check_main_field_ignored(&package_json, ctx);
+                        }
                         // 5. If pjson is not null and pjson.exports is not null or undefined, then
                         // 1. Return the result of PACKAGE_EXPORTS_RESOLVE(packageURL, packageSubpath, pjson.exports, defaultConditions).
-                        for exports in package_json.exports_fields(&self.options.exports_fields) {
+                        let exports_fields = if prefers_main_field {
+                            &[][..]
+                        } else {
+                            self.options.exports_fields.as_slice()
+                        };
+                        for exports in package_json.exports_fields(exports_fields) {
                             if let Some(path) = self.package_exports_resolve(
                                 &cached_path,
                                 &format!(".{subpath}"),
@@ -1327,6 +2694,29 @@ impl<C: Cache> ResolverGeneric<C> {
         Err(ResolveError::NotFound(specifier.to_string()))
     }
 
+    /// Rejects an "exports" object mixing keys starting with "." and other keys, or with keys starting with "#".
+    fn validate_exports_keys<'a, Io: ImportsExportsEntry<'a>>(
+        package_url: &Path,
+        exports: &Io,
+    ) -> Result<(), ResolveError> {
+        let Some(map) = exports.as_map() else { return Ok(()) };
+        let mut has_dot = false;
+        let mut without_dot = false;
+        for key in map.keys() {
+            // Keys starting with "#" are only valid in "imports".
+            if key.starts_with('#') {
+                return Err(ResolveError::InvalidPackageConfig(package_url.join("package.json")));
+            }
+            let starts_with_dot = key.starts_with('.');
+            has_dot = has_dot || starts_with_dot;
+            without_dot = without_dot || !starts_with_dot;
+            if has_dot && without_dot {
+                return Err(ResolveError::InvalidPackageConfig(package_url.join("package.json")));
+            }
+        }
+        Ok(())
+    }
+
     /// PACKAGE_EXPORTS_RESOLVE(packageURL, subpath, exports, conditions)
     fn package_exports_resolve<'a, Io: ImportsExportsEntry<'a>>(
         &self,
@@ -1337,20 +2727,7 @@ impl<C: Cache> ResolverGeneric<C> {
     ) -> ResolveResult<C::Cp> {
         let conditions = &self.options.condition_names;
         // 1. If exports is an Object with both a key starting with "." and a key not starting with ".", throw an Invalid Package Configuration error.
-        if let Some(map) = exports.as_map() {
-            let mut has_dot = false;
-            let mut without_dot = false;
-            for key in map.keys() {
-                let starts_with_dot_or_hash = key.starts_with(['.', '#']);
-                has_dot = has_dot || starts_with_dot_or_hash;
-                without_dot = without_dot || !starts_with_dot_or_hash;
-                if has_dot && without_dot {
-                    return Err(ResolveError::InvalidPackageConfig(
-                        package_url.path().join("package.json"),
-                    ));
-                }
-            }
-        }
+        Self::validate_exports_keys(package_url.path(), exports)?;
         // 2. If subpath is equal to ".", then
         // Note: subpath is not prepended with a dot when passed in.
         if subpath == "." {
@@ -1388,6 +2765,9 @@ impl<C: Cache> ResolverGeneric<C> {
             };
             // 4. If mainExport is not undefined, then
             if let Some(main_export) = main_export {
+                let conditions = self.main_export_conditions(conditions);
+                // Collect the conditions of the root export for reporting a mismatch.
+                let recorded = ctx.available_conditions.replace(vec![]);
                 // 1. Let resolved be the result of PACKAGE_TARGET_RESOLVE( packageURL, mainExport, null, false, conditions).
                 let resolved = self.package_target_resolve(
                     package_url,
@@ -1395,12 +2775,29 @@ impl<C: Cache> ResolverGeneric<C> {
                     main_export.as_ref(),
                     None,
                     /* is_imports */ false,
-                    conditions,
+                    &conditions,
                     ctx,
-                )?;
+                );
+                let available =
+                    std::mem::replace(&mut ctx.available_conditions, recorded).unwrap_or_default();
+                ctx.add_available_conditions(available.iter().map(String::as_str));
                 // 2. If resolved is not null or undefined, return resolved.
-                if let Some(path) = resolved {
-                    return Ok(Some(path));
+                if let Some(path) = resolved? {
+                    ctx.export_subpath = Some(".".to_string());
+                    return Ok(Some(self.load_sibling_declaration(path, ctx)));
+                }
+                // A condition that matched and resolved to null is not exported, see below.
+                let matched = available.iter().any(|key| {
+                    key == "default"
+                        || conditions
+                            .iter()
+                            .any(|condition| Self::condition_matches(condition, key))
+                });
+                if !available.is_empty() && !matched {
+                    return Err(ResolveError::PackageConditionNotMatched {
+                        package: package_url.path().join("package.json"),
+                        conditions: available,
+                    });
                 }
             }
         }
@@ -1419,7 +2816,8 @@ impl<C: Cache> ResolverGeneric<C> {
                 ctx,
             )? {
                 // 3. If resolved is not null or undefined, return resolved.
-                return Ok(Some(path));
+                ctx.export_subpath = Some(subpath.to_string());
+                return Ok(Some(self.load_sibling_declaration(path, ctx)));
             }
         }
         // 4. Throw a Package Path Not Exported error.
@@ -1429,6 +2827,54 @@ impl<C: Cache> ResolverGeneric<C> {
         ))
     }
 
+    /// TypeScript: adds the `types` condition for resolving the declaration entry of the package root.
+    ///
+    /// See [crate::TypeScriptOptions::types_condition].
+    fn main_export_conditions<'c>(&self, conditions: &'c [String]) -> Cow<'c, [String]> {
+        if self.options.typescript.as_ref().is_some_and(|typescript| typescript.types_condition)
+            && !conditions.iter().any(|condition| condition == "types")
+        {
+            Cow::Owned(
+                std::iter::once("types".to_string()).chain(conditions.iter().cloned()).collect(),
+            )
+        } else {
+            Cow::Borrowed(conditions)
+        }
+    }
+
+    /// TypeScript: returns the declaration file next to a resolved JavaScript file if it exists.
+    ///
+    /// See [crate::TypeScriptOptions::sibling_declarations].
+    fn load_sibling_declaration(&self, cached_path: C::Cp, ctx: &mut Ctx) -> C::Cp {
+        if !self
+            .options
+            .typescript
+            .as_ref()
+            .is_some_and(|typescript| typescript.sibling_declarations)
+        {
+            return cached_path;
+        }
+        let path = cached_path.path();
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            return cached_path;
+        };
+        let declaration = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("js") => format!("{stem}.d.ts"),
+            Some("mjs") => format!("{stem}.d.mts"),
+            Some("cjs") => format!("{stem}.d.cts"),
+            _ => return cached_path,
+        };
+        let Some(parent) = cached_path.parent() else {
+            return cached_path;
+        };
+        let declaration = parent.normalize_with(declaration, self.cache.as_ref());
+        if self.cache.is_file(&declaration, ctx) {
+            declaration
+        } else {
+            cached_path
+        }
+    }
+
     /// PACKAGE_IMPORTS_RESOLVE(specifier, parentURL, conditions)
     fn package_imports_resolve(
         &self,
@@ -1484,6 +2930,9 @@ impl<C: Cache> ResolverGeneric<C> {
     }
 
     /// PACKAGE_IMPORTS_EXPORTS_RESOLVE(matchKey, matchObj, packageURL, isImports, conditions)
+    ///
+    /// `match_key` is compared to the keys of `match_obj` byte for byte, independent of the
+    /// case sensitivity of the file system.
     fn package_imports_exports_resolve<'a, Io: ImportsExportsMap<'a>>(
         &self,
         match_key: &str,
@@ -1518,6 +2967,7 @@ impl<C: Cache> ResolverGeneric<C> {
         let mut best_target = None;
         let mut best_match = "";
         let mut best_key = "";
+        let mut disabled_folder_key = None;
         // 2. Let expansionKeys be the list of keys of matchObj containing only a single "*", sorted by the sorting function PATTERN_KEY_COMPARE which orders in descending order of specificity.
         // 3. For each key expansionKey in expansionKeys, do
         for (expansion_key, target) in match_obj.iter() {
@@ -1545,6 +2995,10 @@ impl<C: Cache> ResolverGeneric<C> {
                     && match_key.starts_with(expansion_key)
                     && Self::pattern_key_compare(best_key, expansion_key).is_gt()
                 {
+                    if !self.options.folder_mapping {
+                        disabled_folder_key.get_or_insert(expansion_key);
+                        continue;
+                    }
                     // TODO: [DEP0148] DeprecationWarning: Use of deprecated folder mapping "./dist/" in the "exports" field module resolution of the package at xxx/package.json.
                     best_target = Some(target);
                     best_match = &match_key[expansion_key.len()..];
@@ -1564,6 +3018,13 @@ impl<C: Cache> ResolverGeneric<C> {
                 ctx,
             );
         }
+        if let Some(key) = disabled_folder_key {
+            return Err(ResolveError::FolderMappingDisabled(
+                key.to_string(),
+                match_key.to_string(),
+                package_url.path().join("package.json"),
+            ));
+        }
         // 4. Return null.
         Ok(None)
     }
@@ -1645,11 +3106,14 @@ impl<C: Cache> ResolverGeneric<C> {
         }
         // 2. Otherwise, if target is a non-null Object, then
         else if let Some(target) = target.as_map() {
+            ctx.add_available_conditions(target.keys());
             // 1. If exports contains any index property keys, as defined in ECMA-262 6.1.7 Array Index, throw an Invalid Package Configuration error.
             // 2. For each property p of target, in object insertion order as,
             for (key, target_value) in target.iter() {
                 // 1. If p equals "default" or conditions contains an entry for p, then
-                if key == "default" || conditions.iter().any(|condition| condition == key) {
+                if key == "default"
+                    || conditions.iter().any(|condition| Self::condition_matches(condition, key))
+                {
                     // 1. Let targetValue be the value of the p property in target.
                     // 2. Let resolved be the result of PACKAGE_TARGET_RESOLVE( packageURL, targetValue, patternMatch, isImports, conditions).
                     let resolved = self.package_target_resolve(
@@ -1663,6 +3127,9 @@ impl<C: Cache> ResolverGeneric<C> {
                     );
                     // 3. If resolved is equal to undefined, continue the loop.
                     if let Some(path) = resolved? {
+                        if self.options.module_type_from_conditions {
+                            Self::record_condition_module_type(key, ctx);
+                        }
                         // 4. Return resolved.
                         return Ok(Some(path));
                     }
@@ -1694,7 +3161,11 @@ impl<C: Cache> ResolverGeneric<C> {
                     ctx,
                 );
 
-                if resolved.is_err() && i == targets.len() {
+                // Throw the error of the last fallback.
+                // Other errors, e.g. a bare imports target that is not found, continue the loop as in enhanced-resolve.
+                if matches!(resolved, Err(ResolveError::InvalidPackageTarget(..)))
+                    && i == targets.len() - 1
+                {
                     return resolved;
                 }
 
@@ -1705,7 +3176,7 @@ impl<C: Cache> ResolverGeneric<C> {
                 }
             }
             // 3. Return or throw the last fallback resolution null return or error.
-            // Note: see `resolved.is_err() && i == targets.len()`
+            // Note: see `i == targets.len() - 1`
         }
         // 4. Otherwise, if target is null, return null.
         Ok(None)
@@ -1748,6 +3219,18 @@ impl<C: Cache> ResolverGeneric<C> {
         (package_name, package_subpath)
     }
 
+    /// Whether the condition name matches the condition key, a `*` in the condition name matches any characters.
+    fn condition_matches(condition: &str, key: &str) -> bool {
+        if condition == key {
+            return true;
+        }
+        condition.split_once('*').is_some_and(|(prefix, suffix)| {
+            key.len() >= prefix.len() + suffix.len()
+                && key.starts_with(prefix)
+                && key.ends_with(suffix)
+        })
+    }
+
     /// PATTERN_KEY_COMPARE(keyA, keyB)
     fn pattern_key_compare(key_a: &str, key_b: &str) -> Ordering {
         if key_a.is_empty() {
@@ -1,8 +1,14 @@
 use std::{
     fmt,
+    hash::{Hash, Hasher},
     path::{Path, PathBuf},
+    sync::{atomic::AtomicBool, Arc},
 };
 
+use rustc_hash::FxHasher;
+
+use crate::{context::ResolveContext, PackageJson, PackageType};
+
 /// Module Resolution Options
 ///
 /// Options are directly ported from [enhanced-resolve](https://github.com/webpack/enhanced-resolve#resolver-options).
@@ -38,11 +44,18 @@ pub struct ResolveOptions {
     ///
     /// The key order in the exports field is significant. During condition matching, earlier entries have higher priority and take precedence over later entries.
     ///
+    /// A condition name containing a single `*` matches any key with the same prefix and suffix,
+    /// e.g. `worker*` matches `worker` and `workerd`.
+    ///
     /// Default `[]`
     pub condition_names: Vec<String>,
 
     /// The JSON files to use for descriptions. (There was once a `bower.json`.)
     ///
+    /// The files are tried in order in each directory before looking in the parent directory,
+    /// the first file that parses is used.
+    /// When empty, "main" fields are not loaded, but package.json is still read for "exports".
+    ///
     /// Default `["package.json"]`
     pub description_files: Vec<String>,
 
@@ -65,6 +78,11 @@ pub struct ResolveOptions {
     ///
     /// Can be a path to a JSON object such as `["path", "to", "exports"]`.
     ///
+    /// Set to `[]` to ignore "exports" entirely, packages are then resolved through
+    /// [ResolveOptions::main_fields] and direct file paths as before "exports" existed.
+    ///
+    /// Subpaths are matched against the keys case-sensitively, also on case-insensitive file systems.
+    ///
     /// Default `[["exports"]]`.
     pub exports_fields: Vec<Vec<String>>,
 
@@ -115,6 +133,9 @@ pub struct ResolveOptions {
 
     /// A list of directories to resolve modules from, can be absolute path or folder name.
     ///
+    /// A folder name is looked up in the current directory and all of its ancestors, similar to `node_modules`.
+    /// An absolute path is a fixed directory that is searched directly.
+    ///
     /// Default `["node_modules"]`
     pub modules: Vec<String>,
 
@@ -126,6 +147,9 @@ pub struct ResolveOptions {
 
     /// Resolve to a context instead of a file.
     ///
+    /// Bare specifiers are still matched against the "exports" and "imports" fields of the package,
+    /// returning the matched directory, or the directory containing the matched file.
+    ///
     /// Default `false`
     pub resolve_to_context: bool,
 
@@ -154,6 +178,10 @@ pub struct ResolveOptions {
     /// When enabled, symlinked resources are resolved to their real path, not their symlinked location.
     /// Note that this may cause module resolution to fail when using tools that symlink packages (like npm link).
     ///
+    /// Only symbolic links are followed, the path is not canonicalized by the operating system.
+    /// On case-insensitive but case-preserving file systems such as the macOS default,
+    /// the requested case is kept instead of the on-disk case, e.g. `./A` resolves to `A.js` for an `a.js` file.
+    ///
     /// Default `true`
     pub symlinks: bool,
 
@@ -162,6 +190,267 @@ pub struct ResolveOptions {
     ///
     /// Default `false`
     pub builtin_modules: bool,
+
+    /// Options for resolving TypeScript declaration files.
+    ///
+    /// Default `None`
+    pub typescript: Option<TypeScriptOptions>,
+
+    /// Prefer files with [ResolveOptions::extensions] over an extensionless file of the same name.
+    ///
+    /// When enabled, `require('./foo')` resolves to `./foo.js` even when an extensionless `./foo` file exists.
+    ///
+    /// Default `false`
+    pub prefer_extensions: bool,
+
+    /// Resolve the package root with the main fields instead of "exports" when both are defined.
+    ///
+    /// This is for legacy interop, where `require('pkg')` should load "main" even when the package defines "exports".
+    /// Subpaths such as `pkg/foo` are still resolved with "exports".
+    ///
+    /// Default `false`
+    pub prefer_main_over_exports: bool,
+
+    /// A list of workspace package directories used for resolving `workspace:` protocol specifiers.
+    ///
+    /// `workspace:pkg/sub` resolves `./sub` in the workspace package whose package.json "name" is `pkg`.
+    ///
+    /// Default `[]`
+    pub workspaces: Vec<PathBuf>,
+
+    /// Skip a malformed package.json found while looking up the closest package scope of a file, instead of failing the resolution.
+    ///
+    /// The skipped package.json is reported as [crate::ResolveDiagnostic::InvalidPackageJsonSkipped].
+    /// A malformed package.json of a package being resolved, e.g. `node_modules/foo/package.json` for `require('foo')`, is still an error.
+    ///
+    /// Default `false`
+    pub skip_invalid_package_json: bool,
+
+    /// Apply [ResolveOptions::fully_specified] to relative and absolute specifiers only, matching Node.js ESM.
+    ///
+    /// `import './foo'` must be written as `import './foo.js'`,
+    /// while bare specifiers such as `import 'pkg/feature'` are still resolved with extensions and main files.
+    ///
+    /// Default `false`
+    pub strict_esm: bool,
+
+    /// Check the resolved file against the "files" field of its `package.json`,
+    /// erroring with [crate::ResolveError::NotPublished] when the file would not be published.
+    ///
+    /// Useful for catching imports into files that only exist in the source repository of a package.
+    /// Only packages inside [ResolveOptions::modules] are checked, the project importing its own files is not.
+    ///
+    /// <https://docs.npmjs.com/cli/configuring-npm/package-json#files>
+    ///
+    /// Default `false`
+    pub honor_files_field: bool,
+
+    /// Use the `paths` of the nearest `tsconfig.json` from the importing directory when [ResolveOptions::tsconfig] is not set.
+    ///
+    /// Useful for monorepos where each package has its own `tsconfig.json`.
+    /// Project references of the discovered tsconfig are loaded as with [TsconfigReferences::Auto].
+    ///
+    /// Default `false`
+    pub tsconfig_auto_discovery: bool,
+
+    /// Prefer the index file matching the "type" of the closest `package.json` when resolving a directory.
+    ///
+    /// `index.mjs` is tried before [ResolveOptions::extensions] in a `"type": "module"` package,
+    /// and `index.cjs` otherwise.
+    ///
+    /// <https://nodejs.org/api/packages.html#type>
+    ///
+    /// Default `false`
+    pub type_aware_index: bool,
+
+    /// A hook invoked with the resolved path after [ResolveOptions::restrictions] are checked,
+    /// giving the embedder a final say over the result.
+    ///
+    /// See [PostResolveAction].
+    ///
+    /// Default `None`
+    pub post_resolve: Option<PostResolve>,
+
+    /// List the directory once instead of checking every extension when [ResolveOptions::extensions] has at least this many entries.
+    ///
+    /// Only candidates present in the directory listing are checked, trading one `read_dir` for a metadata call per extension.
+    /// Not applied when [ResolveOptions::alias_fields] is set, because aliased files are not required to exist.
+    ///
+    /// Default `None`
+    pub extensions_read_dir_threshold: Option<usize>,
+
+    /// Cancel long resolves, e.g. on slow network file systems, by setting the flag to `true`.
+    ///
+    /// The flag is checked before each directory walked while looking up [ResolveOptions::modules]
+    /// and before canonicalizing the resolved path, failing with [crate::ResolveError::Aborted].
+    ///
+    /// Default `None`
+    pub abort_signal: Option<Arc<AtomicBool>>,
+
+    /// Add the `development` or `production` condition to [ResolveOptions::condition_names].
+    ///
+    /// The condition is only added when it is not already listed.
+    /// As with any condition, the key order in the exports field decides its priority, so it is matched before a later `default` key.
+    ///
+    /// Default `None`
+    pub mode: Option<BuildMode>,
+
+    /// Defer resolving symlinks to the final resolved path when [ResolveOptions::symlinks] is enabled.
+    ///
+    /// By default the directory of every `package.json` read during resolution is canonicalized.
+    /// When enabled, `package.json` files keep the path they were found at,
+    /// and only the resolved path is canonicalized before looking up its package scope.
+    ///
+    /// Default `false`
+    pub lazy_symlinks: bool,
+
+    /// Allow the deprecated folder mapping of keys ending with `/` in "exports" and "imports", e.g. `"./": "./dist/"`.
+    ///
+    /// Node.js removed folder mappings in favor of subpath patterns (`"./*": "./dist/*"`), webpack still supports them.
+    /// When disabled, a specifier matching a folder mapping fails with [crate::ResolveError::FolderMappingDisabled].
+    ///
+    /// <https://nodejs.org/api/deprecations.html#DEP0148>
+    ///
+    /// Default `true`
+    pub folder_mapping: bool,
+
+    /// Replace [crate::NODEJS_BUILTINS] with another list of builtin modules for [ResolveOptions::builtin_modules],
+    /// e.g. for a specific Node.js version or a runtime such as Bun or Deno.
+    ///
+    /// Specifiers with the `node:` prefix are always builtin.
+    /// Only [crate::NODEJS_BUILTINS] are resolved with the `node:` prefix, other entries are resolved verbatim.
+    ///
+    /// Default `None`
+    pub builtin_modules_list: Option<Vec<String>>,
+
+    /// An absolute path to a flat dependency directory, e.g. a vendored dependencies folder.
+    ///
+    /// Bare specifiers are resolved directly inside this directory without walking the ancestor directories for [ResolveOptions::modules].
+    /// Set [ResolveOptions::flat_modules_fallback] to walk [ResolveOptions::modules] when the package is not found in it.
+    ///
+    /// Default `None`
+    pub flat_modules: Option<PathBuf>,
+
+    /// Walk [ResolveOptions::modules] when a bare specifier is not found in [ResolveOptions::flat_modules].
+    ///
+    /// Default `false`
+    pub flat_modules_fallback: bool,
+
+    /// Decide per path whether symlinks are followed when [ResolveOptions::symlinks] is enabled.
+    ///
+    /// Called with the resolved path and the directory of each `package.json` before canonicalizing,
+    /// returning `false` keeps the path as is, e.g. for preserving the paths of a vendored directory.
+    ///
+    /// Default `None`, which follows symlinks for all paths.
+    pub symlinks_filter: Option<SymlinksFilter>,
+
+    /// Resolve targets of "exports" and "imports" strictly as Node.js does, e.g. `"./feature": "./dist/feature"`.
+    ///
+    /// When enabled, a target must be an existing file, [ResolveOptions::extensions] are not tried and directories are not loaded.
+    /// enhanced-resolve is lenient and completes the target like a relative request.
+    ///
+    /// Default `false`
+    pub strict_exports_targets: bool,
+
+    /// List each `node_modules` directory once and look up packages in the listing instead of checking each package directory.
+    ///
+    /// Trades one `read_dir` per `node_modules` for a metadata call per missing package,
+    /// which pays off for deep hierarchies of `node_modules` with many packages.
+    /// Lookups are case-sensitive even on case-insensitive file systems.
+    ///
+    /// Default `false`
+    pub node_modules_read_dir: bool,
+
+    /// Check the "engines.node" range in the `package.json` of the resolved file against this Node.js version, e.g. `"18.19.0"`,
+    /// erroring with [crate::ResolveError::IncompatibleEngine] when the version does not satisfy the range.
+    ///
+    /// Ranges that cannot be parsed are ignored.
+    ///
+    /// <https://docs.npmjs.com/cli/configuring-npm/package-json#engines>
+    ///
+    /// Default `None`
+    pub target_node_version: Option<String>,
+
+    /// Infer [crate::Resolution::module_type] from the "import" or "require" condition that selected the file in "exports" or "imports".
+    ///
+    /// This is more accurate than the "type" field for `.js` files of dual packages.
+    /// Explicit `.mjs` / `.cjs` extensions still take precedence.
+    ///
+    /// Default `false`
+    pub module_type_from_conditions: bool,
+
+    /// Extensions attempted in order as a last resort, when nothing resolves with [ResolveOptions::extensions],
+    /// e.g. `[".vue", ".coffee"]` while keeping the common case fast.
+    ///
+    /// The specifier is resolved again with these extensions in place of [ResolveOptions::extensions]
+    /// when the first attempt fails with [crate::ResolveError::NotFound].
+    ///
+    /// All extensions must have a leading dot.
+    ///
+    /// Default `[]`
+    pub fallback_extensions: Vec<String>,
+
+    /// Select the entry of a package directory instead of trying [ResolveOptions::main_fields] in order,
+    /// e.g. "module" only when the package is an ES module and "main" otherwise.
+    ///
+    /// The selector is called with the [ResolveOptions::main_fields] present in the `package.json`.
+    /// When it returns `None` or the selected entry does not exist, the index file of the directory is loaded.
+    ///
+    /// Default `None`
+    pub entry_selector: Option<EntrySelector>,
+
+    /// Fall back to [ResolveOptions::main_fields] and loading files directly when a package subpath is not exported,
+    /// for interop with bundlers resolving packages as before "exports" existed.
+    ///
+    /// Node.js ignores "main" and fails with [crate::ResolveError::PackagePathNotExported] when the package has "exports".
+    /// The package root also falls back when none of its conditions match, or when its "exports" target does not exist on disk,
+    /// reported as [crate::ResolveDiagnostic::ExportsTargetNotFound].
+    /// Only packages found in [ResolveOptions::modules] fall back, other errors from "exports" are returned as is.
+    ///
+    /// Default `false`
+    pub exports_fallback_to_main: bool,
+
+    /// Only resolve packages found in [ResolveOptions::modules] through their "exports" field.
+    ///
+    /// When the package has a package.json, "main", [ResolveOptions::main_fields], the index file and subpaths without a matching export
+    /// fail with [crate::ResolveError::PackagePathNotExported] instead of being loaded from the package directory.
+    /// Packages without a package.json are not affected. Takes precedence over [ResolveOptions::exports_fallback_to_main].
+    ///
+    /// Default `false`
+    pub exports_only: bool,
+
+    /// Remove leading and trailing whitespace from the specifier before resolving, e.g. `" ./x "` resolves as `"./x"`.
+    ///
+    /// Whitespace is significant by default, as in Node.js. Whitespace inside the specifier is always kept.
+    ///
+    /// Default `false`
+    pub trim_specifier_whitespace: bool,
+
+    /// Force bare specifiers of a package to resolve from a fixed package directory,
+    /// like npm's "overrides" and yarn's "resolutions" fields.
+    ///
+    /// A pattern is a package name such as `"lodash"`, redirecting imports from every package,
+    /// or `"parent>name"`, only redirecting imports from files in the `parent` package.
+    /// Patterns with a parent take precedence, [ResolveOptions::modules] are not searched for overridden packages.
+    /// The package directory is resolved as a package found in `node_modules`, including its "exports" field.
+    ///
+    /// Default `[]`
+    pub overrides: Vec<(String, PathBuf)>,
+
+    /// Resolve an extensionless specifier naming an existing directory only as that directory,
+    /// as if it ended with `/`, e.g. `./dir` resolves to `./dir/index.js` and never to `./dir.js`.
+    ///
+    /// By default a file is tried before the directory, as in Node.js.
+    ///
+    /// Default `false`
+    pub directory_index_only: bool,
+
+    /// Prefer the ESM build of packages without "exports" by prepending "module" to [ResolveOptions::main_fields].
+    ///
+    /// Has no effect when [ResolveOptions::main_fields] already contains "module", its position is kept.
+    ///
+    /// Default `false`
+    pub prefer_esm: bool,
 }
 
 impl ResolveOptions {
@@ -360,77 +649,929 @@ impl ResolveOptions {
         self
     }
 
-    pub(crate) fn sanitize(mut self) -> Self {
-        debug_assert!(
-            self.extensions.iter().filter(|e| !e.is_empty()).all(|e| e.starts_with('.')),
-            "All extensions must start with a leading dot"
-        );
-        // Set `enforceExtension` to `true` when [ResolveOptions::extensions] contains an empty string.
-        // See <https://github.com/webpack/enhanced-resolve/pull/285>
-        if self.enforce_extension == EnforceExtension::Auto {
-            if !self.extensions.is_empty() && self.extensions.iter().any(String::is_empty) {
-                self.enforce_extension = EnforceExtension::Enabled;
-            } else {
-                self.enforce_extension = EnforceExtension::Disabled;
-            }
-        }
+    /// Sets [ResolveOptions::tsconfig]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::{ResolveOptions, TsconfigOptions, TsconfigReferences};
+    /// use std::path::PathBuf;
+    ///
+    /// let options = ResolveOptions::default().with_tsconfig(TsconfigOptions {
+    ///     config_file: PathBuf::from("tsconfig.json"),
+    ///     references: TsconfigReferences::Auto,
+    /// });
+    /// assert_eq!(options.tsconfig.unwrap().config_file, PathBuf::from("tsconfig.json"));
+    /// ```
+    #[must_use]
+    pub fn with_tsconfig(mut self, tsconfig: TsconfigOptions) -> Self {
+        self.tsconfig = Some(tsconfig);
         self
     }
-}
-
-/// Value for [ResolveOptions::enforce_extension]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum EnforceExtension {
-    Auto,
-    Enabled,
-    Disabled,
-}
 
-impl EnforceExtension {
+    /// Adds an entry to [ResolveOptions::alias]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::{AliasValue, ResolveOptions};
+    ///
+    /// let options = ResolveOptions::default().with_alias("@", &[AliasValue::from("./src")]);
+    /// assert_eq!(options.alias, vec![("@".to_string(), vec![AliasValue::from("./src")])]);
+    /// ```
     #[must_use]
-    pub const fn is_auto(&self) -> bool {
-        matches!(self, Self::Auto)
+    pub fn with_alias<S: Into<String>>(mut self, key: S, values: &[AliasValue]) -> Self {
+        self.alias.push((key.into(), values.to_vec()));
+        self
     }
 
+    /// Adds a field, or a path to a nested field, to [ResolveOptions::alias_fields]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_alias_field(&["browser"]);
+    /// assert_eq!(options.alias_fields, vec![vec!["browser".to_string()]]);
+    /// ```
     #[must_use]
-    pub const fn is_enabled(&self) -> bool {
-        matches!(self, Self::Enabled)
+    pub fn with_alias_field(mut self, field: &[&str]) -> Self {
+        self.alias_fields.push(field.iter().map(ToString::to_string).collect());
+        self
     }
 
+    /// Adds a description file to [ResolveOptions::description_files]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_description_file("component.json");
+    /// assert!(options.description_files.contains(&"component.json".to_string()));
+    /// ```
     #[must_use]
-    pub const fn is_disabled(&self) -> bool {
-        matches!(self, Self::Disabled)
+    pub fn with_description_file<S: Into<String>>(mut self, file: S) -> Self {
+        self.description_files.push(file.into());
+        self
     }
-}
 
-/// Alias for [ResolveOptions::alias] and [ResolveOptions::fallback]
-pub type Alias = Vec<(String, Vec<AliasValue>)>;
+    /// Adds a field, or a path to a nested field, to [ResolveOptions::exports_fields]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_exports_field(&["custom", "exports"]);
+    /// assert!(options.exports_fields.contains(&vec!["custom".to_string(), "exports".to_string()]));
+    /// ```
+    #[must_use]
+    pub fn with_exports_field(mut self, field: &[&str]) -> Self {
+        self.exports_fields.push(field.iter().map(ToString::to_string).collect());
+        self
+    }
 
-/// Alias Value for [ResolveOptions::alias] and [ResolveOptions::fallback]
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
-pub enum AliasValue {
-    /// The path value
-    Path(String),
+    /// Adds a field, or a path to a nested field, to [ResolveOptions::imports_fields]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_imports_field(&["custom", "imports"]);
+    /// assert!(options.imports_fields.contains(&vec!["custom".to_string(), "imports".to_string()]));
+    /// ```
+    #[must_use]
+    pub fn with_imports_field(mut self, field: &[&str]) -> Self {
+        self.imports_fields.push(field.iter().map(ToString::to_string).collect());
+        self
+    }
 
-    /// The `false` value
-    Ignore,
-}
+    /// Adds an extension and its aliases to [ResolveOptions::extension_alias]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_extension_alias(".js", &[".ts", ".js"]);
+    /// assert_eq!(
+    ///     options.extension_alias,
+    ///     vec![(".js".to_string(), vec![".ts".to_string(), ".js".to_string()])]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_extension_alias<S: Into<String>>(mut self, extension: S, aliases: &[&str]) -> Self {
+        self.extension_alias
+            .push((extension.into(), aliases.iter().map(ToString::to_string).collect()));
+        self
+    }
 
-impl<S> From<S> for AliasValue
-where
-    S: Into<String>,
-{
-    fn from(value: S) -> Self {
-        Self::Path(value.into())
+    /// Adds an entry to [ResolveOptions::fallback]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::{AliasValue, ResolveOptions};
+    ///
+    /// let options = ResolveOptions::default().with_fallback("path", &[AliasValue::from("path-browserify")]);
+    /// assert_eq!(options.fallback, vec![("path".to_string(), vec![AliasValue::from("path-browserify")])]);
+    /// ```
+    #[must_use]
+    pub fn with_fallback<S: Into<String>>(mut self, key: S, values: &[AliasValue]) -> Self {
+        self.fallback.push((key.into(), values.to_vec()));
+        self
     }
-}
 
-/// Value for [ResolveOptions::restrictions]
-#[derive(Debug, Clone)]
-pub enum Restriction {
-    Path(PathBuf),
-    RegExp(String),
-}
+    /// Adds a restriction to [ResolveOptions::restrictions]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::{ResolveOptions, Restriction};
+    /// use std::path::PathBuf;
+    ///
+    /// let options = ResolveOptions::default().with_restriction(Restriction::Path(PathBuf::from("src")));
+    /// assert_eq!(options.restrictions.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn with_restriction(mut self, restriction: Restriction) -> Self {
+        self.restrictions.push(restriction);
+        self
+    }
+
+    /// Sets [ResolveOptions::typescript]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::{ResolveOptions, TypeScriptOptions};
+    ///
+    /// let options = ResolveOptions::default().with_typescript(TypeScriptOptions::default());
+    /// assert!(options.typescript.is_some());
+    /// ```
+    #[must_use]
+    pub fn with_typescript(mut self, typescript: TypeScriptOptions) -> Self {
+        self.typescript = Some(typescript);
+        self
+    }
+
+    /// Adds a workspace directory to [ResolveOptions::workspaces]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    /// use std::path::PathBuf;
+    ///
+    /// let options = ResolveOptions::default().with_workspace("packages/foo");
+    /// assert_eq!(options.workspaces, vec![PathBuf::from("packages/foo")]);
+    /// ```
+    #[must_use]
+    pub fn with_workspace<P: AsRef<Path>>(mut self, workspace: P) -> Self {
+        self.workspaces.push(workspace.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sets [ResolveOptions::post_resolve]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::{PostResolve, PostResolveAction, ResolveOptions};
+    ///
+    /// let options =
+    ///     ResolveOptions::default().with_post_resolve(PostResolve::new(|_| PostResolveAction::Accept));
+    /// assert!(options.post_resolve.is_some());
+    /// ```
+    #[must_use]
+    pub fn with_post_resolve(mut self, post_resolve: PostResolve) -> Self {
+        self.post_resolve = Some(post_resolve);
+        self
+    }
+
+    /// Sets [ResolveOptions::entry_selector]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::{EntrySelector, ResolveOptions};
+    ///
+    /// let options = ResolveOptions::default()
+    ///     .with_entry_selector(EntrySelector::new(|entry, _| entry.main_field("main").map(ToString::to_string)));
+    /// assert!(options.entry_selector.is_some());
+    /// ```
+    #[must_use]
+    pub fn with_entry_selector(mut self, entry_selector: EntrySelector) -> Self {
+        self.entry_selector = Some(entry_selector);
+        self
+    }
+
+    /// Sets [ResolveOptions::extensions_read_dir_threshold]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_extensions_read_dir_threshold(8);
+    /// assert_eq!(options.extensions_read_dir_threshold, Some(8));
+    /// ```
+    #[must_use]
+    pub const fn with_extensions_read_dir_threshold(mut self, threshold: usize) -> Self {
+        self.extensions_read_dir_threshold = Some(threshold);
+        self
+    }
+
+    /// Sets [ResolveOptions::abort_signal]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    /// use std::sync::{atomic::AtomicBool, Arc};
+    ///
+    /// let options = ResolveOptions::default().with_abort_signal(Arc::new(AtomicBool::new(false)));
+    /// assert!(options.abort_signal.is_some());
+    /// ```
+    #[must_use]
+    pub fn with_abort_signal(mut self, signal: Arc<AtomicBool>) -> Self {
+        self.abort_signal = Some(signal);
+        self
+    }
+
+    /// Sets [ResolveOptions::mode]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::{BuildMode, ResolveOptions};
+    ///
+    /// let options = ResolveOptions::default().with_mode(BuildMode::Production);
+    /// assert_eq!(options.mode, Some(BuildMode::Production));
+    /// ```
+    #[must_use]
+    pub const fn with_mode(mut self, mode: BuildMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Sets [ResolveOptions::builtin_modules_list]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_builtin_modules_list(&["fs", "bun:test"]);
+    /// assert_eq!(options.builtin_modules_list, Some(vec!["fs".to_string(), "bun:test".to_string()]));
+    /// ```
+    #[must_use]
+    pub fn with_builtin_modules_list(mut self, modules: &[&str]) -> Self {
+        self.builtin_modules_list = Some(modules.iter().map(ToString::to_string).collect());
+        self
+    }
+
+    /// Sets [ResolveOptions::flat_modules]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    /// use std::path::PathBuf;
+    ///
+    /// let options = ResolveOptions::default().with_flat_modules("/vendor");
+    /// assert_eq!(options.flat_modules, Some(PathBuf::from("/vendor")));
+    /// ```
+    #[must_use]
+    pub fn with_flat_modules<P: AsRef<Path>>(mut self, directory: P) -> Self {
+        self.flat_modules = Some(directory.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sets [ResolveOptions::symlinks_filter]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::{ResolveOptions, SymlinksFilter};
+    ///
+    /// let options = ResolveOptions::default()
+    ///     .with_symlinks_filter(SymlinksFilter::new(|path| !path.starts_with("/vendor")));
+    /// assert!(options.symlinks_filter.is_some());
+    /// ```
+    #[must_use]
+    pub fn with_symlinks_filter(mut self, filter: SymlinksFilter) -> Self {
+        self.symlinks_filter = Some(filter);
+        self
+    }
+
+    /// Sets [ResolveOptions::target_node_version]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_target_node_version("18.0.0");
+    /// assert_eq!(options.target_node_version, Some("18.0.0".to_string()));
+    /// ```
+    #[must_use]
+    pub fn with_target_node_version<S: Into<String>>(mut self, version: S) -> Self {
+        self.target_node_version = Some(version.into());
+        self
+    }
+
+    /// Adds an extension to [ResolveOptions::fallback_extensions]. Extension must start with a `.`
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_fallback_extension(".vue");
+    /// assert_eq!(options.fallback_extensions, vec![".vue".to_string()]);
+    /// ```
+    #[must_use]
+    pub fn with_fallback_extension<S: Into<String>>(mut self, extension: S) -> Self {
+        self.fallback_extensions.push(extension.into());
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::resolve_to_context]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_resolve_to_context(true);
+    /// assert_eq!(options.resolve_to_context, true);
+    /// ```
+    #[must_use]
+    pub const fn with_resolve_to_context(mut self, flag: bool) -> Self {
+        self.resolve_to_context = flag;
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::prefer_extensions]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_prefer_extensions(true);
+    /// assert_eq!(options.prefer_extensions, true);
+    /// ```
+    #[must_use]
+    pub const fn with_prefer_extensions(mut self, flag: bool) -> Self {
+        self.prefer_extensions = flag;
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::prefer_main_over_exports]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_prefer_main_over_exports(true);
+    /// assert_eq!(options.prefer_main_over_exports, true);
+    /// ```
+    #[must_use]
+    pub const fn with_prefer_main_over_exports(mut self, flag: bool) -> Self {
+        self.prefer_main_over_exports = flag;
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::skip_invalid_package_json]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_skip_invalid_package_json(true);
+    /// assert_eq!(options.skip_invalid_package_json, true);
+    /// ```
+    #[must_use]
+    pub const fn with_skip_invalid_package_json(mut self, flag: bool) -> Self {
+        self.skip_invalid_package_json = flag;
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::strict_esm]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_strict_esm(true);
+    /// assert_eq!(options.strict_esm, true);
+    /// ```
+    #[must_use]
+    pub const fn with_strict_esm(mut self, flag: bool) -> Self {
+        self.strict_esm = flag;
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::honor_files_field]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_honor_files_field(true);
+    /// assert_eq!(options.honor_files_field, true);
+    /// ```
+    #[must_use]
+    pub const fn with_honor_files_field(mut self, flag: bool) -> Self {
+        self.honor_files_field = flag;
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::tsconfig_auto_discovery]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_tsconfig_auto_discovery(true);
+    /// assert_eq!(options.tsconfig_auto_discovery, true);
+    /// ```
+    #[must_use]
+    pub const fn with_tsconfig_auto_discovery(mut self, flag: bool) -> Self {
+        self.tsconfig_auto_discovery = flag;
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::type_aware_index]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_type_aware_index(true);
+    /// assert_eq!(options.type_aware_index, true);
+    /// ```
+    #[must_use]
+    pub const fn with_type_aware_index(mut self, flag: bool) -> Self {
+        self.type_aware_index = flag;
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::lazy_symlinks]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_lazy_symlinks(true);
+    /// assert_eq!(options.lazy_symlinks, true);
+    /// ```
+    #[must_use]
+    pub const fn with_lazy_symlinks(mut self, flag: bool) -> Self {
+        self.lazy_symlinks = flag;
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::folder_mapping]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_folder_mapping(false);
+    /// assert_eq!(options.folder_mapping, false);
+    /// ```
+    #[must_use]
+    pub const fn with_folder_mapping(mut self, flag: bool) -> Self {
+        self.folder_mapping = flag;
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::flat_modules_fallback]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_flat_modules_fallback(true);
+    /// assert_eq!(options.flat_modules_fallback, true);
+    /// ```
+    #[must_use]
+    pub const fn with_flat_modules_fallback(mut self, flag: bool) -> Self {
+        self.flat_modules_fallback = flag;
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::strict_exports_targets]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_strict_exports_targets(true);
+    /// assert_eq!(options.strict_exports_targets, true);
+    /// ```
+    #[must_use]
+    pub const fn with_strict_exports_targets(mut self, flag: bool) -> Self {
+        self.strict_exports_targets = flag;
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::node_modules_read_dir]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_node_modules_read_dir(true);
+    /// assert_eq!(options.node_modules_read_dir, true);
+    /// ```
+    #[must_use]
+    pub const fn with_node_modules_read_dir(mut self, flag: bool) -> Self {
+        self.node_modules_read_dir = flag;
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::module_type_from_conditions]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_module_type_from_conditions(true);
+    /// assert_eq!(options.module_type_from_conditions, true);
+    /// ```
+    #[must_use]
+    pub const fn with_module_type_from_conditions(mut self, flag: bool) -> Self {
+        self.module_type_from_conditions = flag;
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::exports_fallback_to_main]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_exports_fallback_to_main(true);
+    /// assert_eq!(options.exports_fallback_to_main, true);
+    /// ```
+    #[must_use]
+    pub const fn with_exports_fallback_to_main(mut self, flag: bool) -> Self {
+        self.exports_fallback_to_main = flag;
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::exports_only]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_exports_only(true);
+    /// assert_eq!(options.exports_only, true);
+    /// ```
+    #[must_use]
+    pub const fn with_exports_only(mut self, flag: bool) -> Self {
+        self.exports_only = flag;
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::trim_specifier_whitespace]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_trim_specifier_whitespace(true);
+    /// assert_eq!(options.trim_specifier_whitespace, true);
+    /// ```
+    #[must_use]
+    pub const fn with_trim_specifier_whitespace(mut self, flag: bool) -> Self {
+        self.trim_specifier_whitespace = flag;
+        self
+    }
+
+    /// Adds an override to [ResolveOptions::overrides]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_override("app>lodash", "/node_modules/lodash-es");
+    /// assert_eq!(options.overrides.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn with_override<P: AsRef<Path>>(mut self, pattern: &str, directory: P) -> Self {
+        self.overrides.push((pattern.to_string(), directory.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::directory_index_only]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_directory_index_only(true);
+    /// assert_eq!(options.directory_index_only, true);
+    /// ```
+    #[must_use]
+    pub const fn with_directory_index_only(mut self, flag: bool) -> Self {
+        self.directory_index_only = flag;
+        self
+    }
+
+    /// Sets the value for [ResolveOptions::prefer_esm]
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use oxc_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default().with_prefer_esm(true);
+    /// assert_eq!(options.prefer_esm, true);
+    /// ```
+    #[must_use]
+    pub const fn with_prefer_esm(mut self, flag: bool) -> Self {
+        self.prefer_esm = flag;
+        self
+    }
+
+    /// Whether symlinks are followed for `path`, see [ResolveOptions::symlinks_filter].
+    pub(crate) fn follow_symlinks(&self, path: &Path) -> bool {
+        self.symlinks && self.symlinks_filter.as_ref().map_or(true, |filter| filter.call(path))
+    }
+
+    /// Hash of the `Debug` output of all options except [ResolveOptions::abort_signal],
+    /// see [crate::ResolverGeneric::options_fingerprint].
+    pub(crate) fn fingerprint(&self) -> u64 {
+        let options = Self { abort_signal: None, ..self.clone() };
+        let mut hasher = FxHasher::default();
+        format!("{options:?}").hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Whether a closure option is set, which [ResolveOptions::fingerprint] cannot tell apart.
+    #[cfg(feature = "fs_cache")]
+    pub(crate) const fn has_hooks(&self) -> bool {
+        self.post_resolve.is_some()
+            || self.entry_selector.is_some()
+            || self.symlinks_filter.is_some()
+    }
+
+    pub(crate) fn sanitize(mut self) -> Self {
+        debug_assert!(
+            self.extensions
+                .iter()
+                .chain(&self.fallback_extensions)
+                .filter(|e| !e.is_empty())
+                .all(|e| e.starts_with('.')),
+            "All extensions must start with a leading dot"
+        );
+        // Set `enforceExtension` to `true` when [ResolveOptions::extensions] contains an empty string.
+        // See <https://github.com/webpack/enhanced-resolve/pull/285>
+        if self.enforce_extension == EnforceExtension::Auto {
+            if !self.extensions.is_empty() && self.extensions.iter().any(String::is_empty) {
+                self.enforce_extension = EnforceExtension::Enabled;
+            } else {
+                self.enforce_extension = EnforceExtension::Disabled;
+            }
+        }
+        debug_assert!(
+            self.flat_modules.as_ref().map_or(true, |dir| dir.is_absolute()),
+            "flat_modules must be an absolute path"
+        );
+        // Sorted for binary search.
+        if let Some(builtin_modules_list) = &mut self.builtin_modules_list {
+            builtin_modules_list.sort_unstable();
+        }
+        if let Some(mode) = self.mode {
+            let condition = mode.condition_name();
+            if !self.condition_names.iter().any(|c| c == condition) {
+                self.condition_names.push(condition.to_string());
+            }
+        }
+        if self.prefer_esm && !self.main_fields.iter().any(|field| field == "module") {
+            self.main_fields.insert(0, "module".into());
+        }
+        self
+    }
+}
+
+/// Value for [ResolveOptions::enforce_extension]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnforceExtension {
+    Auto,
+    Enabled,
+    Disabled,
+}
+
+impl EnforceExtension {
+    #[must_use]
+    pub const fn is_auto(&self) -> bool {
+        matches!(self, Self::Auto)
+    }
+
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        matches!(self, Self::Enabled)
+    }
+
+    #[must_use]
+    pub const fn is_disabled(&self) -> bool {
+        matches!(self, Self::Disabled)
+    }
+}
+
+/// Value for [ResolveOptions::mode]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildMode {
+    Development,
+    Production,
+}
+
+impl BuildMode {
+    /// The condition name added to [ResolveOptions::condition_names].
+    #[must_use]
+    pub const fn condition_name(self) -> &'static str {
+        match self {
+            Self::Development => "development",
+            Self::Production => "production",
+        }
+    }
+}
+
+/// Alias for [ResolveOptions::alias] and [ResolveOptions::fallback]
+pub type Alias = Vec<(String, Vec<AliasValue>)>;
+
+/// Alias Value for [ResolveOptions::alias] and [ResolveOptions::fallback]
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub enum AliasValue {
+    /// The path value
+    Path(String),
+
+    /// The `false` value
+    Ignore,
+}
+
+impl<S> From<S> for AliasValue
+where
+    S: Into<String>,
+{
+    fn from(value: S) -> Self {
+        Self::Path(value.into())
+    }
+}
+
+/// Value for [ResolveOptions::restrictions]
+#[derive(Debug, Clone)]
+pub enum Restriction {
+    /// The resolved path must be inside this directory.
+    Path(PathBuf),
+    /// The resolved path must match this [regex](https://docs.rs/regex) pattern.
+    ///
+    /// The path is matched with forward slashes on all platforms, e.g. `.*/__generated__/.*`.
+    /// Resolving with a pattern the regex crate does not support, e.g. a lookahead,
+    /// fails with [crate::ResolveError::InvalidRestriction].
+    RegExp(String),
+}
+
+/// Hook for [ResolveOptions::post_resolve]
+#[derive(Clone)]
+pub struct PostResolve(Arc<dyn Fn(&Path) -> PostResolveAction + Send + Sync>);
+
+impl PostResolve {
+    pub fn new<F: Fn(&Path) -> PostResolveAction + Send + Sync + 'static>(hook: F) -> Self {
+        Self(Arc::new(hook))
+    }
+
+    #[must_use]
+    pub fn call(&self, path: &Path) -> PostResolveAction {
+        (self.0)(path)
+    }
+}
+
+impl fmt::Debug for PostResolve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PostResolve")
+    }
+}
+
+/// A `package.json` as seen by [ResolveOptions::entry_selector].
+#[derive(Debug, Clone)]
+pub struct PackageEntry<'a> {
+    /// Path to the `package.json`.
+    pub path: &'a Path,
+
+    /// The "type" field.
+    pub r#type: Option<PackageType>,
+
+    /// The [ResolveOptions::main_fields] present in the `package.json` and their values, in the order of the option.
+    pub main_fields: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> PackageEntry<'a> {
+    pub(crate) fn new<P: PackageJson>(package_json: &'a P, main_fields: &'a [String]) -> Self {
+        let main_fields = main_fields
+            .iter()
+            .filter_map(|field| {
+                let value = package_json.main_fields(std::slice::from_ref(field)).next()?;
+                Some((field.as_str(), value))
+            })
+            .collect();
+        Self { path: package_json.path(), r#type: package_json.r#type(), main_fields }
+    }
+
+    /// Returns the value of a main field, e.g. `entry.main_field("module")`.
+    #[must_use]
+    pub fn main_field(&self, field: &str) -> Option<&'a str> {
+        self.main_fields.iter().find(|(name, _)| *name == field).map(|(_, value)| *value)
+    }
+}
+
+type EntrySelectorFn = dyn Fn(&PackageEntry<'_>, &ResolveContext) -> Option<String> + Send + Sync;
+
+/// Selector for [ResolveOptions::entry_selector]
+#[derive(Clone)]
+pub struct EntrySelector(Arc<EntrySelectorFn>);
+
+impl EntrySelector {
+    pub fn new<
+        F: Fn(&PackageEntry<'_>, &ResolveContext) -> Option<String> + Send + Sync + 'static,
+    >(
+        selector: F,
+    ) -> Self {
+        Self(Arc::new(selector))
+    }
+
+    #[must_use]
+    pub fn call(&self, entry: &PackageEntry<'_>, ctx: &ResolveContext) -> Option<String> {
+        (self.0)(entry, ctx)
+    }
+}
+
+impl fmt::Debug for EntrySelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EntrySelector")
+    }
+}
+
+/// Predicate for [ResolveOptions::symlinks_filter]
+#[derive(Clone)]
+pub struct SymlinksFilter(Arc<dyn Fn(&Path) -> bool + Send + Sync>);
+
+impl SymlinksFilter {
+    pub fn new<F: Fn(&Path) -> bool + Send + Sync + 'static>(predicate: F) -> Self {
+        Self(Arc::new(predicate))
+    }
+
+    #[must_use]
+    pub fn call(&self, path: &Path) -> bool {
+        (self.0)(path)
+    }
+}
+
+impl fmt::Debug for SymlinksFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SymlinksFilter")
+    }
+}
+
+/// Value returned by [ResolveOptions::post_resolve]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PostResolveAction {
+    /// Keep the resolved path.
+    Accept,
+
+    /// Fail the resolution with [crate::ResolveError::Rejected] and the reason.
+    Reject(String),
+
+    /// Replace the resolved path, [ResolveOptions::restrictions] are checked again.
+    Redirect(PathBuf),
+}
 
 /// Tsconfig Options for [ResolveOptions::tsconfig]
 ///
@@ -457,6 +1598,53 @@ pub enum TsconfigReferences {
     Paths(Vec<PathBuf>),
 }
 
+/// Options for [ResolveOptions::typescript]
+#[derive(Debug, Clone)]
+pub struct TypeScriptOptions {
+    /// Resolve Node.js builtin modules to their declaration files in the installed `@types/node` package.
+    ///
+    /// e.g. `node:fs` and `fs` resolve to `@types/node/fs.d.ts`,
+    /// `node:fs/promises` resolves to `@types/node/fs/promises.d.ts`.
+    ///
+    /// Default `true`
+    pub node_builtin_types: bool,
+
+    /// Add the `types` condition to [ResolveOptions::condition_names] when resolving the package root `.` with "exports",
+    /// so the declaration entry is returned.
+    ///
+    /// e.g. `{ "exports": { ".": { "types": "./index.d.ts", "default": "./index.js" } } }` resolves to `./index.d.ts`.
+    ///
+    /// Default `true`
+    pub types_condition: bool,
+
+    /// When a subpath of "exports" resolves to a JavaScript file,
+    /// return its sibling declaration file instead if it exists, the same as TypeScript does when the `types` condition is absent.
+    ///
+    /// e.g. `./index.js` resolves to `./index.d.ts`, `./index.mjs` to `./index.d.mts` and `./index.cjs` to `./index.d.cts`.
+    ///
+    /// Default `false`
+    pub sibling_declarations: bool,
+
+    /// Scope directories searched in order for declaration packages, e.g. `@types/node`
+    /// for [TypeScriptOptions::node_builtin_types].
+    ///
+    /// Useful when types are mirrored under a private scope, e.g. `vec!["@company-types".into(), "@types".into()]`.
+    ///
+    /// Default `["@types"]`
+    pub types_scopes: Vec<String>,
+}
+
+impl Default for TypeScriptOptions {
+    fn default() -> Self {
+        Self {
+            node_builtin_types: true,
+            types_condition: true,
+            sibling_declarations: false,
+            types_scopes: vec!["@types".into()],
+        }
+    }
+}
+
 impl Default for ResolveOptions {
     fn default() -> Self {
         Self {
@@ -484,12 +1672,44 @@ impl Default for ResolveOptions {
             roots: vec![],
             symlinks: true,
             builtin_modules: false,
+            typescript: None,
+            prefer_extensions: false,
+            prefer_main_over_exports: false,
+            workspaces: vec![],
+            skip_invalid_package_json: false,
+            strict_esm: false,
+            honor_files_field: false,
+            tsconfig_auto_discovery: false,
+            type_aware_index: false,
+            post_resolve: None,
+            extensions_read_dir_threshold: None,
+            abort_signal: None,
+            mode: None,
+            lazy_symlinks: false,
+            folder_mapping: true,
+            builtin_modules_list: None,
+            flat_modules: None,
+            flat_modules_fallback: false,
+            symlinks_filter: None,
+            strict_exports_targets: false,
+            node_modules_read_dir: false,
+            target_node_version: None,
+            module_type_from_conditions: false,
+            fallback_extensions: vec![],
+            entry_selector: None,
+            exports_fallback_to_main: false,
+            exports_only: false,
+            trim_specifier_whitespace: false,
+            overrides: vec![],
+            directory_index_only: false,
+            prefer_esm: false,
         }
     }
 }
 
 // For tracing
 impl fmt::Display for ResolveOptions {
+    #[allow(clippy::cognitive_complexity, clippy::too_many_lines)] // a flat list of fields
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if let Some(tsconfig) = &self.tsconfig {
             write!(f, "tsconfig:{tsconfig:?},")?;
@@ -554,6 +1774,99 @@ impl fmt::Display for ResolveOptions {
         if self.builtin_modules {
             write!(f, "builtin_modules:{:?},", self.builtin_modules)?;
         }
+        if let Some(typescript) = &self.typescript {
+            write!(f, "typescript:{typescript:?},")?;
+        }
+        if self.prefer_extensions {
+            write!(f, "prefer_extensions:{:?},", self.prefer_extensions)?;
+        }
+        if self.prefer_main_over_exports {
+            write!(f, "prefer_main_over_exports:{:?},", self.prefer_main_over_exports)?;
+        }
+        if !self.workspaces.is_empty() {
+            write!(f, "workspaces:{:?},", self.workspaces)?;
+        }
+        if self.skip_invalid_package_json {
+            write!(f, "skip_invalid_package_json:{:?},", self.skip_invalid_package_json)?;
+        }
+        if self.strict_esm {
+            write!(f, "strict_esm:{:?},", self.strict_esm)?;
+        }
+        if self.honor_files_field {
+            write!(f, "honor_files_field:{:?},", self.honor_files_field)?;
+        }
+        if self.tsconfig_auto_discovery {
+            write!(f, "tsconfig_auto_discovery:{:?},", self.tsconfig_auto_discovery)?;
+        }
+        if self.type_aware_index {
+            write!(f, "type_aware_index:{:?},", self.type_aware_index)?;
+        }
+        if let Some(post_resolve) = &self.post_resolve {
+            write!(f, "post_resolve:{post_resolve:?},")?;
+        }
+        if self.extensions_read_dir_threshold.is_some() {
+            write!(f, "extensions_read_dir_threshold:{:?},", self.extensions_read_dir_threshold)?;
+        }
+        if self.abort_signal.is_some() {
+            write!(f, "abort_signal:{:?},", self.abort_signal)?;
+        }
+        if self.mode.is_some() {
+            write!(f, "mode:{:?},", self.mode)?;
+        }
+        if self.lazy_symlinks {
+            write!(f, "lazy_symlinks:{:?},", self.lazy_symlinks)?;
+        }
+        if !self.folder_mapping {
+            write!(f, "folder_mapping:{:?},", self.folder_mapping)?;
+        }
+        if self.builtin_modules_list.is_some() {
+            write!(f, "builtin_modules_list:{:?},", self.builtin_modules_list)?;
+        }
+        if self.flat_modules.is_some() {
+            write!(f, "flat_modules:{:?},", self.flat_modules)?;
+        }
+        if self.flat_modules_fallback {
+            write!(f, "flat_modules_fallback:{:?},", self.flat_modules_fallback)?;
+        }
+        if let Some(symlinks_filter) = &self.symlinks_filter {
+            write!(f, "symlinks_filter:{symlinks_filter:?},")?;
+        }
+        if self.strict_exports_targets {
+            write!(f, "strict_exports_targets:{:?},", self.strict_exports_targets)?;
+        }
+        if self.node_modules_read_dir {
+            write!(f, "node_modules_read_dir:{:?},", self.node_modules_read_dir)?;
+        }
+        if let Some(target_node_version) = &self.target_node_version {
+            write!(f, "target_node_version:{target_node_version:?},")?;
+        }
+        if self.module_type_from_conditions {
+            write!(f, "module_type_from_conditions:{:?},", self.module_type_from_conditions)?;
+        }
+        if !self.fallback_extensions.is_empty() {
+            write!(f, "fallback_extensions:{:?},", self.fallback_extensions)?;
+        }
+        if let Some(entry_selector) = &self.entry_selector {
+            write!(f, "entry_selector:{entry_selector:?},")?;
+        }
+        if self.exports_fallback_to_main {
+            write!(f, "exports_fallback_to_main:{:?},", self.exports_fallback_to_main)?;
+        }
+        if self.exports_only {
+            write!(f, "exports_only:{:?},", self.exports_only)?;
+        }
+        if self.trim_specifier_whitespace {
+            write!(f, "trim_specifier_whitespace:{:?},", self.trim_specifier_whitespace)?;
+        }
+        if !self.overrides.is_empty() {
+            write!(f, "overrides:{:?},", self.overrides)?;
+        }
+        if self.directory_index_only {
+            write!(f, "directory_index_only:{:?},", self.directory_index_only)?;
+        }
+        if self.prefer_esm {
+            write!(f, "prefer_esm:{:?},", self.prefer_esm)?;
+        }
         Ok(())
     }
 }
@@ -564,7 +1877,7 @@ mod test {
 
     use super::{
         AliasValue, EnforceExtension, ResolveOptions, Restriction, TsconfigOptions,
-        TsconfigReferences,
+        TsconfigReferences, TypeScriptOptions,
     };
 
     #[test]
@@ -604,10 +1917,11 @@ mod test {
             restrictions: vec![Restriction::Path(PathBuf::from("restrictions"))],
             roots: vec![PathBuf::from("roots")],
             builtin_modules: true,
+            typescript: Some(TypeScriptOptions::default()),
             ..ResolveOptions::default()
         };
 
-        let expected = r#"tsconfig:TsconfigOptions { config_file: "tsconfig.json", references: Auto },alias:[("a", [Ignore])],alias_fields:[["browser"]],condition_names:["require"],enforce_extension:Enabled,exports_fields:[["exports"]],imports_fields:[["imports"]],extension_alias:[(".js", [".ts"])],extensions:[".js", ".json", ".node"],fallback:[("fallback", [Ignore])],fully_specified:true,main_fields:["main"],main_files:["index"],modules:["node_modules"],resolve_to_context:true,prefer_relative:true,prefer_absolute:true,restrictions:[Path("restrictions")],roots:["roots"],symlinks:true,builtin_modules:true,"#;
+        let expected = r#"tsconfig:TsconfigOptions { config_file: "tsconfig.json", references: Auto },alias:[("a", [Ignore])],alias_fields:[["browser"]],condition_names:["require"],enforce_extension:Enabled,exports_fields:[["exports"]],imports_fields:[["imports"]],extension_alias:[(".js", [".ts"])],extensions:[".js", ".json", ".node"],fallback:[("fallback", [Ignore])],fully_specified:true,main_fields:["main"],main_files:["index"],modules:["node_modules"],resolve_to_context:true,prefer_relative:true,prefer_absolute:true,restrictions:[Path("restrictions")],roots:["roots"],symlinks:true,builtin_modules:true,typescript:TypeScriptOptions { node_builtin_types: true, types_condition: true, sibling_declarations: false, types_scopes: ["@types"] },"#;
         assert_eq!(format!("{options}"), expected);
 
         let options = ResolveOptions {
@@ -635,6 +1949,37 @@ mod test {
             roots: vec![],
             symlinks: false,
             tsconfig: None,
+            typescript: None,
+            prefer_extensions: false,
+            prefer_main_over_exports: false,
+            workspaces: vec![],
+            skip_invalid_package_json: false,
+            strict_esm: false,
+            honor_files_field: false,
+            tsconfig_auto_discovery: false,
+            type_aware_index: false,
+            post_resolve: None,
+            extensions_read_dir_threshold: None,
+            abort_signal: None,
+            mode: None,
+            lazy_symlinks: false,
+            folder_mapping: true,
+            builtin_modules_list: None,
+            flat_modules: None,
+            flat_modules_fallback: false,
+            symlinks_filter: None,
+            strict_exports_targets: false,
+            node_modules_read_dir: false,
+            target_node_version: None,
+            module_type_from_conditions: false,
+            fallback_extensions: vec![],
+            entry_selector: None,
+            exports_fallback_to_main: false,
+            exports_only: false,
+            trim_specifier_whitespace: false,
+            overrides: vec![],
+            directory_index_only: false,
+            prefer_esm: false,
         };
 
         assert_eq!(format!("{options}"), "");
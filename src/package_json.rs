@@ -43,11 +43,52 @@ pub trait PackageJson: Sized {
     /// <https://nodejs.org/api/packages.html#name>
     fn name(&self) -> Option<&str>;
 
+    /// Version of the package.
+    ///
+    /// <https://docs.npmjs.com/cli/configuring-npm/package-json#version>
+    fn version(&self) -> Option<&str> {
+        None
+    }
+
+    /// Files included when the package is published, in the order they are declared.
+    ///
+    /// <https://docs.npmjs.com/cli/configuring-npm/package-json#files>
+    fn files(&self) -> Option<impl Iterator<Item = &str>> {
+        None::<std::iter::Empty<&str>>
+    }
+
+    /// Runtimes the package works on and their semver ranges, e.g. `("node", ">=18")`.
+    ///
+    /// <https://docs.npmjs.com/cli/configuring-npm/package-json#engines>
+    fn engines(&self) -> Option<impl Iterator<Item = (&str, &str)>> {
+        None::<std::iter::Empty<(&str, &str)>>
+    }
+
     /// Returns the package type, if one is configured in the `package.json`.
     ///
     /// <https://nodejs.org/api/packages.html#type>
     fn r#type(&self) -> Option<PackageType>;
 
+    /// Whether the top-level `field` is set, including fields unknown to the resolver.
+    ///
+    /// Used by [crate::ResolverGeneric::find_package_json_with_field].
+    ///
+    /// The default implementation only knows about the fields read by this trait
+    /// and returns `false` for any other field, override it to support them.
+    fn has_field(&self, field: &str) -> bool {
+        match field {
+            "name" => self.name().is_some(),
+            "version" => self.version().is_some(),
+            "type" => self.r#type().is_some(),
+            "files" => self.files().is_some(),
+            "engines" => self.engines().is_some(),
+            "main" => self.main_fields(&[field.to_string()]).next().is_some(),
+            "exports" => self.exports_fields(&[vec![field.to_string()]]).next().is_some(),
+            "imports" => self.imports_fields(&[vec![field.to_string()]]).next().is_some(),
+            _ => false,
+        }
+    }
+
     /// The "main" field defines the entry point of a package when imported by
     /// name via a node_modules lookup. Its value should be a path.
     ///
@@ -82,6 +123,14 @@ pub trait PackageJson: Sized {
         imports_fields: &'a [Vec<String>],
     ) -> impl Iterator<Item = impl ImportsExportsMap<'a>> + 'a;
 
+    /// Returns whether any of the [crate::ResolveOptions::alias_fields] has a
+    /// shape that is ignored, i.e. it is neither a string, an object nor an
+    /// array of objects.
+    #[must_use]
+    fn has_invalid_browser_field(&self, _alias_fields: &[Vec<String>]) -> bool {
+        false
+    }
+
     /// Resolves the request string for this `package.json` by looking at the
     /// "browser" field.
     ///
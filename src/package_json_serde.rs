@@ -29,6 +29,9 @@ pub struct PackageJsonSerde {
     /// Name of the package.
     pub name: Option<String>,
 
+    /// Version of the package.
+    pub version: Option<String>,
+
     /// The "type" field.
     ///
     /// <https://nodejs.org/api/packages.html#type>
@@ -53,7 +56,6 @@ impl PackageJson for PackageJsonSerde {
     }
 
     fn directory(&self) -> &Path {
-        debug_assert!(self.realpath.file_name().is_some_and(|x| x == "package.json"));
         self.realpath.parent().unwrap()
     }
 
@@ -61,10 +63,31 @@ impl PackageJson for PackageJsonSerde {
         self.name.as_deref()
     }
 
+    fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    fn files(&self) -> Option<impl Iterator<Item = &str>> {
+        self.raw_json
+            .get("files")
+            .and_then(JSONValue::as_array)
+            .map(|files| files.iter().filter_map(JSONValue::as_str))
+    }
+
+    fn engines(&self) -> Option<impl Iterator<Item = (&str, &str)>> {
+        self.raw_json.get("engines").and_then(JSONValue::as_object).map(|engines| {
+            engines.iter().filter_map(|(name, range)| Some((name.as_str(), range.as_str()?)))
+        })
+    }
+
     fn r#type(&self) -> Option<PackageType> {
         self.r#type
     }
 
+    fn has_field(&self, field: &str) -> bool {
+        self.raw_json.get(field).is_some()
+    }
+
     fn main_fields<'a>(&'a self, main_fields: &'a [String]) -> impl Iterator<Item = &'a str> + 'a {
         main_fields
             .iter()
@@ -101,6 +124,20 @@ impl PackageJson for PackageJsonSerde {
             .map(ImportsExportsSerdeMap)
     }
 
+    fn has_invalid_browser_field(&self, alias_fields: &[Vec<String>]) -> bool {
+        alias_fields.iter().any(|object_path| {
+            self.raw_json
+                .as_object()
+                .and_then(|json_object| Self::get_value_by_path(json_object, object_path))
+                .is_some_and(|value| match value {
+                    // A string is the alternative main field.
+                    JSONValue::String(_) | JSONValue::Object(_) => false,
+                    JSONValue::Array(array) => !array.iter().all(JSONValue::is_object),
+                    _ => true,
+                })
+        })
+    }
+
     fn resolve_browser_field<'a>(
         &'a self,
         path: &Path,
@@ -129,15 +166,30 @@ impl PackageJson for PackageJsonSerde {
 impl PackageJsonSerde {
     /// # Panics
     /// # Errors
+    ///
+    /// * [ResolveError::JSON]
+    /// * [ResolveError::InvalidPackageConfig] when "imports" has a bare `#` key
     pub(crate) fn parse(
         path: PathBuf,
         realpath: PathBuf,
         json: &str,
-    ) -> Result<Self, serde_json::Error> {
-        let mut raw_json: JSONValue = serde_json::from_str(json)?;
+    ) -> Result<Self, ResolveError> {
+        // Skip the byte order mark, which is not valid JSON.
+        let json = json.strip_prefix('\u{feff}').unwrap_or(json);
+        let mut raw_json: JSONValue = serde_json::from_str(json)
+            .map_err(|error| ResolveError::from_serde_json_error(path.clone(), &error))?;
         let mut package_json = Self::default();
 
         if let Some(json_object) = raw_json.as_object_mut() {
+            // A bare "#" can never be imported, reject the key when the package.json is read.
+            if json_object
+                .get("imports")
+                .and_then(JSONValue::as_object)
+                .is_some_and(|imports| imports.contains_key("#"))
+            {
+                return Err(ResolveError::InvalidPackageConfig(path));
+            }
+
             // Remove large fields that are useless for pragmatic use.
             #[cfg(feature = "package_json_raw_json_api")]
             {
@@ -150,9 +202,13 @@ impl PackageJsonSerde {
                 json_object.remove("optionalDependencies");
             }
 
-            // Add name, type and sideEffects.
+            // Add name, version, type and sideEffects.
             package_json.name =
                 json_object.get("name").and_then(|field| field.as_str()).map(ToString::to_string);
+            package_json.version = json_object
+                .get("version")
+                .and_then(|field| field.as_str())
+                .map(ToString::to_string);
             package_json.r#type =
                 json_object.get("type").and_then(|ty| serde_json::from_value(ty.clone()).ok());
             package_json.side_effects = json_object.get("sideEffects").cloned();
@@ -205,14 +261,22 @@ impl PackageJsonSerde {
         &'a self,
         alias_fields: &'a [Vec<String>],
     ) -> impl Iterator<Item = &'a JSONMap> + 'a {
-        alias_fields.iter().filter_map(|object_path| {
-            self.raw_json
-                .as_object()
-                .and_then(|json_object| Self::get_value_by_path(json_object, object_path))
-                // Only object is valid, all other types are invalid
-                // https://github.com/webpack/enhanced-resolve/blob/3a28f47788de794d9da4d1702a3a583d8422cd48/lib/AliasFieldPlugin.js#L44-L52
-                .and_then(|value| value.as_object())
-        })
+        alias_fields
+            .iter()
+            .filter_map(|object_path| {
+                self.raw_json
+                    .as_object()
+                    .and_then(|json_object| Self::get_value_by_path(json_object, object_path))
+            })
+            // Only object is valid, all other types are invalid
+            // https://github.com/webpack/enhanced-resolve/blob/3a28f47788de794d9da4d1702a3a583d8422cd48/lib/AliasFieldPlugin.js#L44-L52
+            // An array of objects used by some bundlers is also tolerated, the objects are looked up in order.
+            .flat_map(|value| {
+                value
+                    .as_object()
+                    .into_iter()
+                    .chain(value.as_array().into_iter().flatten().filter_map(JSONValue::as_object))
+            })
     }
 
     fn alias_value<'a>(key: &Path, value: &'a JSONValue) -> Result<Option<&'a str>, ResolveError> {
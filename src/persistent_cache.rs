@@ -0,0 +1,197 @@
+//! On-disk cache of resolutions, see [crate::ResolverGeneric::with_persistent_cache].
+
+use std::{
+    hash::{BuildHasherDefault, Hash, Hasher},
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::UNIX_EPOCH,
+};
+
+use papaya::HashMap;
+use rustc_hash::FxHasher;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    context::ResolveContext as Ctx, FileMetadata, FileSystem, PackageType, ResolveOptions,
+};
+
+/// A resolution and the files it was computed from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistentEntry {
+    pub path: PathBuf,
+    pub query: Option<String>,
+    pub fragment: Option<String>,
+    pub condition_module_type: Option<PackageType>,
+    pub export_subpath: Option<String>,
+    /// Files found while resolving and their modification time as `(seconds, nanoseconds)`.
+    files: Vec<(PathBuf, Option<(u64, u32)>)>,
+    /// Files that did not exist while resolving.
+    missing: Vec<PathBuf>,
+    /// Directories found while resolving.
+    directories: Vec<PathBuf>,
+}
+
+impl PersistentEntry {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<
+        F: IntoIterator<Item = PathBuf>,
+        M: IntoIterator<Item = PathBuf>,
+        D: IntoIterator<Item = PathBuf>,
+    >(
+        fs: &dyn FileSystem,
+        path: PathBuf,
+        query: Option<String>,
+        fragment: Option<String>,
+        condition_module_type: Option<PackageType>,
+        export_subpath: Option<String>,
+        files: F,
+        missing: M,
+        directories: D,
+    ) -> Self {
+        let files = files
+            .into_iter()
+            .map(|file| {
+                let mtime = modified(fs, &file);
+                (file, mtime)
+            })
+            .collect();
+        let missing = missing.into_iter().collect();
+        let directories = directories.into_iter().collect();
+        Self {
+            path,
+            query,
+            fragment,
+            condition_module_type,
+            export_subpath,
+            files,
+            missing,
+            directories,
+        }
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|(file, _)| file.as_path())
+    }
+
+    pub fn missing(&self) -> impl Iterator<Item = &Path> {
+        self.missing.iter().map(PathBuf::as_path)
+    }
+
+    pub fn directories(&self) -> impl Iterator<Item = &Path> {
+        self.directories.iter().map(PathBuf::as_path)
+    }
+
+    /// Whether the recorded files are unchanged, the missing ones still do not exist
+    /// and the directories still exist.
+    fn is_fresh(&self, fs: &dyn FileSystem) -> bool {
+        self.files.iter().all(|(file, mtime)| modified(fs, file) == *mtime)
+            && self.missing.iter().all(|file| fs.symlink_metadata(file).is_err())
+            && self.directories.iter().all(|dir| fs.metadata(dir).is_ok_and(FileMetadata::is_dir))
+    }
+}
+
+fn modified(fs: &dyn FileSystem, path: &Path) -> Option<(u64, u32)> {
+    let duration = fs.metadata(path).ok()?.modified()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((duration.as_secs(), duration.subsec_nanos()))
+}
+
+/// A resolve call: the directory, the specifier and the per-call state that changes its result.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct PersistentKey {
+    directory: PathBuf,
+    specifier: String,
+    /// See [crate::ResolverGeneric::resolve_with_extensions].
+    extensions: Option<Vec<String>>,
+    fully_specified: bool,
+}
+
+impl PersistentKey {
+    pub fn new(directory: &Path, specifier: &str, ctx: &Ctx) -> Self {
+        Self {
+            directory: directory.to_path_buf(),
+            specifier: specifier.to_string(),
+            extensions: ctx.extensions.as_deref().map(<[String]>::to_vec),
+            fully_specified: ctx.fully_specified,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PersistentCacheFile {
+    fingerprint: u64,
+    entries: Vec<(PersistentKey, PersistentEntry)>,
+}
+
+/// Resolutions keyed by [PersistentKey], stored in a JSON file together with
+/// a fingerprint of the [ResolveOptions] they were resolved with.
+///
+/// The file and the dependencies of the entries are accessed through the [FileSystem] of the resolver.
+pub struct PersistentCache {
+    path: PathBuf,
+    fingerprint: u64,
+    fs: Arc<dyn FileSystem>,
+    entries: HashMap<PersistentKey, Arc<PersistentEntry>, BuildHasherDefault<FxHasher>>,
+}
+
+impl PersistentCache {
+    /// Loads the cache file at `path`, starting empty when it is missing, unreadable or
+    /// written with different options.
+    pub fn load(path: PathBuf, options: &ResolveOptions, fs: Arc<dyn FileSystem>) -> Self {
+        let fingerprint = Self::fingerprint(options);
+        let entries = HashMap::default();
+        if let Some(file) = fs
+            .read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<PersistentCacheFile>(&json).ok())
+            .filter(|file| file.fingerprint == fingerprint)
+        {
+            let pinned = entries.pin();
+            for (key, entry) in file.entries {
+                pinned.insert(key, Arc::new(entry));
+            }
+        }
+        Self { path, fingerprint, fs, entries }
+    }
+
+    /// Options are compared by their fingerprint, together with the crate version.
+    fn fingerprint(options: &ResolveOptions) -> u64 {
+        let mut hasher = FxHasher::default();
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        options.fingerprint().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    pub fn file_system(&self) -> &dyn FileSystem {
+        self.fs.as_ref()
+    }
+
+    /// Returns the entry when none of its dependencies changed, evicting it otherwise.
+    pub fn get(&self, key: &PersistentKey) -> Option<Arc<PersistentEntry>> {
+        let entries = self.entries.pin();
+        let entry = entries.get(key)?;
+        if entry.is_fresh(self.fs.as_ref()) {
+            return Some(Arc::clone(entry));
+        }
+        entries.remove(key);
+        None
+    }
+
+    pub fn insert(&self, key: PersistentKey, entry: PersistentEntry) {
+        self.entries.pin().insert(key, Arc::new(entry));
+    }
+
+    /// Writes all entries to the cache file.
+    pub fn save(&self) -> io::Result<()> {
+        let mut entries = self
+            .entries
+            .pin()
+            .iter()
+            .map(|(key, entry)| (key.clone(), PersistentEntry::clone(entry)))
+            .collect::<Vec<_>>();
+        entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        let file = PersistentCacheFile { fingerprint: self.fingerprint, entries };
+        let json = serde_json::to_vec(&file).map_err(io::Error::from)?;
+        self.fs.write(&self.path, &json)
+    }
+}
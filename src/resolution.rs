@@ -4,7 +4,7 @@ use std::{
     sync::Arc,
 };
 
-use crate::{Cache, PackageJson};
+use crate::{Cache, PackageJson, PackageType};
 
 /// The final path resolution with optional `?query` and `#fragment`
 pub struct Resolution<C: Cache> {
@@ -17,6 +17,12 @@ pub struct Resolution<C: Cache> {
     pub(crate) fragment: Option<String>,
 
     pub(crate) package_json: Option<Arc<C::Pj>>,
+
+    /// See [crate::ResolveOptions::module_type_from_conditions].
+    pub(crate) condition_module_type: Option<PackageType>,
+
+    /// See [Resolution::matched_export_subpath].
+    pub(crate) export_subpath: Option<String>,
 }
 
 impl<C: Cache> Clone for Resolution<C> {
@@ -26,6 +32,8 @@ impl<C: Cache> Clone for Resolution<C> {
             query: self.query.clone(),
             fragment: self.fragment.clone(),
             package_json: self.package_json.clone(),
+            condition_module_type: self.condition_module_type,
+            export_subpath: self.export_subpath.clone(),
         }
     }
 }
@@ -37,6 +45,8 @@ impl<C: Cache> fmt::Debug for Resolution<C> {
             .field("query", &self.query)
             .field("fragment", &self.fragment)
             .field("package_json", &self.package_json.as_ref().map(|p| p.path()))
+            .field("condition_module_type", &self.condition_module_type)
+            .field("export_subpath", &self.export_subpath)
             .finish()
     }
 }
@@ -50,6 +60,9 @@ impl<C: Cache> Eq for Resolution<C> {}
 
 impl<C: Cache> Resolution<C> {
     /// Returns the path without query and fragment
+    ///
+    /// The case of the requested path is preserved on case-insensitive file systems,
+    /// see [crate::ResolveOptions::symlinks].
     #[must_use]
     pub fn path(&self) -> &Path {
         &self.path
@@ -61,6 +74,20 @@ impl<C: Cache> Resolution<C> {
         self.path
     }
 
+    /// Returns the path without query and fragment, using `/` as the separator on all platforms.
+    ///
+    /// On Windows, `C:\foo\bar.js` becomes `C:/foo/bar.js`. Other platforms return the path as is,
+    /// because `\` is a valid file name character there.
+    #[must_use]
+    pub fn to_slash(&self) -> String {
+        let path = self.path.to_string_lossy();
+        if cfg!(windows) {
+            path.replace('\\', "/")
+        } else {
+            path.into_owned()
+        }
+    }
+
     /// Returns the path query `?query`, contains the leading `?`
     #[must_use]
     pub fn query(&self) -> Option<&str> {
@@ -79,6 +106,41 @@ impl<C: Cache> Resolution<C> {
         self.package_json.as_ref()
     }
 
+    /// Returns the "exports" subpath that produced the resolved path, e.g. `./feature` or `.` for the package root.
+    ///
+    /// For a subpath pattern such as `./features/*`, the expanded subpath is returned, e.g. `./features/foo`.
+    /// Returns `None` when the path was not resolved through "exports".
+    #[must_use]
+    pub fn matched_export_subpath(&self) -> Option<&str> {
+        self.export_subpath.as_deref()
+    }
+
+    /// Returns the module type of the resolved file.
+    ///
+    /// An explicit `.mjs` / `.cjs` extension (and `.mts` / `.cts` for TypeScript) always wins,
+    /// otherwise `.js`, `.jsx`, `.ts` and `.tsx` files follow the "import" or "require" condition that selected them
+    /// with [crate::ResolveOptions::module_type_from_conditions],
+    /// or the "type" field of the closest `package.json`, defaulting to CommonJS.
+    ///
+    /// Returns `None` for other extensions such as `.json`.
+    ///
+    /// <https://nodejs.org/api/packages.html#determining-module-system>
+    #[must_use]
+    pub fn module_type(&self) -> Option<PackageType> {
+        match self.path.extension().and_then(|ext| ext.to_str())? {
+            "mjs" | "mts" => Some(PackageType::Module),
+            "cjs" | "cts" => Some(PackageType::CommonJs),
+            "js" | "jsx" | "ts" | "tsx" => Some(
+                self.condition_module_type
+                    .or_else(|| {
+                        self.package_json.as_ref().and_then(|package_json| package_json.r#type())
+                    })
+                    .unwrap_or(PackageType::CommonJs),
+            ),
+            _ => None,
+        }
+    }
+
     /// Returns the full path with query and fragment
     #[must_use]
     pub fn full_path(&self) -> PathBuf {
@@ -92,3 +154,14 @@ impl<C: Cache> Resolution<C> {
         PathBuf::from(path)
     }
 }
+
+/// Whether the lookups of a resolution were served from the cache,
+/// returned by [crate::ResolverGeneric::resolve_with_source].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolveSource {
+    /// All file metadata and `package.json` lookups were served from the cache.
+    Cached,
+
+    /// At least one lookup required fresh IO.
+    Io,
+}
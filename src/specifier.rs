@@ -15,7 +15,7 @@ impl<'a> Specifier<'a> {
     }
 
     pub fn parse(specifier: &'a str) -> Result<Self, SpecifierError> {
-        if specifier.is_empty() {
+        if specifier.trim().is_empty() {
             return Err(SpecifierError::Empty(specifier.to_string()));
         }
         let offset = match specifier.as_bytes()[0] {
@@ -94,13 +94,23 @@ mod tests {
 
     #[test]
     fn empty() {
-        let specifiers = ["", "?"];
+        let specifiers = ["", "?", " ", "   ", "\t\n"];
         for specifier in specifiers {
             let error = Specifier::parse(specifier).unwrap_err();
             assert_eq!(error, SpecifierError::Empty(specifier.to_string()));
         }
     }
 
+    #[test]
+    fn current_and_parent_directory() -> Result<(), SpecifierError> {
+        let specifiers = [".", "..", "./", "../"];
+        for specifier in specifiers {
+            let parsed = Specifier::parse(specifier)?;
+            assert_eq!(parsed.path, specifier);
+        }
+        Ok(())
+    }
+
     #[test]
     fn absolute() -> Result<(), SpecifierError> {
         let specifier = "/test?#";
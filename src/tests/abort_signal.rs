@@ -0,0 +1,96 @@
+//! Tests for [crate::ResolveOptions::abort_signal]
+
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+mod windows {
+    use std::{
+        io,
+        path::{Path, PathBuf},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+    };
+
+    use super::super::memory_fs::MemoryFS;
+    use crate::{FileMetadata, FileSystem, FsCache, ResolveError, ResolveOptions, ResolverGeneric};
+
+    /// Sets the abort signal once a `node_modules` directory is stat'ed, like a caller
+    /// cancelling a resolve that is stuck on a slow file system.
+    struct AbortingFS {
+        fs: MemoryFS,
+        abort_signal: Arc<AtomicBool>,
+    }
+
+    impl FileSystem for AbortingFS {
+        fn read_to_string(&self, path: &Path) -> io::Result<String> {
+            self.fs.read_to_string(path)
+        }
+
+        fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+            if path.ends_with("node_modules") {
+                self.abort_signal.store(true, Ordering::Relaxed);
+            }
+            self.fs.metadata(path)
+        }
+
+        fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+            self.fs.symlink_metadata(path)
+        }
+
+        fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+            self.fs.read_link(path)
+        }
+    }
+
+    fn file_system(abort_signal: &Arc<AtomicBool>) -> AbortingFS {
+        let fs = MemoryFS::new(&[
+            ("/node_modules/pkg/index.js", ""),
+            ("/a/b/c/index.js", ""),
+            ("/a/b/c/foo.js", ""),
+        ]);
+        AbortingFS { fs, abort_signal: Arc::clone(abort_signal) }
+    }
+
+    #[test]
+    fn abort_mid_resolve() {
+        let abort_signal = Arc::new(AtomicBool::new(false));
+        let resolver = ResolverGeneric::new_with_cache(
+            Arc::new(FsCache::new(file_system(&abort_signal))),
+            ResolveOptions {
+                abort_signal: Some(Arc::clone(&abort_signal)),
+                ..ResolveOptions::default()
+            },
+        );
+
+        let resolution = resolver.resolve("/a/b/c", "pkg");
+        assert_eq!(resolution, Err(ResolveError::Aborted));
+        assert!(abort_signal.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn not_aborted() {
+        let abort_signal = Arc::new(AtomicBool::new(false));
+        let resolver = ResolverGeneric::new_with_cache(
+            Arc::new(FsCache::new(file_system(&abort_signal))),
+            ResolveOptions::default(),
+        );
+
+        let resolved_path = resolver.resolve("/a/b/c", "pkg").map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(PathBuf::from("/node_modules/pkg/index.js")));
+    }
+
+    #[test]
+    fn aborted_before_canonicalize() {
+        let abort_signal = Arc::new(AtomicBool::new(true));
+        let resolver = ResolverGeneric::new_with_cache(
+            Arc::new(FsCache::new(file_system(&abort_signal))),
+            ResolveOptions {
+                abort_signal: Some(Arc::clone(&abort_signal)),
+                ..ResolveOptions::default()
+            },
+        );
+
+        let resolution = resolver.resolve("/a/b/c", "./foo");
+        assert_eq!(resolution, Err(ResolveError::Aborted));
+    }
+}
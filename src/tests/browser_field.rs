@@ -1,6 +1,8 @@
 //! <https://github.com/webpack/enhanced-resolve/blob/main/test/browserField.test.js>
 
-use crate::{AliasValue, ResolveError, ResolveOptions, Resolver};
+use crate::{
+    AliasValue, ResolveContext, ResolveDiagnostic, ResolveError, ResolveOptions, Resolver,
+};
 
 #[test]
 fn ignore() {
@@ -187,3 +189,56 @@ fn with_query() {
     let resolved_path = resolver.resolve(&f, "./foo").map(|r| r.full_path());
     assert_eq!(resolved_path, Ok(f.join("lib").join("browser.js?query")));
 }
+
+#[test]
+fn array_of_objects() {
+    let f = super::fixture_root().join("misc/browser-field-shapes");
+
+    let resolver = Resolver::new(ResolveOptions {
+        alias_fields: vec![vec!["browser".into()]],
+        ..ResolveOptions::default()
+    });
+
+    let module = f.join("node_modules/array");
+    let resolved_path = resolver.resolve(&f, "array").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(module.join("lib/browser.js")));
+
+    let resolution = resolver.resolve(&module, "./lib/node.js");
+    assert_eq!(resolution, Err(ResolveError::Ignored(module.join("lib/node.js"))));
+}
+
+#[test]
+fn malformed() {
+    let f = super::fixture_root().join("misc/browser-field-shapes");
+
+    let resolver = Resolver::new(ResolveOptions {
+        alias_fields: vec![vec!["browser".into()]],
+        ..ResolveOptions::default()
+    });
+
+    let module = f.join("node_modules/malformed");
+    let mut ctx = ResolveContext::default();
+    let resolved_path =
+        resolver.resolve_with_context(&f, "malformed", &mut ctx).map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(module.join("index.js")));
+    assert_eq!(
+        ctx.diagnostics,
+        vec![ResolveDiagnostic::InvalidBrowserField(module.join("package.json"))]
+    );
+}
+
+#[test]
+fn self_reference_cycle() {
+    let f = super::fixture_root().join("misc/self-reference-cycle");
+
+    let resolver = Resolver::new(ResolveOptions {
+        alias_fields: vec![vec!["browser".into()]],
+        ..ResolveOptions::default()
+    });
+
+    let resolution = resolver.resolve(&f, "browser-cycle/a");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::SelfReferenceCycle("browser-cycle/a".into(), f.join("package.json")))
+    );
+}
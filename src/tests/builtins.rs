@@ -126,3 +126,79 @@ fn imports() {
         assert_eq!(resolved_path, Err(err));
     }
 }
+
+#[test]
+fn builtin_modules_list() {
+    let f = Path::new("/");
+    let resolver = Resolver::new(ResolveOptions {
+        builtin_modules: true,
+        // Unsorted on purpose, sorted by the resolver.
+        builtin_modules_list: Some(vec![
+            "path".into(),
+            "bun".into(),
+            "fs".into(),
+            "bun:test".into(),
+        ]),
+        ..ResolveOptions::default()
+    });
+
+    for request in ["fs", "path"] {
+        let resolved_path = resolver.resolve(f, request).map(|r| r.full_path());
+        let err =
+            ResolveError::Builtin { resolved: format!("node:{request}"), is_runtime_module: false };
+        assert_eq!(resolved_path, Err(err), "{request}");
+    }
+
+    // Builtins of other runtimes are not prefixed with `node:`.
+    for request in ["bun", "bun:test"] {
+        let resolved_path = resolver.resolve(f, request).map(|r| r.full_path());
+        let err = ResolveError::Builtin { resolved: request.into(), is_runtime_module: false };
+        assert_eq!(resolved_path, Err(err), "{request}");
+    }
+
+    // A Node.js builtin not in the list.
+    let resolved_path = resolver.resolve(f, "zlib").map(|r| r.full_path());
+    assert_eq!(resolved_path, Err(ResolveError::NotFound("zlib".into())));
+
+    // The `node:` prefix is always builtin.
+    let resolved_path = resolver.resolve(f, "node:zlib").map(|r| r.full_path());
+    let err = ResolveError::Builtin { resolved: "node:zlib".into(), is_runtime_module: true };
+    assert_eq!(resolved_path, Err(err));
+}
+
+#[test]
+fn node_prefixed_targets() {
+    let f = super::fixture_root().join("misc/node-builtin-targets");
+    let resolver = Resolver::new(ResolveOptions {
+        builtin_modules: true,
+        condition_names: vec!["node".into()],
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let data = [
+        ("#crypto", "node:crypto", true),
+        ("#stream", "node:stream", true),
+        ("#buffer", "node:buffer", false),
+    ];
+
+    for (request, resolved, is_runtime_module) in data {
+        let resolution = resolver.resolve(&f, request);
+        let err = ResolveError::Builtin { resolved: resolved.into(), is_runtime_module };
+        assert_eq!(resolution, Err(err), "{request}");
+    }
+
+    // The polyfill is selected without the "node" condition.
+    let resolver = Resolver::new(ResolveOptions::default().with_builtin_modules(true));
+    let resolved_path = resolver.resolve(&f, "#stream").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("stream-polyfill.js")));
+
+    // Only "imports" may map to bare specifiers, "exports" targets must start with "./".
+    let resolution = resolver.resolve(&f, "polyfilled/crypto");
+    let err = ResolveError::InvalidPackageTarget(
+        "node:crypto".into(),
+        "./crypto".into(),
+        f.join("node_modules/polyfilled/package.json"),
+    );
+    assert_eq!(resolution, Err(err));
+}
@@ -103,4 +103,24 @@ mod windows {
             assert_eq!(ctx.missing_dependencies, missing_dependencies, "{name}");
         }
     }
+
+    #[test]
+    fn context_dependencies() {
+        let resolver = ResolverGeneric::new_with_cache(
+            Arc::new(FsCache::new(file_system())),
+            ResolveOptions {
+                extensions: vec![".json".into(), ".js".into()],
+                modules: vec!["/modules".into(), "node_modules".into()],
+                ..ResolveOptions::default()
+            },
+        );
+
+        let mut ctx = ResolveContext::default();
+        let resolved_path =
+            resolver.resolve_with_context("/a/b/c", "some-module", &mut ctx).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(PathBuf::from("/a/b/node_modules/some-module/index.js")));
+        assert!(ctx.context_dependencies.contains(&PathBuf::from("/a/b/node_modules")));
+        assert!(ctx.context_dependencies.contains(&PathBuf::from("/a/b/node_modules/some-module")));
+        assert!(!ctx.context_dependencies.contains(&PathBuf::from("/a/b/c")));
+    }
 }
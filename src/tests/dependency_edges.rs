@@ -0,0 +1,46 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{DependencyEdge, ResolveContext, Resolver};
+
+#[test]
+fn dependency_edges() {
+    let f = super::fixture_root().join("misc/dependency-edges");
+    let resolver = Resolver::default();
+
+    let mut ctx = ResolveContext { dependency_edges: Some(vec![]), ..ResolveContext::default() };
+
+    #[rustfmt::skip]
+    let chain = [
+        (f.clone(), "a", f.join("node_modules/a/index.js")),
+        (f.join("node_modules/a"), "b", f.join("node_modules/a/node_modules/b/index.js")),
+        // Relative specifiers are not recorded
+        (f.clone(), "./index.js", f.join("index.js")),
+    ];
+
+    for (path, request, expected) in chain {
+        let resolved_path =
+            resolver.resolve_with_context(&path, request, &mut ctx).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{path:?} {request}");
+    }
+
+    assert_eq!(
+        ctx.dependency_edges,
+        Some(vec![
+            DependencyEdge {
+                importer: Some("app".into()),
+                imported: "a".into(),
+                version: Some("1.0.0".into()),
+            },
+            DependencyEdge {
+                importer: Some("a".into()),
+                imported: "b".into(),
+                version: Some("2.0.0".into()),
+            },
+        ])
+    );
+
+    // Not recorded unless opted in
+    let mut ctx = ResolveContext::default();
+    let _ = resolver.resolve_with_context(&f, "a", &mut ctx);
+    assert_eq!(ctx.dependency_edges, None);
+}
@@ -0,0 +1,43 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{PackageJson, ResolveOptions, Resolver};
+
+#[test]
+fn description_files() {
+    let f = super::fixture_root().join("misc/description-files");
+    let node_modules = f.join("node_modules");
+
+    let resolver = Resolver::default();
+    let resolved_path = resolver.resolve(&f, "both").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(node_modules.join("both/node.js")));
+
+    let resolver = Resolver::new(ResolveOptions {
+        description_files: vec!["deno.json".into(), "package.json".into()],
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("deno-pkg", node_modules.join("deno-pkg/mod.js")),
+        // deno.json is tried first.
+        ("both", node_modules.join("both/deno.js")),
+        // A description file that does not parse is skipped.
+        ("broken", node_modules.join("broken/main.js")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    // The description file is found in the parent directory.
+    let resolution = resolver.resolve(&f, "./node_modules/deno-pkg/sub/file.js").unwrap();
+    let package_json = resolution.package_json().unwrap();
+    assert_eq!(package_json.path(), node_modules.join("deno-pkg/deno.json"));
+    assert_eq!(package_json.name(), Some("deno-pkg"));
+
+    // The cache is shared with resolvers reading other description files.
+    let resolver = resolver.clone_with_options(ResolveOptions::default());
+    let resolved_path = resolver.resolve(&f, "both").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(node_modules.join("both/node.js")));
+}
@@ -0,0 +1,72 @@
+//! Not part of enhanced_resolve's test suite
+
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+mod windows {
+    use std::{path::PathBuf, sync::Arc};
+
+    use super::super::memory_fs::MemoryFS;
+    use crate::{FsCache, ResolveContext, ResolveDiagnostic, ResolveOptions, ResolverGeneric};
+
+    #[test]
+    fn main_field_ignored() {
+        let file_system = MemoryFS::new(&[
+            (
+                "/node_modules/both/package.json",
+                r#"{"main":"./main.js","exports":{".":"./exports.js"}}"#,
+            ),
+            ("/node_modules/both/main.js", ""),
+            ("/node_modules/both/exports.js", ""),
+            ("/node_modules/main-only/package.json", r#"{"main":"./main.js"}"#),
+            ("/node_modules/main-only/main.js", ""),
+        ]);
+
+        let resolver = ResolverGeneric::new_with_cache(
+            Arc::new(FsCache::new(file_system)),
+            ResolveOptions::default(),
+        );
+
+        let mut ctx = ResolveContext::default();
+        let resolved_path =
+            resolver.resolve_with_context("/", "both", &mut ctx).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(PathBuf::from("/node_modules/both/exports.js")));
+        assert_eq!(
+            ctx.diagnostics,
+            vec![ResolveDiagnostic::MainFieldIgnored(PathBuf::from(
+                "/node_modules/both/package.json"
+            ))]
+        );
+
+        let mut ctx = ResolveContext::default();
+        let resolved_path =
+            resolver.resolve_with_context("/", "main-only", &mut ctx).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(PathBuf::from("/node_modules/main-only/main.js")));
+        assert!(ctx.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn exports_target_not_found() {
+        let file_system = MemoryFS::new(&[
+            (
+                "/node_modules/broken/package.json",
+                r#"{"main":"./main.js","exports":{".":"./missing.js"}}"#,
+            ),
+            ("/node_modules/broken/main.js", ""),
+        ]);
+
+        let resolver = ResolverGeneric::new_with_cache(
+            Arc::new(FsCache::new(file_system)),
+            ResolveOptions { exports_fallback_to_main: true, ..ResolveOptions::default() },
+        );
+
+        let mut ctx = ResolveContext::default();
+        let resolved_path =
+            resolver.resolve_with_context("/", "broken", &mut ctx).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(PathBuf::from("/node_modules/broken/main.js")));
+        assert_eq!(
+            ctx.diagnostics,
+            vec![ResolveDiagnostic::ExportsTargetNotFound(PathBuf::from(
+                "/node_modules/broken/package.json"
+            ))]
+        );
+    }
+}
@@ -2,13 +2,13 @@
 //!
 //! The huge exports field test cases are at the bottom of this file.
 
-use std::path::Path;
+use std::{path::Path, sync::Arc};
 
 use serde_json::json;
 
 use crate::{
-    cache::CachedPath, package_json_serde::ImportsExportsSerdeEntry, Cache, Ctx, PathUtil,
-    ResolveError, ResolveOptions, Resolver,
+    cache::CachedPath, package_json_serde::ImportsExportsSerdeEntry, BuildMode, Cache, Ctx,
+    PathUtil, ResolveContext, ResolveError, ResolveOptions, Resolver,
 };
 
 #[test]
@@ -208,6 +208,49 @@ fn shared_resolvers() {
     assert_eq!(resolved_path, Ok(f3.join("node_modules/exports-field/index")));
 }
 
+#[test]
+fn clone_with_condition_names() {
+    let f = super::fixture_root().join("misc/typescript");
+
+    let resolver1 = Resolver::new(ResolveOptions {
+        condition_names: vec!["require".into()],
+        ..ResolveOptions::default()
+    });
+    let resolved_path = resolver1.resolve(&f, "dual").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("node_modules/dual/index.cjs")));
+
+    let resolver2 = resolver1.clone_with_condition_names(&["import"]);
+    assert!(Arc::ptr_eq(&resolver1.cache, &resolver2.cache));
+    assert_eq!(resolver2.options().condition_names, vec!["import".to_string()]);
+    let resolved_path = resolver2.resolve(&f, "dual").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("node_modules/dual/index.mjs")));
+}
+
+#[test]
+fn available_conditions() {
+    let f = super::fixture_root().join("misc/typescript");
+
+    let resolver = Resolver::new(ResolveOptions {
+        condition_names: vec!["require".into()],
+        ..ResolveOptions::default()
+    });
+
+    let mut ctx =
+        ResolveContext { available_conditions: Some(vec![]), ..ResolveContext::default() };
+    let resolved_path = resolver.resolve_with_context(&f, "dual", &mut ctx).map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("node_modules/dual/index.cjs")));
+    // Conditions after the matched `require` are reported too.
+    assert_eq!(
+        ctx.available_conditions,
+        Some(vec!["types".into(), "import".into(), "require".into()])
+    );
+
+    // Not recorded unless opted in.
+    let mut ctx = ResolveContext::default();
+    assert!(resolver.resolve_with_context(&f, "dual", &mut ctx).is_ok());
+    assert_eq!(ctx.available_conditions, None);
+}
+
 #[test]
 fn extension_alias_1_2() {
     let f = super::fixture().join("exports-field-and-extension-alias");
@@ -285,6 +328,604 @@ fn extension_alias_throw_error() {
     }
 }
 
+#[test]
+fn subpath_query_fragment() {
+    let f = super::fixture_root().join("misc/exports-subpath-query");
+    let p = f.join("node_modules/pkg");
+
+    let resolver = Resolver::default();
+
+    #[rustfmt::skip]
+    let pass = [
+        ("pkg/feature?foo", p.join("lib/feature.js"), Some("?foo"), None),
+        ("pkg/feature#bar", p.join("lib/feature.js"), None, Some("#bar")),
+        ("pkg/feature?foo#bar", p.join("lib/feature.js"), Some("?foo"), Some("#bar")),
+        ("pkg/features/a?foo", p.join("lib/features/a.js"), Some("?foo"), None),
+    ];
+
+    for (request, path, query, fragment) in pass {
+        let resolution = resolver.resolve(&f, request).unwrap();
+        assert_eq!(resolution.path(), path, "{request}");
+        assert_eq!(resolution.query(), query, "{request}");
+        assert_eq!(resolution.fragment(), fragment, "{request}");
+    }
+}
+
+#[test]
+fn exports_fields_disabled() {
+    let f = super::fixture_root().join("misc/exports-disabled");
+    let p = f.join("node_modules/blocked");
+
+    let resolution = Resolver::default().resolve(&f, "blocked/lib/internal.js");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::PackagePathNotExported(
+            "./lib/internal.js".into(),
+            p.join("package.json")
+        ))
+    );
+
+    let resolver =
+        Resolver::new(ResolveOptions { exports_fields: vec![], ..ResolveOptions::default() });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("main field", "blocked", p.join("main.js")),
+        ("subpath", "blocked/lib/internal.js", p.join("lib/internal.js")),
+        ("subpath without extension", "blocked/lib/internal", p.join("lib/internal.js")),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{comment} {request}");
+    }
+}
+
+#[test]
+fn string_exports() {
+    let f = super::fixture_root().join("misc/exports-string");
+    let p = f.join("node_modules/string-exports");
+
+    let resolver = Resolver::default();
+
+    let resolved_path = resolver.resolve(&f, "string-exports").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(p.join("index.js")));
+
+    for (request, subpath) in
+        [("string-exports/sub", "./sub"), ("string-exports/index.js", "./index.js")]
+    {
+        let resolution = resolver.resolve(&f, request);
+        let error = ResolveError::PackagePathNotExported(subpath.into(), p.join("package.json"));
+        assert_eq!(resolution, Err(error), "{request}");
+    }
+}
+
+#[test]
+fn hash_key() {
+    let f = super::fixture_root().join("misc/exports-hash-key");
+    let p = f.join("node_modules/hash-key");
+
+    let resolver = Resolver::default();
+
+    for request in ["hash-key", "hash-key/#internal"] {
+        let resolution = resolver.resolve(&f, request);
+        let error = ResolveError::InvalidPackageConfig(p.join("package.json"));
+        assert_eq!(resolution, Err(error), "{request}");
+    }
+}
+
+#[test]
+fn nested_conditions() {
+    let f = super::fixture_root().join("misc/exports-nested-conditions");
+    let p = f.join("node_modules/nested");
+
+    // The first matching condition in object order wins at each level.
+    #[rustfmt::skip]
+    let pass = [
+        (vec!["browser", "import"], p.join("b.mjs")),
+        (vec!["browser"], p.join("b.js")),
+        (vec!["import", "browser"], p.join("b.mjs")),
+        (vec!["browser", "production", "import"], p.join("b.prod.mjs")),
+        (vec!["browser", "production"], p.join("b.prod.js")),
+        (vec!["browser", "node", "import"], p.join("n.mjs")),
+        (vec!["import"], p.join("index.js")),
+    ];
+
+    for (condition_names, expected) in pass {
+        let resolver = Resolver::new(ResolveOptions {
+            condition_names: condition_names.iter().map(ToString::to_string).collect(),
+            ..ResolveOptions::default()
+        });
+        let resolved_path = resolver.resolve(&f, "nested").map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{condition_names:?}");
+    }
+}
+
+#[test]
+fn array_of_strings_and_conditions() {
+    let f = super::fixture_root().join("misc/exports-mixed-array");
+    let p = f.join("node_modules/mixed");
+
+    #[rustfmt::skip]
+    let pass = [
+        (vec!["import"], "mixed/x", p.join("a.mjs")),
+        (vec!["require"], "mixed/x", p.join("a.cjs")),
+        (vec![], "mixed/x", p.join("a.js")),
+        // Invalid targets are skipped
+        (vec!["require"], "mixed/y", p.join("a.cjs")),
+        (vec!["import"], "mixed/z", p.join("a.mjs")),
+    ];
+
+    for (condition_names, request, expected) in pass {
+        let resolver = Resolver::new(ResolveOptions {
+            condition_names: condition_names.iter().map(ToString::to_string).collect(),
+            ..ResolveOptions::default()
+        });
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request} {condition_names:?}");
+    }
+
+    // The error of the last fallback is returned
+    let resolver = Resolver::new(ResolveOptions {
+        condition_names: vec!["require".into()],
+        ..ResolveOptions::default()
+    });
+    let resolution = resolver.resolve(&f, "mixed/z");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::InvalidPackageTarget(
+            "invalid".into(),
+            "./z".into(),
+            p.join("package.json")
+        ))
+    );
+
+    // A null fallback after an invalid target is not an error of the target
+    let resolution = resolver.resolve(&f, "mixed/w");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::PackagePathNotExported("./w".into(), p.join("package.json")))
+    );
+}
+
+#[test]
+fn folder_mapping() {
+    let f = super::fixture_root().join("misc/exports-folder-mapping");
+    let p = f.join("node_modules/legacy");
+
+    let resolver = Resolver::default();
+    let resolved_path = resolver.resolve(&f, "legacy/index.js").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(p.join("dist/index.js")));
+
+    let resolver =
+        Resolver::new(ResolveOptions { folder_mapping: false, ..ResolveOptions::default() });
+    let resolved_path = resolver.resolve(&f, "legacy/index.js").map(|r| r.full_path());
+    assert_eq!(
+        resolved_path,
+        Err(ResolveError::FolderMappingDisabled(
+            "./".into(),
+            "./index.js".into(),
+            p.join("package.json")
+        ))
+    );
+    // Subpath patterns are not affected.
+    let resolved_path = resolver.resolve(&f, "legacy/sub/a").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(p.join("dist/sub/a.js")));
+}
+
+#[test]
+fn no_matching_condition() {
+    let f = super::fixture_root().join("misc/exports-no-condition");
+    let p = f.join("node_modules/browser-only");
+
+    let resolver = Resolver::new(ResolveOptions {
+        condition_names: vec!["node".into()],
+        ..ResolveOptions::default()
+    });
+
+    let error = resolver.resolve(&f, "browser-only").unwrap_err();
+    assert_eq!(
+        error,
+        ResolveError::PackageConditionNotMatched {
+            package: p.join("package.json"),
+            conditions: vec!["browser".into()],
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        format!(
+            r#"No "exports" condition matched for the package root in {}, available conditions: browser"#,
+            p.join("package.json").display()
+        )
+    );
+
+    // Subpaths without a match are still not exported.
+    let resolution = resolver.resolve(&f, "browser-only/missing");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::PackagePathNotExported("./missing".into(), p.join("package.json")))
+    );
+
+    // A matched condition with a null target is not exported.
+    let p = f.join("node_modules/node-null");
+    let resolution = resolver.resolve(&f, "node-null");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::PackagePathNotExported(".".into(), p.join("package.json")))
+    );
+
+    // A package without "exports" is not found.
+    let resolution = resolver.resolve(&f, "no-exports");
+    assert_eq!(resolution, Err(ResolveError::NotFound("no-exports".into())));
+}
+
+#[test]
+fn wildcard_conditions() {
+    let f = super::fixture_root().join("misc/exports-wildcard-conditions");
+    let p = f.join("node_modules/runtime");
+
+    #[rustfmt::skip]
+    let pass = [
+        (vec!["worker*"], p.join("workerd.js")),
+        (vec!["*d"], p.join("workerd.js")),
+        (vec!["no*de"], p.join("node.js")),
+        (vec!["worker"], p.join("index.js")),
+        (vec!["workers*"], p.join("index.js")),
+        // The key order in the exports field decides the priority.
+        (vec!["node", "worker*"], p.join("workerd.js")),
+    ];
+
+    for (condition_names, expected) in pass {
+        let resolver = Resolver::new(ResolveOptions {
+            condition_names: condition_names.iter().map(ToString::to_string).collect(),
+            ..ResolveOptions::default()
+        });
+        let resolved_path = resolver.resolve(&f, "runtime").map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{condition_names:?}");
+    }
+}
+
+#[test]
+fn wildcard_subpaths_only() {
+    let f = super::fixture_root().join("misc/exports-wildcard-only");
+    let p = f.join("node_modules/wildcard");
+
+    let resolver = Resolver::default();
+
+    // The package root is not exported, "main" is ignored.
+    let resolution = resolver.resolve(&f, "wildcard");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::PackagePathNotExported(".".into(), p.join("package.json")))
+    );
+
+    #[rustfmt::skip]
+    let pass = [
+        ("wildcard/foo", p.join("src/foo.js")),
+        ("wildcard/nested/bar", p.join("src/nested/bar.js")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+}
+
+#[test]
+fn mode() {
+    let f = super::fixture_root().join("misc/exports-mode");
+    let p = f.join("node_modules/mode");
+
+    #[rustfmt::skip]
+    let pass = [
+        (None, vec![], "mode", p.join("index.js")),
+        (Some(BuildMode::Development), vec![], "mode", p.join("dev.js")),
+        (Some(BuildMode::Production), vec![], "mode", p.join("prod.js")),
+        // Composes with explicit conditions.
+        (Some(BuildMode::Development), vec!["node"], "mode/feature", p.join("node-dev.js")),
+        (Some(BuildMode::Production), vec!["node"], "mode/feature", p.join("node.js")),
+        (Some(BuildMode::Development), vec![], "mode/feature", p.join("feature.js")),
+    ];
+
+    for (mode, condition_names, request, expected) in pass {
+        let resolver = Resolver::new(ResolveOptions {
+            condition_names: condition_names.iter().map(ToString::to_string).collect(),
+            mode,
+            ..ResolveOptions::default()
+        });
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{mode:?} {condition_names:?} {request}");
+    }
+
+    // Already listed conditions are not duplicated.
+    let resolver = Resolver::new(ResolveOptions {
+        condition_names: vec!["production".into()],
+        mode: Some(BuildMode::Production),
+        ..ResolveOptions::default()
+    });
+    assert_eq!(resolver.options().condition_names, vec!["production".to_string()]);
+}
+
+#[test]
+fn strict_exports_targets() {
+    let f = super::fixture_root().join("misc/exports-extensionless");
+    let p = f.join("node_modules/extensionless");
+
+    // Lenient by default, targets are completed like relative requests.
+    let resolver = Resolver::default();
+
+    #[rustfmt::skip]
+    let pass = [
+        (&f, "extensionless", p.join("dist/index.js")),
+        (&f, "extensionless/feature", p.join("dist/feature.js")),
+        (&f, "extensionless/exact", p.join("dist/exact.js")),
+        (&f, "extensionless/dir", p.join("dist/dir/index.js")),
+        (&p, "#feature", p.join("dist/feature.js")),
+    ];
+
+    for (path, request, expected) in pass {
+        let resolved_path = resolver.resolve(path, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    let resolver =
+        Resolver::new(ResolveOptions { strict_exports_targets: true, ..ResolveOptions::default() });
+
+    let resolved_path = resolver.resolve(&f, "extensionless/exact").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(p.join("dist/exact.js")));
+
+    #[rustfmt::skip]
+    let fail = [
+        (&f, "extensionless"),
+        (&f, "extensionless/feature"),
+        (&f, "extensionless/dir"),
+        (&p, "#feature"),
+    ];
+
+    for (path, request) in fail {
+        let resolution = resolver.resolve(path, request);
+        assert_eq!(resolution, Err(ResolveError::NotFound(request.into())), "{request}");
+    }
+}
+
+#[test]
+fn multi_segment_pattern_trailer() {
+    let f = super::fixture_root().join("misc/exports-pattern-trailer");
+    let p = f.join("node_modules/trailer");
+
+    let resolver = Resolver::default();
+
+    #[rustfmt::skip]
+    let pass = [
+        ("trailer/features/foo/index.js", p.join("src/features/foo/index.js")),
+        // The capture may span segments.
+        ("trailer/features/bar/baz/index.js", p.join("src/features/bar/baz/index.js")),
+        ("trailer/types/qux/index.d.ts", p.join("src/features/qux/index.d.ts")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    #[rustfmt::skip]
+    let fail = [
+        // Only part of the trailer matches.
+        ("trailer/features/foo/main.js", "./features/foo/main.js"),
+        ("trailer/features/foo", "./features/foo"),
+        // The trailer must not overlap the key prefix.
+        ("trailer/features/index.js", "./features/index.js"),
+    ];
+
+    for (request, subpath) in fail {
+        let resolution = resolver.resolve(&f, request);
+        assert_eq!(
+            resolution,
+            Err(ResolveError::PackagePathNotExported(subpath.into(), p.join("package.json"))),
+            "{request}"
+        );
+    }
+}
+
+#[test]
+fn nested_custom_exports_field() {
+    let f = super::fixture_root().join("misc/exports-nested-field");
+    let p = f.join("node_modules/framework-plugin");
+
+    let resolver = Resolver::new(ResolveOptions {
+        exports_fields: vec![vec!["myframework".into(), "exports".into()]],
+        condition_names: vec!["myframework".into()],
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("framework-plugin", p.join("framework/index.js")),
+        ("framework-plugin/feature", p.join("framework/feature.js")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    // Subpaths not listed by the nested field are not exported.
+    let resolution = resolver.resolve(&f, "framework-plugin/lib/index.js");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::PackagePathNotExported("./lib/index.js".into(), p.join("package.json")))
+    );
+
+    // The top-level "exports" is used by default.
+    let resolved_path = Resolver::default().resolve(&f, "framework-plugin").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(p.join("lib/index.js")));
+}
+
+#[test]
+fn case_sensitive_exports_keys() {
+    let f = super::fixture_root().join("misc/exports-case");
+    let p = f.join("node_modules/case");
+
+    let resolver = Resolver::default();
+
+    #[rustfmt::skip]
+    let pass = [
+        (&f, "case/feature", p.join("feature.js")),
+        (&f, "case/lib/util", p.join("lib/util.js")),
+        (&p, "#feature", p.join("feature.js")),
+    ];
+
+    for (path, request, expected) in pass {
+        let resolved_path = resolver.resolve(path, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    // Keys are matched byte for byte, also on case-insensitive file systems
+    // where `feature.js` could be opened as `Feature.js`.
+    let package_json = p.join("package.json");
+
+    #[rustfmt::skip]
+    let fail = [
+        (&f, "case/Feature", ResolveError::PackagePathNotExported("./Feature".into(), package_json.clone())),
+        (&f, "case/FEATURE", ResolveError::PackagePathNotExported("./FEATURE".into(), package_json.clone())),
+        (&f, "case/Lib/util", ResolveError::PackagePathNotExported("./Lib/util".into(), package_json.clone())),
+        (&p, "#Feature", ResolveError::PackageImportNotDefined("#Feature".into(), package_json)),
+    ];
+
+    for (path, request, error) in fail {
+        let resolution = resolver.resolve(path, request);
+        assert_eq!(resolution, Err(error), "{request}");
+    }
+}
+
+#[test]
+fn matched_export_subpath() {
+    let f = super::fixture_root().join("misc/exports-subpath");
+    let p = f.join("node_modules/subpath");
+
+    let resolver = Resolver::default();
+
+    #[rustfmt::skip]
+    let data = [
+        ("subpath", p.join("index.js"), Some(".")),
+        ("subpath/feature", p.join("feature.js"), Some("./feature")),
+        ("subpath/conditional", p.join("index.js"), Some("./conditional")),
+        // Patterns report the expanded subpath instead of the key.
+        ("subpath/features/foo", p.join("src/features/foo.js"), Some("./features/foo")),
+        ("subpath/features/bar", p.join("src/features/bar.js"), Some("./features/bar")),
+        ("subpath/utils/format.js", p.join("src/utils/format.js"), Some("./utils/format.js")),
+        ("sugar", f.join("node_modules/sugar/main.js"), Some(".")),
+        ("./node_modules/subpath/feature.js", p.join("feature.js"), None),
+    ];
+
+    for (request, expected_path, expected_subpath) in data {
+        let resolution = resolver.resolve(&f, request).unwrap();
+        assert_eq!(resolution.path(), expected_path, "{request}");
+        assert_eq!(resolution.matched_export_subpath(), expected_subpath, "{request}");
+    }
+}
+
+#[test]
+fn exports_fallback_to_main() {
+    let f = super::fixture_root().join("misc/exports-fallback-to-main");
+    let p = f.join("node_modules/legacy");
+
+    let resolver = Resolver::new(ResolveOptions {
+        condition_names: vec!["require".into()],
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let fail = [
+        ("legacy", "."),
+        ("legacy/lib/internal.js", "./lib/internal.js"),
+    ];
+
+    for (request, subpath) in fail {
+        let resolution = resolver.resolve(&f, request);
+        let error = ResolveError::PackagePathNotExported(subpath.into(), p.join("package.json"));
+        assert_eq!(resolution, Err(error), "{request}");
+    }
+
+    // "exports" matched a missing file.
+    let resolution = resolver.resolve(&f, "broken");
+    assert_eq!(resolution, Err(ResolveError::NotFound("broken".into())));
+
+    // The package root only has an "import" condition.
+    let resolution = resolver.resolve(&f, "esm-only");
+    let error = ResolveError::PackageConditionNotMatched {
+        package: f.join("node_modules/esm-only/package.json"),
+        conditions: vec!["import".into()],
+    };
+    assert_eq!(resolution, Err(error));
+
+    let resolver = Resolver::new(ResolveOptions {
+        condition_names: vec!["require".into()],
+        exports_fallback_to_main: true,
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("legacy", p.join("lib/main.js")),
+        ("legacy/feature", p.join("lib/feature.js")),
+        ("legacy/lib/internal.js", p.join("lib/internal.js")),
+        ("legacy/lib/internal", p.join("lib/internal.js")),
+        ("broken", f.join("node_modules/broken/lib/main.js")),
+        ("esm-only", f.join("node_modules/esm-only/lib/main.js")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    let resolution = resolver.resolve(&f, "legacy/missing");
+    assert_eq!(resolution, Err(ResolveError::NotFound("legacy/missing".into())));
+}
+
+#[test]
+fn exports_only() {
+    let f = super::fixture_root().join("misc/exports-only");
+    let node_modules = f.join("node_modules");
+
+    let resolver = Resolver::new(ResolveOptions {
+        exports_only: true,
+        exports_fallback_to_main: true,
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("strict", node_modules.join("strict/index.js")),
+        // Packages without a package.json are loaded as before.
+        ("bare", node_modules.join("bare/index.js")),
+        ("bare/util", node_modules.join("bare/util.js")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    #[rustfmt::skip]
+    let fail = [
+        ("strict/other.js", "strict", "./other.js"),
+        ("strict/main.js", "strict", "./main.js"),
+        ("legacy", "legacy", "."),
+        ("legacy/lib/main.js", "legacy", "./lib/main.js"),
+    ];
+
+    for (request, package, subpath) in fail {
+        let resolution = resolver.resolve(&f, request);
+        let error = ResolveError::PackagePathNotExported(
+            subpath.into(),
+            node_modules.join(package).join("package.json"),
+        );
+        assert_eq!(resolution, Err(error), "{request}");
+    }
+}
+
 // Small script for generating the test cases from enhanced-resolve
 // for (c of testCases) {
 //  console.log("TestCase {")
@@ -2537,7 +3178,11 @@ fn test_cases() {
         if let Some(expect) = case.expect {
             if expect.is_empty() {
                 assert!(
-                    matches!(resolved_path, Err(ResolveError::PackagePathNotExported(_, _))),
+                    matches!(
+                        resolved_path,
+                        Err(ResolveError::PackagePathNotExported(_, _)
+                            | ResolveError::PackageConditionNotMatched { .. })
+                    ),
                     "{} {:?}",
                     &case.name,
                     &resolved_path
@@ -59,3 +59,46 @@ fn not_apply_to_extension_nor_main_files() {
         assert_eq!(resolved_path, Ok(expected), "{comment} {path:?} {request}");
     }
 }
+
+#[test]
+fn cjs() {
+    let f = super::fixture_root().join("misc/extension-alias-cjs");
+
+    let resolver = Resolver::new(ResolveOptions {
+        extension_alias: vec![
+            (".js".into(), vec![".ts".into(), ".js".into()]),
+            (".mjs".into(), vec![".mts".into()]),
+            (".cjs".into(), vec![".cts".into()]),
+        ],
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("./a.cjs", f.join("a.cts")),
+        // Only the last extension is replaced, `types.d.ts` is not taken for `types.ts`.
+        ("./types.js", f.join("types.ts")),
+        ("./types.d.ts", f.join("types.d.ts")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    // The declaration file `c.d.cts` is not a candidate for `c.cjs`.
+    let resolution = resolver.resolve(&f, "./c.cjs");
+    assert_eq!(resolution, Err(ResolveError::NotFound("./c.cjs".into())));
+
+    // The error lists the aliased candidates when the original file exists.
+    #[rustfmt::skip]
+    let fail = [
+        ("./b.cjs", ResolveError::ExtensionAlias("b.cjs".into(), "b.cts".into(), f.clone())),
+        ("./e.cjs", ResolveError::ExtensionAlias("e.cjs".into(), "e.cts".into(), f.clone())),
+    ];
+
+    for (request, expected) in fail {
+        let resolution = resolver.resolve(&f, request);
+        assert_eq!(resolution, Err(expected), "{request}");
+    }
+}
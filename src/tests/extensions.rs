@@ -124,3 +124,161 @@ fn without_leading_dot() {
         ..ResolveOptions::default()
     });
 }
+
+// Not part of enhanced-resolve
+#[test]
+fn prefer_extensions() {
+    let f = super::fixture_root().join("misc/prefer-extensions");
+
+    let resolver = Resolver::default();
+    let resolved_path = resolver.resolve(&f, "./foo").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("foo")));
+
+    let resolver =
+        Resolver::new(ResolveOptions { prefer_extensions: true, ..ResolveOptions::default() });
+    let resolved_path = resolver.resolve(&f, "./foo").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("foo.js")));
+}
+
+#[test]
+fn resolve_with_extensions() {
+    let f = super::fixture_root().join("misc/extensions-override");
+
+    let resolver = Resolver::default();
+
+    #[rustfmt::skip]
+    let pass = [
+        (vec![".css"], "./styles", f.join("styles.css")),
+        (vec![".js"], "./styles", f.join("styles.js")),
+        (vec![".css"], "./theme", f.join("theme/index.css")),
+        (vec![".js"], "./theme", f.join("theme/index.js")),
+    ];
+
+    for (extensions, request, expected) in pass {
+        let resolved_path =
+            resolver.resolve_with_extensions(&f, request, &extensions).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{extensions:?} {request}");
+    }
+
+    // The override does not leak into later calls.
+    let resolved_path = resolver.resolve(&f, "./styles").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("styles.js")));
+}
+
+#[test]
+fn type_aware_index() {
+    let f = super::fixture_root().join("misc/type-aware-index");
+
+    let resolver =
+        Resolver::new(ResolveOptions { type_aware_index: true, ..ResolveOptions::default() });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("module", "./dir", f.join("module/dir/index.mjs")),
+        ("module", "./only-js", f.join("module/only-js/index.js")),
+        ("commonjs", "./dir", f.join("commonjs/dir/index.cjs")),
+    ];
+
+    for (dir, request, expected) in pass {
+        let resolved_path = resolver.resolve(f.join(dir), request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{dir} {request}");
+    }
+
+    // Disabled by default
+    let resolved_path =
+        Resolver::default().resolve(f.join("module"), "./dir").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("module/dir/index.js")));
+}
+
+#[test]
+fn extensions_read_dir_threshold() {
+    let f = super::fixture().join("extensions");
+
+    let resolver = Resolver::new(ResolveOptions {
+        extensions: vec![".mjs".into(), ".ts".into(), ".js".into()],
+        extensions_read_dir_threshold: Some(2),
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("./foo", "foo.ts"),
+        ("./app.module", "app.module.js"),
+        ("./dir", "dir/index.ts"),
+        ("module/", "node_modules/module/index.ts"),
+    ];
+
+    for (request, expected_path) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(f.join(expected_path)), "{request}");
+    }
+
+    // Skipped candidates are still reported as missing dependencies.
+    let mut ctx = ResolveContext::default();
+    let resolved_path = resolver.resolve_with_context(&f, "./foo", &mut ctx).map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("foo.ts")));
+    assert!(ctx.missing_dependencies.contains(&f.join("foo.mjs")));
+
+    let resolution = resolver.resolve(&f, "./missing");
+    assert_eq!(resolution, Err(ResolveError::NotFound("./missing".into())));
+}
+
+#[test]
+fn fallback_extensions() {
+    let f = super::fixture_root().join("misc/fallback-extensions");
+
+    let resolver = Resolver::new(ResolveOptions {
+        fallback_extensions: vec![".vue".into()],
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("./Component", f.join("Component.vue")),
+        ("./dir", f.join("dir/index.vue")),
+        ("vue-pkg", f.join("node_modules/vue-pkg/main.vue")),
+        // The primary extensions are tried first.
+        ("./both", f.join("both.js")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    let resolution = resolver.resolve(&f, "./missing");
+    assert_eq!(resolution, Err(ResolveError::NotFound("./missing".into())));
+
+    // Disabled by default
+    let resolution = Resolver::default().resolve(&f, "./Component");
+    assert_eq!(resolution, Err(ResolveError::NotFound("./Component".into())));
+}
+
+#[test]
+fn directory_index_only() {
+    let f = super::fixture_root().join("misc/directory-index");
+
+    let resolver = Resolver::default();
+    let resolved_path = resolver.resolve(&f, "./dir").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("dir.js")));
+
+    let resolver = Resolver::new(ResolveOptions::default().with_directory_index_only(true));
+
+    #[rustfmt::skip]
+    let pass = [
+        ("./dir", f.join("dir/index.js")),
+        ("./dir.js", f.join("dir.js")),
+        ("./dir/", f.join("dir/index.js")),
+        // Specifiers with an extension are loaded as a file first.
+        ("./config.d", f.join("config.d.js")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    // No fallback to the file when the directory does not have an index.
+    let resolution = resolver.resolve(&f, "./empty");
+    assert_eq!(resolution, Err(ResolveError::NotFound("./empty".into())));
+}
@@ -0,0 +1,38 @@
+//! Not part of enhanced_resolve's test suite
+
+use std::sync::Arc;
+
+use crate::{
+    FileSystemOs, FsCache, FsCall, RecordingFileSystem, ReplayFileSystem, ResolveOptions,
+    ResolverGeneric,
+};
+
+#[test]
+fn record_and_replay() {
+    let f = super::fixture();
+
+    #[allow(clippy::default_constructed_unit_structs)] // only a unit struct without yarn_pnp
+    let file_system = RecordingFileSystem::new(FileSystemOs::default());
+    let calls = file_system.calls();
+    let resolver = ResolverGeneric::new_with_cache(
+        Arc::new(FsCache::new(file_system)),
+        ResolveOptions::default(),
+    );
+    let expected = resolver.resolve(&f, "m1/a").map(|r| r.full_path());
+    assert_eq!(expected, Ok(f.join("node_modules/m1/a.js")));
+
+    let calls = calls.lock().unwrap().clone();
+    assert!(calls.iter().any(|call| matches!(call, FsCall::ReadToString { .. })));
+    assert!(calls.iter().any(|call| matches!(call, FsCall::Metadata { metadata: None, .. })));
+
+    // The log is serializable for attaching to bug reports.
+    let json = serde_json::to_string(&calls).unwrap();
+    let calls: Vec<FsCall> = serde_json::from_str(&json).unwrap();
+
+    let resolver = ResolverGeneric::new_with_cache(
+        Arc::new(FsCache::new(ReplayFileSystem::new(calls))),
+        ResolveOptions::default(),
+    );
+    let resolved_path = resolver.resolve(&f, "m1/a").map(|r| r.full_path());
+    assert_eq!(resolved_path, expected);
+}
@@ -0,0 +1,48 @@
+//! Tests for [crate::ResolveOptions::honor_files_field]
+
+use crate::{ResolveError, ResolveOptions, Resolver};
+
+#[test]
+fn test() {
+    let f = super::fixture_root().join("misc/files-field");
+    let p = f.join("node_modules/pkg");
+
+    let resolver =
+        Resolver::new(ResolveOptions { honor_files_field: true, ..ResolveOptions::default() });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("main is always published", "pkg", p.join("index.js")),
+        ("package.json is always published", "pkg/package.json", p.join("package.json")),
+        ("readme is always published", "pkg/README.md", p.join("README.md")),
+        ("directory", "pkg/dist/feature", p.join("dist/feature.js")),
+        ("glob", "pkg/lib/a", p.join("lib/a.js")),
+        ("without files field", "no-files", f.join("node_modules/no-files/index.js")),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{comment} {request}");
+    }
+
+    #[rustfmt::skip]
+    let fail = [
+        ("not listed", "pkg/src/feature", p.join("src/feature.js")),
+        ("negated", "pkg/lib/internal", p.join("lib/internal.js")),
+        ("glob does not match nested directories", "pkg/lib/nested/b", p.join("lib/nested/b.js")),
+    ];
+
+    for (comment, request, file) in fail {
+        let resolution = resolver.resolve(&f, request);
+        let error = ResolveError::NotPublished { file, package: p.join("package.json") };
+        assert_eq!(resolution, Err(error), "{comment} {request}");
+    }
+
+    // The root project is not installed, its own files are not checked.
+    let resolved_path = resolver.resolve(&f, "./src/x.js").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("src/x.js")));
+
+    // Disabled by default
+    let resolved_path = Resolver::default().resolve(&f, "pkg/src/feature").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(p.join("src/feature.js")));
+}
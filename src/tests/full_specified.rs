@@ -74,6 +74,42 @@ mod windows {
         }
     }
 
+    #[test]
+    fn strict_esm() {
+        let file_system = file_system();
+
+        let resolver = ResolverGeneric::new_with_cache(
+            Arc::new(FsCache::new(file_system)),
+            ResolveOptions { strict_esm: true, ..ResolveOptions::default() },
+        );
+
+        let failing_resolves = [
+            ("no extensions", "./abc"),
+            ("no extensions (absolute)", "/a/abc"),
+            ("no directories", "."),
+            ("no directories 2", "./dir"),
+        ];
+
+        for (comment, request) in failing_resolves {
+            let resolution = resolver.resolve("/a", request);
+            assert!(resolution.is_err(), "{comment} {request}");
+        }
+
+        let successful_resolves = [
+            ("fully relative", "./abc.js", "/a/abc.js"),
+            ("fully absolute", "/a/abc.js", "/a/abc.js"),
+            ("no extensions in packages", "package1/file", "/a/node_modules/package1/file.js"),
+            ("extensions in mainFiles", "package1", "/a/node_modules/package1/index.js"),
+            ("extensions in mainFields", "package2", "/a/node_modules/package2/a.js"),
+            ("directories in packages", "package3/dir", "/a/node_modules/package3/dir/index.js"),
+        ];
+
+        for (comment, request, expected) in successful_resolves {
+            let resolution = resolver.resolve("/a", request).map(|r| r.full_path());
+            assert_eq!(resolution, Ok(PathBuf::from(expected)), "{comment} {request}");
+        }
+    }
+
     #[test]
     #[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
     fn resolve_to_context() {
@@ -110,3 +146,31 @@ mod windows {
         }
     }
 }
+
+#[test]
+fn unknown_extension() {
+    use crate::{ResolveError, ResolveOptions, Resolver};
+
+    let f = super::fixture_root().join("misc/fully-specified-css");
+
+    // `.css` is not in [ResolveOptions::extensions], the file is only tried as is.
+    let resolver =
+        Resolver::new(ResolveOptions { fully_specified: true, ..ResolveOptions::default() });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("./style.css", f.join("style.css")),
+        ("./style.module.css", f.join("style.module.css")),
+        ("./style.css?inline", f.join("style.css?inline")),
+        ("theme", f.join("node_modules/theme/dist/theme.css")),
+        ("theme/theme.css", f.join("node_modules/theme/dist/theme.css")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    let resolution = resolver.resolve(&f, "./style");
+    assert_eq!(resolution, Err(ResolveError::NotFound("./style".into())));
+}
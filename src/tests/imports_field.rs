@@ -80,6 +80,91 @@ fn shared_resolvers() {
     assert_eq!(resolved_path, Ok(f.join("a.js")));
 }
 
+#[test]
+fn external_package() {
+    let f = super::fixture_root().join("misc/imports-external");
+    let dep = f.join("node_modules/dep");
+
+    let resolver = Resolver::default();
+
+    // The external package is resolved from the package scope, not the importing directory,
+    // so `src/node_modules/dep` is not picked up.
+    #[rustfmt::skip]
+    let pass = [
+        (f.clone(), "#dep", dep.join("index.js")),
+        (f.join("src"), "#dep", dep.join("index.js")),
+        (f.join("src/nested"), "#dep", dep.join("index.js")),
+        (f.join("src/nested"), "#dep/util", dep.join("lib/util.js")),
+    ];
+
+    for (path, request, expected) in pass {
+        let resolved_path = resolver.resolve(&path, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{path:?} {request}");
+    }
+}
+
+#[test]
+fn bare_target_from_ancestors() {
+    let f = super::fixture_root().join("misc/imports-field-bare-target");
+    let app = f.join("packages/app");
+    let test_dir = app.join("src/deep/test");
+
+    let resolver = Resolver::default();
+
+    // Bare targets are resolved from the node_modules of the package and its ancestors.
+    #[rustfmt::skip]
+    let pass = [
+        ("#dep", f.join("node_modules/some-pkg/index.js")),
+        ("#dep/util.js", f.join("node_modules/some-pkg/lib/util.js")),
+        ("#exported/a", f.join("node_modules/exported/lib/a.js")),
+        ("#internal/util.js", app.join("src/internal/util.js")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&test_dir, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+}
+
+#[test]
+fn exact_key_with_conditions() {
+    let f = super::fixture_root().join("misc/imports-conditions");
+
+    #[rustfmt::skip]
+    let pass = [
+        (vec!["node"], f.join("env.node.js")),
+        (vec!["browser"], f.join("env.browser.js")),
+        (vec!["browser", "node"], f.join("env.node.js")),
+        (vec![], f.join("env.js")),
+    ];
+
+    for (condition_names, expected) in pass {
+        let resolver = Resolver::new(ResolveOptions {
+            condition_names: condition_names.iter().map(ToString::to_string).collect(),
+            ..ResolveOptions::default()
+        });
+        let resolved_path = resolver.resolve(&f, "#env").map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{condition_names:?}");
+    }
+}
+
+#[test]
+fn bare_hash_key() {
+    let f = super::fixture_root().join("misc/imports-bare-hash");
+    let error = ResolveError::InvalidPackageConfig(f.join("package.json"));
+
+    // The key is rejected when the package.json is read, also for requests not using "imports".
+    let resolver = Resolver::default();
+    assert_eq!(resolver.find_package_json_with_field(&f, "name").unwrap_err(), error);
+    assert_eq!(resolver.resolve(&f, "#a"), Err(error.clone()));
+    assert_eq!(resolver.resolve(&f, "./a.js"), Err(error.clone()));
+
+    // The raw "imports" key is checked, independent of the configured fields.
+    let resolver =
+        Resolver::new(ResolveOptions { imports_fields: vec![], ..ResolveOptions::default() });
+    assert_eq!(resolver.resolve(&f, "./a.js"), Err(error));
+}
+
 // Small script for generating the test cases from enhanced_resolve
 // for (c of testCases) {
 //  console.log("TestCase {")
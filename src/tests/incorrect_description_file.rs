@@ -2,7 +2,10 @@
 
 use rustc_hash::FxHashSet;
 
-use crate::{JSONError, Resolution, ResolveContext, ResolveError, ResolveOptions, Resolver};
+use crate::{
+    JSONError, Resolution, ResolveContext, ResolveDiagnostic, ResolveError, ResolveOptions,
+    Resolver,
+};
 
 // should not resolve main in incorrect description file #1
 #[test]
@@ -63,3 +66,27 @@ fn no_description_file() {
         Resolver::new(ResolveOptions { description_files: vec![], ..ResolveOptions::default() });
     assert_eq!(resolver.resolve(&f, "."), Err(ResolveError::NotFound(".".into())));
 }
+
+#[test]
+fn skip_invalid_package_json() {
+    let f = super::fixture_root().join("misc/skip-invalid-package-json");
+    let error = JSONError {
+        path: f.join("package.json"),
+        message: String::from("EOF while parsing a value at line 3 column 0"),
+        line: 3,
+        column: 0,
+    };
+
+    let resolution = Resolver::default().resolve(f.join("src"), "./foo.js");
+    assert_eq!(resolution, Err(ResolveError::JSON(error.clone())));
+
+    let resolver = Resolver::new(ResolveOptions {
+        skip_invalid_package_json: true,
+        ..ResolveOptions::default()
+    });
+    let mut ctx = ResolveContext::default();
+    let resolved_path =
+        resolver.resolve_with_context(f.join("src"), "./foo.js", &mut ctx).map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("src/foo.js")));
+    assert_eq!(ctx.diagnostics, vec![ResolveDiagnostic::InvalidPackageJsonSkipped(error)]);
+}
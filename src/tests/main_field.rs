@@ -1,6 +1,6 @@
 //! Not part of enhanced_resolve's test suite
 
-use crate::{ResolveOptions, Resolver};
+use crate::{EntrySelector, PackageType, ResolveOptions, Resolver};
 
 #[test]
 fn test() {
@@ -22,3 +22,95 @@ fn test() {
     let resolution = resolver2.resolve(&f, "pck2").map(|r| r.full_path());
     assert_eq!(resolution, Ok(f.join("node_modules/pck2/module.js")));
 }
+
+#[test]
+fn prefer_main_over_exports() {
+    let f = super::fixture_root().join("misc/prefer-main-over-exports");
+
+    let resolver = Resolver::default();
+    let resolution = resolver.resolve(&f, "both").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(f.join("node_modules/both/exports.js")));
+
+    let resolver = Resolver::new(ResolveOptions {
+        prefer_main_over_exports: true,
+        ..ResolveOptions::default()
+    });
+    let resolution = resolver.resolve(&f, "both").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(f.join("node_modules/both/main.js")));
+    // Subpaths are still resolved with "exports"
+    let resolution = resolver.resolve(&f, "both/sub").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(f.join("node_modules/both/sub.js")));
+}
+
+#[test]
+fn prefer_esm() {
+    let f = super::fixture_root().join("misc/prefer-esm");
+    let p = f.join("node_modules/mixed");
+
+    let resolver = Resolver::default();
+    let resolution = resolver.resolve(&f, "mixed").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(p.join("cjs.js")));
+
+    #[rustfmt::skip]
+    let pass = [
+        (vec!["main".into()], p.join("esm.js")),
+        (vec!["browser".into(), "main".into()], p.join("esm.js")),
+        // An explicit "module" keeps its position.
+        (vec!["browser".into(), "module".into()], p.join("browser.js")),
+    ];
+
+    for (main_fields, expected) in pass {
+        let resolver = Resolver::new(ResolveOptions {
+            main_fields: main_fields.clone(),
+            prefer_esm: true,
+            ..ResolveOptions::default()
+        });
+        let resolution = resolver.resolve(&f, "mixed").map(|r| r.full_path());
+        assert_eq!(resolution, Ok(expected), "{main_fields:?}");
+    }
+}
+
+#[test]
+fn entry_selector() {
+    let f = super::fixture_root().join("misc/entry-selector");
+
+    let main_fields = vec!["module".into(), "main".into()];
+
+    // Main fields are tried in order by default.
+    let resolver = Resolver::new(ResolveOptions {
+        main_fields: main_fields.clone(),
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("esm", f.join("node_modules/esm/esm.js")),
+        ("cjs", f.join("node_modules/cjs/esm.js")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    // "module" only for ES module packages.
+    let resolver = Resolver::new(ResolveOptions {
+        main_fields,
+        entry_selector: Some(EntrySelector::new(|entry, _ctx| {
+            let field = if entry.r#type == Some(PackageType::Module) { "module" } else { "main" };
+            entry.main_field(field).map(ToString::to_string)
+        })),
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("esm", f.join("node_modules/esm/esm.js")),
+        ("cjs", f.join("node_modules/cjs/cjs.js")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+}
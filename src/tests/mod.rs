@@ -1,19 +1,29 @@
+mod abort_signal;
 mod alias;
 mod browser_field;
 mod builtins;
 mod dependencies;
+mod dependency_edges;
+mod description_files;
+mod diagnostics;
 mod exports_field;
 mod extension_alias;
 mod extensions;
 mod fallback;
+mod file_system_recorder;
+mod files_field;
 mod full_specified;
 mod imports_field;
 mod incorrect_description_file;
 mod main_field;
 mod memory_fs;
 mod missing;
+mod overrides;
+mod package_entries;
+mod persistent_cache;
 #[cfg(feature = "yarn_pnp")]
 mod pnp;
+mod post_resolve;
 mod resolution;
 mod resolve;
 mod restrictions;
@@ -23,6 +33,8 @@ mod simple;
 mod symlink;
 mod tsconfig_paths;
 mod tsconfig_project_references;
+mod typescript;
+mod workspaces;
 
 use std::{env, path::PathBuf, sync::Arc, thread};
 
@@ -0,0 +1,54 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{ResolveError, ResolveOptions, Resolver};
+
+#[test]
+fn overrides() {
+    let f = super::fixture_root().join("misc/overrides");
+    let pkg_a = f.join("node_modules/pkg-a");
+    let forced = f.join("forced/dep");
+
+    let resolver = Resolver::default();
+    let resolved_path = resolver.resolve(&pkg_a, "dep").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(pkg_a.join("node_modules/dep/index.js")));
+
+    // Only imports from pkg-a are redirected.
+    let resolver = Resolver::new(ResolveOptions::default().with_override("pkg-a>dep", &forced));
+
+    #[rustfmt::skip]
+    let pass = [
+        (pkg_a.clone(), "dep", forced.join("main.js")),
+        (pkg_a.clone(), "dep/util", forced.join("util.js")),
+        (f.clone(), "dep", f.join("node_modules/dep/index.js")),
+    ];
+
+    for (directory, request, expected) in pass {
+        let resolved_path = resolver.resolve(&directory, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{directory:?} {request}");
+    }
+
+    // Every import is redirected, a parent pattern takes precedence.
+    let resolver = Resolver::new(
+        ResolveOptions::default()
+            .with_override("dep", f.join("node_modules/pkg-a/node_modules/dep"))
+            .with_override("app>dep", &forced),
+    );
+
+    #[rustfmt::skip]
+    let pass = [
+        (pkg_a.clone(), "dep", pkg_a.join("node_modules/dep/index.js")),
+        (f.clone(), "dep", forced.join("main.js")),
+    ];
+
+    for (directory, request, expected) in pass {
+        let resolved_path = resolver.resolve(&directory, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{directory:?} {request}");
+    }
+
+    // Overridden packages are not searched in node_modules.
+    let resolution = resolver.resolve(&f, "dep/index.js");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::PackagePathNotExported("./index.js".into(), forced.join("package.json")))
+    );
+}
@@ -0,0 +1,64 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{ResolveError, Resolver};
+
+#[test]
+fn test() {
+    let f = super::fixture_root().join("misc/package-entries");
+    let resolver = Resolver::default();
+
+    let mixed = f.join("node_modules/mixed");
+    let entries = resolver.resolve_package_entries(&f, "mixed");
+    assert_eq!(
+        entries,
+        Ok(vec![
+            (".".into(), mixed.join("src/index.js")),
+            ("./feature".into(), mixed.join("src/feature.js")),
+            ("./features/a.js".into(), mixed.join("src/features/a.js")),
+            ("./features/b.js".into(), mixed.join("src/features/b.js")),
+            ("./features/nested/c.js".into(), mixed.join("src/features/nested/c.js")),
+            ("./package.json".into(), mixed.join("package.json")),
+        ])
+    );
+
+    let entries = resolver.resolve_package_entries(&f, "main-only");
+    assert_eq!(entries, Ok(vec![(".".into(), f.join("node_modules/main-only/lib/main.js"))]));
+
+    let entries = resolver.resolve_package_entries(&f, "missing");
+    assert_eq!(entries, Err(ResolveError::NotFound("missing".into())));
+}
+
+#[test]
+fn package_exports() {
+    let f = super::fixture_root().join("misc/package-entries");
+    let node_modules = f.join("node_modules");
+    let resolver = Resolver::default();
+
+    let subpaths = resolver.package_exports(node_modules.join("mixed"));
+    assert_eq!(
+        subpaths,
+        Ok(vec![
+            ".".into(),
+            "./feature".into(),
+            "./features/*.js".into(),
+            "./package.json".into(),
+            "./private/*".into(),
+        ])
+    );
+
+    assert_eq!(resolver.package_exports(node_modules.join("sugar")), Ok(vec![".".into()]));
+    assert_eq!(resolver.package_exports(node_modules.join("conditions")), Ok(vec![".".into()]));
+    assert_eq!(resolver.package_exports(node_modules.join("main-only")), Ok(vec![]));
+
+    let invalid = node_modules.join("invalid");
+    assert_eq!(
+        resolver.package_exports(&invalid),
+        Err(ResolveError::InvalidPackageConfig(invalid.join("package.json")))
+    );
+
+    let missing = node_modules.join("missing");
+    assert_eq!(
+        resolver.package_exports(&missing),
+        Err(ResolveError::NotFound(missing.to_string_lossy().to_string()))
+    );
+}
@@ -0,0 +1,171 @@
+use std::fs;
+
+use crate::{
+    PostResolve, PostResolveAction, ResolveContext, ResolveError, ResolveOptions, Resolver,
+    Restriction,
+};
+
+#[test]
+fn persistent_cache() {
+    let dirname = super::fixture_root().join("enhanced_resolve").join("test");
+    let temp_path = dirname.join("temp-persistent-cache");
+    if temp_path.exists() {
+        _ = fs::remove_dir_all(&temp_path);
+    }
+    let project = temp_path.join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(project.join("index.js"), "").unwrap();
+    let cache_file = temp_path.join("resolutions.json");
+
+    // Write
+    let resolver = Resolver::with_persistent_cache(&cache_file, ResolveOptions::default());
+    let resolved_path = resolver.resolve(&project, "./index?query").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(project.join("index.js?query")));
+    resolver.save_persistent_cache().unwrap();
+    assert!(cache_file.is_file());
+
+    // Reload and hit, the recorded dependencies are reported.
+    let resolver = Resolver::with_persistent_cache(&cache_file, ResolveOptions::default());
+    let mut ctx = ResolveContext::default();
+    let resolution = resolver.resolve_with_context(&project, "./index?query", &mut ctx).unwrap();
+    assert_eq!(resolution.full_path(), project.join("index.js?query"));
+    assert!(ctx.file_dependencies.contains(&project.join("index.js")));
+    assert!(ctx.missing_dependencies.contains(&project.join("index")));
+
+    // The resolution is read from the file, point it to another file to observe the hit.
+    fs::write(project.join("other.js"), "").unwrap();
+    let json = fs::read_to_string(&cache_file).unwrap();
+    let json = json.replacen(
+        &serde_json::to_string(&project.join("index.js")).unwrap(),
+        &serde_json::to_string(&project.join("other.js")).unwrap(),
+        1,
+    );
+    fs::write(&cache_file, json).unwrap();
+    let resolver = Resolver::with_persistent_cache(&cache_file, ResolveOptions::default());
+    let resolved_path = resolver.resolve(&project, "./index?query").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(project.join("other.js?query")));
+
+    // A cache file written with other options is discarded.
+    let options = ResolveOptions { extensions: vec![".js".into()], ..ResolveOptions::default() };
+    let other = Resolver::with_persistent_cache(&cache_file, options);
+    let resolved_path = other.resolve(&project, "./index?query").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(project.join("index.js?query")));
+
+    // A missing dependency that now exists invalidates the entry.
+    fs::write(project.join("index"), "").unwrap();
+    let resolved_path = resolver.resolve(&project, "./index?query").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(project.join("index?query")));
+
+    // A deleted file invalidates the entry, the in-memory cache is cleared as usual.
+    fs::remove_file(project.join("index")).unwrap();
+    resolver.clear_cache();
+    let resolved_path = resolver.resolve(&project, "./index?query").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(project.join("index.js?query")));
+
+    _ = fs::remove_dir_all(&temp_path);
+}
+
+#[test]
+fn persistent_cache_per_call_state() {
+    let dirname = super::fixture_root().join("enhanced_resolve").join("test");
+    let temp_path = dirname.join("temp-persistent-cache-per-call");
+    if temp_path.exists() {
+        _ = fs::remove_dir_all(&temp_path);
+    }
+    let project = temp_path.join("project");
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::create_dir_all(project.join("node_modules/foo")).unwrap();
+    fs::create_dir_all(project.join("node_modules/bar")).unwrap();
+    fs::write(project.join("a.js"), "").unwrap();
+    fs::write(project.join("a.css"), "").unwrap();
+    fs::write(project.join("node_modules/foo/index.js"), "").unwrap();
+    fs::write(project.join("node_modules/bar/index.js"), "").unwrap();
+    let cache_file = temp_path.join("resolutions.json");
+    let options = ResolveOptions {
+        restrictions: vec![Restriction::RegExp("/project/".into())],
+        ..ResolveOptions::default()
+    };
+
+    let resolver = Resolver::with_persistent_cache(&cache_file, options.clone());
+    let resolved_path = resolver.resolve(&project, "./a").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(project.join("a.js")));
+    let resolved_path =
+        resolver.resolve_with_extensions(&project, "./a", &[".css"]).map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(project.join("a.css")));
+    // `src/node_modules` is looked up once in memory, it is still recorded for both resolutions.
+    let src = project.join("src");
+    let resolved_path = resolver.resolve(&src, "bar").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(project.join("node_modules/bar/index.js")));
+    let resolved_path = resolver.resolve(&src, "foo").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(project.join("node_modules/foo/index.js")));
+    resolver.save_persistent_cache().unwrap();
+
+    // Extensions of the call are part of the key.
+    let resolver = Resolver::with_persistent_cache(&cache_file, options.clone());
+    let resolved_path =
+        resolver.resolve_with_extensions(&project, "./a", &[".css"]).map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(project.join("a.css")));
+    let resolved_path = resolver.resolve(&project, "./a").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(project.join("a.js")));
+
+    // A node_modules directory that appears closer invalidates the entry.
+    fs::create_dir_all(src.join("node_modules/foo")).unwrap();
+    fs::write(src.join("node_modules/foo/index.js"), "").unwrap();
+    let resolver = Resolver::with_persistent_cache(&cache_file, options.clone());
+    let resolved_path = resolver.resolve(&src, "foo").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(src.join("node_modules/foo/index.js")));
+
+    // Restrictions are checked for hits, point the entry outside of the project to observe it.
+    let outside = temp_path.join("outside.js");
+    fs::write(&outside, "").unwrap();
+    let json = fs::read_to_string(&cache_file).unwrap();
+    let json = json.replacen(
+        &serde_json::to_string(&project.join("a.js")).unwrap(),
+        &serde_json::to_string(&outside).unwrap(),
+        1,
+    );
+    fs::write(&cache_file, json).unwrap();
+    let resolver = Resolver::with_persistent_cache(&cache_file, options);
+    let resolution = resolver.resolve(&project, "./a");
+    assert_eq!(resolution, Err(ResolveError::Restriction(outside, "/project/".into())));
+
+    _ = fs::remove_dir_all(&temp_path);
+}
+
+#[test]
+fn persistent_cache_with_hooks() {
+    let dirname = super::fixture_root().join("enhanced_resolve").join("test");
+    let temp_path = dirname.join("temp-persistent-cache-hooks");
+    if temp_path.exists() {
+        _ = fs::remove_dir_all(&temp_path);
+    }
+    let project = temp_path.join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(project.join("index.js"), "").unwrap();
+    fs::write(project.join("other.js"), "").unwrap();
+    let cache_file = temp_path.join("resolutions.json");
+
+    // Hooks cannot be fingerprinted, the file is neither read nor written.
+    let redirect = project.join("other.js");
+    let options = ResolveOptions {
+        post_resolve: Some(PostResolve::new(move |_| {
+            PostResolveAction::Redirect(redirect.clone())
+        })),
+        ..ResolveOptions::default()
+    };
+    let resolver = Resolver::with_persistent_cache(&cache_file, options);
+    let resolved_path = resolver.resolve(&project, "./index").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(project.join("other.js")));
+    resolver.save_persistent_cache().unwrap();
+    assert!(!cache_file.exists());
+
+    let options = ResolveOptions {
+        post_resolve: Some(PostResolve::new(|_| PostResolveAction::Accept)),
+        ..ResolveOptions::default()
+    };
+    let resolver = Resolver::with_persistent_cache(&cache_file, options);
+    let resolved_path = resolver.resolve(&project, "./index").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(project.join("index.js")));
+
+    _ = fs::remove_dir_all(&temp_path);
+}
@@ -3,7 +3,7 @@
 //! enhanced_resolve's test <https://github.com/webpack/enhanced-resolve/blob/main/test/pnp.test.js>
 //! cannot be ported over because it uses mocks on `pnpApi` provided by the runtime.
 
-use crate::{ResolveOptions, Resolver};
+use crate::{PackageJson, ResolveOptions, Resolver};
 
 #[test]
 fn pnp1() {
@@ -43,3 +43,56 @@ fn pnp1() {
         )),
     );
 }
+
+#[test]
+fn zip_file_system() {
+    // A Yarn cache archive, resolved into without a PnP manifest.
+    let fixture = super::fixture_root().join("pnp-zip");
+    let package = fixture.join("cache/dep-npm-1.0.0.zip/node_modules/dep");
+
+    let resolver = Resolver::default();
+
+    #[rustfmt::skip]
+    let pass = [
+        (fixture.clone(), "./cache/dep-npm-1.0.0.zip/node_modules/dep", package.join("lib/index.js")),
+        (fixture, "./cache/dep-npm-1.0.0.zip/node_modules/dep/lib/util", package.join("lib/util.js")),
+        (package.join("lib"), "./util.js", package.join("lib/util.js")),
+    ];
+
+    for (path, request, expected) in pass {
+        let resolution = resolver.resolve(&path, request).unwrap();
+        assert_eq!(resolution.full_path(), expected, "{request}");
+        // The package.json is read from the archive.
+        assert_eq!(resolution.package_json().and_then(|p| p.version()), Some("1.0.0"), "{request}");
+    }
+}
+
+#[test]
+fn pnp_zip() {
+    // A committed PnP manifest with a zipped dependency, resolved without yarn install.
+    let fixture = super::fixture_root().join("pnp-zip");
+    let package = fixture.join("cache/dep-npm-1.0.0.zip/node_modules/dep");
+
+    let resolver = Resolver::new(ResolveOptions {
+        pnp_manifest: Some(pnp::load_pnp_manifest(fixture.join(".pnp.cjs")).unwrap()),
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        (fixture.clone(), "dep", package.join("lib/index.js")),
+        (fixture.clone(), "dep/lib/util", package.join("lib/util.js")),
+        // The dependency resolves itself from inside the archive.
+        (package.join("lib"), "dep/lib/util.js", package.join("lib/util.js")),
+    ];
+
+    for (path, request, expected) in pass {
+        let resolution = resolver.resolve(&path, request).unwrap();
+        assert_eq!(resolution.full_path(), expected, "{request}");
+        assert_eq!(resolution.package_json().and_then(|p| p.version()), Some("1.0.0"), "{request}");
+    }
+
+    // Not a dependency of the project.
+    let resolution = resolver.resolve(&fixture, "is-even");
+    assert!(resolution.is_err(), "{resolution:?}");
+}
@@ -0,0 +1,70 @@
+//! Tests for [crate::ResolveOptions::post_resolve]
+
+use crate::{PostResolve, PostResolveAction, ResolveError, ResolveOptions, Resolver, Restriction};
+
+#[test]
+fn accept() {
+    let f = super::fixture();
+
+    let resolver = Resolver::new(ResolveOptions {
+        post_resolve: Some(PostResolve::new(|_| PostResolveAction::Accept)),
+        ..ResolveOptions::default()
+    });
+
+    let resolved_path = resolver.resolve(&f, "./a").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("a.js")));
+}
+
+#[test]
+fn reject() {
+    let f = super::fixture();
+
+    let resolver = Resolver::new(ResolveOptions {
+        post_resolve: Some(PostResolve::new(|path| {
+            if path.ends_with("b.js") {
+                PostResolveAction::Reject("b is not allowed".into())
+            } else {
+                PostResolveAction::Accept
+            }
+        })),
+        ..ResolveOptions::default()
+    });
+
+    let resolved_path = resolver.resolve(&f, "./a").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("a.js")));
+
+    let resolution = resolver.resolve(&f, "./b");
+    assert_eq!(resolution, Err(ResolveError::Rejected(f.join("b.js"), "b is not allowed".into())));
+}
+
+#[test]
+fn redirect() {
+    let f = super::fixture();
+
+    let redirect = f.join("c.js");
+    let resolver = Resolver::new(ResolveOptions {
+        post_resolve: Some(PostResolve::new(move |_| {
+            PostResolveAction::Redirect(redirect.clone())
+        })),
+        ..ResolveOptions::default()
+    });
+
+    let resolved_path = resolver.resolve(&f, "./a").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("c.js")));
+
+    // The redirect is checked against restrictions.
+    let redirect = f.join("node_modules/m1/a.js");
+    let resolver = Resolver::new(ResolveOptions {
+        restrictions: vec![Restriction::Path(f.join("lib/complex1.js"))],
+        post_resolve: Some(PostResolve::new(move |_| {
+            PostResolveAction::Redirect(redirect.clone())
+        })),
+        ..ResolveOptions::default()
+    });
+
+    let resolution = resolver.resolve(&f, "./lib/complex1");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::Restriction(f.join("node_modules/m1/a.js"), f.join("lib/complex1.js")))
+    );
+}
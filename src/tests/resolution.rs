@@ -1,6 +1,8 @@
 use std::path::{Path, PathBuf};
 
-use crate::{tests::memory_fs::MemoryFS, FsCache, Resolution};
+use crate::{
+    tests::memory_fs::MemoryFS, FsCache, PackageType, Resolution, ResolveOptions, Resolver,
+};
 
 #[test]
 fn test() {
@@ -9,6 +11,8 @@ fn test() {
         query: Some("?query".to_string()),
         fragment: Some("#fragment".to_string()),
         package_json: None,
+        condition_module_type: None,
+        export_subpath: None,
     };
     assert_eq!(resolution.path(), Path::new("foo"));
     assert_eq!(resolution.query(), Some("?query"));
@@ -16,3 +20,88 @@ fn test() {
     assert_eq!(resolution.full_path(), PathBuf::from("foo?query#fragment"));
     assert_eq!(resolution.into_path_buf(), PathBuf::from("foo"));
 }
+
+#[test]
+fn to_slash() {
+    let f = super::fixture();
+    let resolution = Resolver::default().resolve(&f, "./a?query").unwrap();
+    let expected = f.join("a.js").to_string_lossy().replace('\\', "/");
+    assert_eq!(resolution.to_slash(), expected);
+    assert!(!resolution.to_slash().contains('\\'));
+    // The stored path is unchanged.
+    assert_eq!(resolution.path(), f.join("a.js"));
+}
+
+#[cfg(windows)]
+#[test]
+fn to_slash_windows() {
+    let resolution: Resolution<FsCache<MemoryFS>> = Resolution {
+        path: PathBuf::from("C:\\foo\\bar.js"),
+        query: None,
+        fragment: None,
+        package_json: None,
+        condition_module_type: None,
+        export_subpath: None,
+    };
+    assert_eq!(resolution.to_slash(), "C:/foo/bar.js");
+}
+
+#[test]
+fn module_type() {
+    let f = super::fixture_root().join("misc/module-type");
+    let resolver = Resolver::default();
+
+    #[rustfmt::skip]
+    let data = [
+        ("commonjs", "./index.js", Some(PackageType::CommonJs)),
+        ("commonjs", "./index.mjs", Some(PackageType::Module)),
+        ("commonjs", "./index.mts", Some(PackageType::Module)),
+        ("commonjs", "./index.cjs", Some(PackageType::CommonJs)),
+        ("module", "./index.js", Some(PackageType::Module)),
+        ("module", "./index.cjs", Some(PackageType::CommonJs)),
+        ("module", "./index.cts", Some(PackageType::CommonJs)),
+        ("module", "./index.mjs", Some(PackageType::Module)),
+        ("module", "./data.json", None),
+    ];
+
+    for (dir, request, expected) in data {
+        let resolution = resolver.resolve(f.join(dir), request).unwrap();
+        assert_eq!(resolution.module_type(), expected, "{dir} {request}");
+    }
+}
+
+#[test]
+fn module_type_from_conditions() {
+    let f = super::fixture_root().join("misc/module-type-conditions");
+
+    #[rustfmt::skip]
+    let data = [
+        (vec!["import"], "dual", Some(PackageType::Module)),
+        (vec!["require"], "dual", Some(PackageType::CommonJs)),
+        (vec!["import", "node"], "dual/nested", Some(PackageType::Module)),
+        // Selected by "default", falls back to the "type" field.
+        (vec!["require", "node"], "dual/nested", Some(PackageType::CommonJs)),
+        // Explicit extensions take precedence.
+        (vec!["import"], "dual/explicit", Some(PackageType::CommonJs)),
+        (vec!["import"], "dual-module", Some(PackageType::Module)),
+        (vec!["require"], "dual-module", Some(PackageType::CommonJs)),
+    ];
+
+    for (condition_names, request, expected) in data {
+        let resolver = Resolver::new(ResolveOptions {
+            condition_names: condition_names.iter().map(ToString::to_string).collect(),
+            module_type_from_conditions: true,
+            ..ResolveOptions::default()
+        });
+        let resolution = resolver.resolve(&f, request).unwrap();
+        assert_eq!(resolution.module_type(), expected, "{condition_names:?} {request}");
+    }
+
+    // The "type" field is used by default.
+    let resolver = Resolver::new(ResolveOptions {
+        condition_names: vec!["require".into()],
+        ..ResolveOptions::default()
+    });
+    let resolution = resolver.resolve(&f, "dual-module").unwrap();
+    assert_eq!(resolution.module_type(), Some(PackageType::Module));
+}
@@ -1,6 +1,14 @@
 //! <https://github.com/webpack/enhanced-resolve/blob/main/test/resolve.test.js>
 
-use crate::{ResolveError, ResolveOptions, Resolver};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use crate::{
+    AliasValue, Resolution, ResolveContext, ResolveError, ResolveOptions, ResolveSource, Resolver,
+    SpecifierError,
+};
 
 #[test]
 fn resolve() {
@@ -71,6 +79,29 @@ fn issue238_resolve() {
     assert_eq!(resolved_path, Ok(f.join("src/common/config/myObjectFile.js")),);
 }
 
+#[test]
+fn absolute_modules_directory() {
+    let f = super::fixture();
+    let vendor = super::fixture_root().join("misc/absolute-modules/vendor");
+
+    let resolver = Resolver::new(ResolveOptions {
+        modules: vec![vendor.to_string_lossy().to_string(), "node_modules".into()],
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("vendored", vendor.join("vendored/lib.js")),
+        ("vendored/other", vendor.join("vendored/other.js")),
+        ("m1/a", f.join("node_modules/m1/a.js")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+}
+
 #[test]
 fn prefer_relative() {
     let f = super::fixture();
@@ -110,6 +141,38 @@ fn resolve_to_context() {
     }
 }
 
+#[test]
+fn resolve_to_context_exports() {
+    let f = super::fixture_root().join("misc/exports-context");
+    let p = f.join("node_modules/pkg");
+    let resolver =
+        Resolver::new(ResolveOptions { resolve_to_context: true, ..ResolveOptions::default() });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("pkg/components/button", p.join("src/components/button")),
+        // Folder mappings
+        ("pkg/dir/button", p.join("src/components/button")),
+        ("pkg/dir/button/index.js", p.join("src/components/button")),
+        // The directory of an exported file
+        ("pkg/feature", p.join("src")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    let resolution = resolver.resolve(&f, "pkg/dir/missing");
+    assert_eq!(resolution, Err(ResolveError::NotFound("pkg/dir/missing".into())));
+
+    let resolution = resolver.resolve(&f, "pkg/src/internal");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::PackagePathNotExported("./src/internal".into(), p.join("package.json")))
+    );
+}
+
 #[test]
 fn resolve_hash_as_module() {
     let f = super::fixture();
@@ -118,6 +181,156 @@ fn resolve_hash_as_module() {
     assert_eq!(resolution, Err(ResolveError::NotFound("#a".into())));
 }
 
+#[test]
+fn resolve_empty_specifier() {
+    let f = super::fixture();
+    let resolver = Resolver::default();
+
+    for specifier in ["", "   "] {
+        let resolution = resolver.resolve(&f, specifier);
+        assert_eq!(
+            resolution,
+            Err(ResolveError::Specifier(SpecifierError::Empty(specifier.to_string()))),
+            "{specifier:?}"
+        );
+    }
+
+    let resolved_path = resolver.resolve(f.join("foo"), ".").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("foo/index.js")));
+}
+
+#[test]
+fn resolve_from_any() {
+    let f = super::fixture();
+    let resolver = Resolver::default();
+
+    let dirs = [f.join("multiple_modules"), f.join("browser-module")];
+    let resolved_path = resolver.resolve_from_any(&dirs, "./lib/browser").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("browser-module/lib/browser.js")));
+
+    let resolution = resolver.resolve_from_any(&dirs, "./missing");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::NotFoundInAny(
+            "./missing".into(),
+            vec![
+                ResolveError::NotFound("./missing".into()),
+                ResolveError::NotFound("./missing".into())
+            ]
+        ))
+    );
+}
+
+#[test]
+fn resolve_with_source() {
+    let f = super::fixture();
+    let resolver = Resolver::default();
+
+    let (resolution, source) = resolver.resolve_with_source(&f, "m1/a").unwrap();
+    assert_eq!(resolution.full_path(), f.join("node_modules/m1/a.js"));
+    assert_eq!(source, ResolveSource::Io);
+
+    let (resolution, source) = resolver.resolve_with_source(&f, "m1/a").unwrap();
+    assert_eq!(resolution.full_path(), f.join("node_modules/m1/a.js"));
+    assert_eq!(source, ResolveSource::Cached);
+
+    resolver.clear_cache();
+    let (_, source) = resolver.resolve_with_source(&f, "m1/a").unwrap();
+    assert_eq!(source, ResolveSource::Io);
+}
+
+#[test]
+fn resolve_ambiguous() {
+    let f = super::fixture_root().join("misc/ambiguous");
+    let resolver = Resolver::default();
+
+    #[rustfmt::skip]
+    let pass = [
+        ("file and directory", "./utils", vec![f.join("utils.js"), f.join("utils/index.js")]),
+        ("file and directory with query", "./utils?query", vec![f.join("utils.js?query"), f.join("utils/index.js?query")]),
+        ("file only", "./only-file", vec![f.join("only-file.js")]),
+        ("directory only", "./only-dir", vec![f.join("only-dir/index.js")]),
+        ("trailing slash", "./utils/", vec![f.join("utils/index.js")]),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolved_paths = resolver
+            .resolve_ambiguous(&f, request)
+            .map(|resolutions| resolutions.into_iter().map(|r| r.full_path()).collect::<Vec<_>>());
+        assert_eq!(resolved_paths, Ok(expected), "{comment} {request}");
+    }
+
+    let resolution = resolver.resolve_ambiguous(&f, "./missing").map(|_| ());
+    assert_eq!(resolution, Err(ResolveError::NotFound("./missing".into())));
+}
+
+#[test]
+fn resolve_many() {
+    let f = super::fixture();
+
+    let specifiers = ["./a", "m1/a", "fs", "./missing", "node:path", "./a"];
+
+    for resolver in [
+        Resolver::new(ResolveOptions::default().with_builtin_modules(true)),
+        // Builtins can be aliased.
+        Resolver::new(ResolveOptions {
+            builtin_modules: true,
+            alias: vec![("fs".into(), vec![AliasValue::from("./b")])],
+            ..ResolveOptions::default()
+        }),
+    ] {
+        let resolutions = resolver.resolve_many(&f, &specifiers);
+        assert_eq!(resolutions.len(), specifiers.len());
+        for (specifier, resolution) in specifiers.iter().zip(resolutions) {
+            assert_eq!(resolution, resolver.resolve(&f, specifier), "{specifier}");
+        }
+    }
+
+    let resolver = Resolver::new(ResolveOptions::default().with_builtin_modules(true));
+    let resolutions = resolver.resolve_many(&f, &["fs", "./a"]);
+    assert_eq!(
+        resolutions[0],
+        Err(ResolveError::Builtin { resolved: "node:fs".into(), is_runtime_module: false })
+    );
+    assert_eq!(resolutions[1].as_ref().map(Resolution::full_path), Ok(f.join("a.js")));
+}
+
+#[test]
+fn would_resolve_to() {
+    let f = super::fixture_root().join("misc/ambiguous");
+    let resolver = Resolver::default();
+
+    #[rustfmt::skip]
+    let data = [
+        ("./utils", f.join("utils.js"), true),
+        ("./utils.js", f.join("utils.js"), true),
+        ("./utils/", f.join("utils/index.js"), true),
+        ("./utils?query", f.join("utils.js"), true),
+        // The file shadows the directory.
+        ("./utils", f.join("utils/index.js"), false),
+        // Extensions are not completed for the expected path.
+        ("./utils", f.join("utils"), false),
+        ("./only-file", f.join("utils.js"), false),
+        ("./missing", f.join("missing.js"), false),
+    ];
+
+    for (request, expected, would_resolve) in data {
+        assert_eq!(resolver.would_resolve_to(&f, request, &expected), would_resolve, "{request}");
+    }
+
+    // The expected path is canonicalized, `node_modules/@scope` is a symlink to `packages/@scope`.
+    let f = super::fixture_root().join("enhanced_resolve/test/temp-would-resolve-to");
+    let Some(packages) = super::symlink::create_scope_symlink(&f) else {
+        return;
+    };
+    let expected = f.join("node_modules/@scope/pkg/index.js");
+    assert!(resolver.would_resolve_to(&f, "@scope/pkg", &expected));
+    let resolver = Resolver::new(ResolveOptions { symlinks: false, ..ResolveOptions::default() });
+    assert!(resolver.would_resolve_to(&f, "@scope/pkg", &expected));
+    assert!(!resolver.would_resolve_to(&f, "@scope/pkg", &packages.join("@scope/pkg/index.js")));
+    super::symlink::cleanup_symlinks(&f);
+}
+
 #[cfg(windows)]
 #[test]
 fn resolve_normalized_on_windows() {
@@ -142,3 +355,295 @@ fn resolve_normalized_on_windows() {
         Ok(absolute_str.clone().into_owned())
     );
 }
+
+// macOS file systems are case-insensitive but case-preserving by default.
+#[cfg(target_os = "macos")]
+#[test]
+fn case_preserving() {
+    let f = super::fixture();
+    let resolver = Resolver::default();
+
+    // The requested case is kept instead of the on-disk `a.js`.
+    let resolved_path = resolver.resolve(&f, "./A").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("A.js")));
+}
+
+#[test]
+fn flat_modules() {
+    let f = super::fixture_root().join("misc/flat-modules");
+    let app = f.join("app");
+    let deps = f.join("deps");
+
+    let resolver = Resolver::new(ResolveOptions {
+        flat_modules: Some(deps.clone()),
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        // Takes precedence over `app/node_modules/flat`
+        ("flat", deps.join("flat/index.js")),
+        ("@scope/pkg", deps.join("@scope/pkg/index.js")),
+        ("flat/index.js", deps.join("flat/index.js")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&app, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    // node_modules are not walked
+    let resolved_path = resolver.resolve(&app, "walked").map(|r| r.full_path());
+    assert_eq!(resolved_path, Err(ResolveError::NotFound("walked".into())));
+
+    let resolver = Resolver::new(ResolveOptions {
+        flat_modules: Some(deps.clone()),
+        flat_modules_fallback: true,
+        ..ResolveOptions::default()
+    });
+    let resolved_path = resolver.resolve(&app, "flat").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(deps.join("flat/index.js")));
+    let resolved_path = resolver.resolve(&app, "walked").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(app.join("node_modules/walked/index.js")));
+}
+
+#[test]
+fn package_json_encoding() {
+    let f = super::fixture_root().join("misc/package-json-encoding");
+    let resolver = Resolver::default();
+
+    for name in ["utf16le", "utf16be", "utf8bom"] {
+        let resolution = resolver.resolve(&f, &format!("./{name}")).unwrap();
+        assert_eq!(resolution.full_path(), f.join(name).join("main.js"), "{name}");
+        let package_json = resolution.package_json().unwrap();
+        assert_eq!(package_json.name.as_deref(), Some(name));
+    }
+}
+
+#[test]
+fn find_package_json_with_field() {
+    let f = super::fixture_root().join("misc/package-json-field");
+    let resolver = Resolver::default();
+    let src = f.join("packages/inner/src");
+
+    // The nearest package.json is found without skipping.
+    let package_json = resolver.find_package_json_with_field(&src, "name").unwrap().unwrap();
+    assert_eq!(package_json.path, f.join("packages/inner/package.json"));
+
+    // `packages/inner/package.json` does not declare "type".
+    let package_json = resolver.find_package_json_with_field(&src, "type").unwrap().unwrap();
+    assert_eq!(package_json.path, f.join("package.json"));
+
+    // Fields not read by the resolver are found as well.
+    let package_json = resolver.find_package_json_with_field(&src, "workspaces").unwrap().unwrap();
+    assert_eq!(package_json.path, f.join("package.json"));
+
+    let package_json = resolver.find_package_json_with_field(&src, "missing-field").unwrap();
+    assert!(package_json.is_none());
+}
+
+#[test]
+fn node_modules_read_dir() {
+    let f = super::fixture();
+    let dir = f.join("browser-module");
+
+    let resolver =
+        Resolver::new(ResolveOptions { node_modules_read_dir: true, ..ResolveOptions::default() });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("m1/a", f.join("node_modules/m1/a.js")),
+        ("m2/b", f.join("node_modules/m2/b.js")),
+        // Files in node_modules are found by their prefix.
+        ("module-a", dir.join("node_modules/module-a.js")),
+        ("complexm/step1", f.join("node_modules/complexm/step1.js")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&dir, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    // Packages missing from the listing are reported as missing dependencies.
+    let mut ctx = ResolveContext::default();
+    let resolved_path =
+        resolver.resolve_with_context(&dir, "m1/a", &mut ctx).map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("node_modules/m1/a.js")));
+    assert!(ctx.missing_dependencies.contains(&dir.join("node_modules/m1")));
+
+    let resolution = resolver.resolve(&dir, "missing-package");
+    assert_eq!(resolution, Err(ResolveError::NotFound("missing-package".into())));
+}
+
+#[test]
+fn target_node_version() {
+    let f = super::fixture_root().join("misc/engines");
+
+    let resolver = Resolver::new(ResolveOptions {
+        target_node_version: Some("16.20.0".into()),
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("legacy", f.join("node_modules/legacy/index.js")),
+        ("none", f.join("node_modules/none/index.js")),
+        // Ranges that cannot be parsed are ignored.
+        ("unparsable", f.join("node_modules/unparsable/index.js")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    let resolution = resolver.resolve(&f, "modern");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::IncompatibleEngine {
+            package: f.join("node_modules/modern/package.json"),
+            required: ">=20".into(),
+            actual: "16.20.0".into(),
+        })
+    );
+
+    // Disabled by default
+    let resolved_path = Resolver::default().resolve(&f, "modern").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("node_modules/modern/index.js")));
+}
+
+#[test]
+fn module_search_paths() {
+    let f = super::fixture_root();
+    let from = f.join("app/node_modules/foo/lib");
+    let root_paths = || f.ancestors().map(|dir| dir.join("node_modules"));
+
+    // Same as `module.paths` in Node.js.
+    let resolver = Resolver::default();
+    let expected = [
+        from.join("node_modules"),
+        f.join("app/node_modules/foo/node_modules"),
+        f.join("app/node_modules"),
+    ]
+    .into_iter()
+    .chain(root_paths())
+    .collect::<Vec<_>>();
+    assert_eq!(resolver.module_search_paths(&from), expected);
+
+    let shared = f.join("shared");
+    let resolver = Resolver::new(ResolveOptions {
+        modules: vec!["node_modules".into(), shared.to_string_lossy().into_owned()],
+        flat_modules: Some(f.join("flat")),
+        flat_modules_fallback: true,
+        ..ResolveOptions::default()
+    });
+    let expected = [f.join("flat"), f.join("app/node_modules")]
+        .into_iter()
+        .chain(root_paths())
+        .chain([shared])
+        .collect::<Vec<_>>();
+    assert_eq!(resolver.module_search_paths(f.join("app")), expected);
+
+    let resolver = Resolver::new(ResolveOptions::default().with_flat_modules(f.join("flat")));
+    assert_eq!(resolver.module_search_paths(&from), vec![f.join("flat")]);
+}
+
+#[test]
+fn trim_specifier_whitespace() {
+    let f = super::fixture_root().join("misc/whitespace");
+
+    // Whitespace is significant by default.
+    let resolver = Resolver::default();
+    for specifier in [" ./x", "./x ", " pkg "] {
+        let resolution = resolver.resolve(&f, specifier);
+        assert_eq!(resolution, Err(ResolveError::NotFound(specifier.into())), "{specifier:?}");
+    }
+    let resolved_path = resolver.resolve(&f, "./a b").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("a b.js")));
+
+    let resolver = Resolver::new(ResolveOptions::default().with_trim_specifier_whitespace(true));
+
+    #[rustfmt::skip]
+    let pass = [
+        (" ./x ", f.join("x.js")),
+        ("\t./x.js\n", f.join("x.js")),
+        (" ./a b ", f.join("a b.js")),
+        (" pkg ", f.join("node_modules/pkg/index.js")),
+    ];
+
+    for (specifier, expected) in pass {
+        let resolved_path = resolver.resolve(&f, specifier).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{specifier:?}");
+    }
+
+    // Whitespace inside the specifier is kept.
+    let resolution = resolver.resolve(&f, " ./ab ");
+    assert_eq!(resolution, Err(ResolveError::NotFound("./ab".into())));
+
+    // Whitespace after the query or fragment is not part of them.
+    let resolution = resolver.resolve(&f, " ./x?q ").unwrap();
+    assert_eq!(resolution.path(), f.join("x.js"));
+    assert_eq!(resolution.query(), Some("?q"));
+    let resolution = resolver.resolve(&f, " ./x#f\t").unwrap();
+    assert_eq!(resolution.fragment(), Some("#f"));
+
+    #[rustfmt::skip]
+    let fail = [
+        (" ", ResolveError::Specifier(SpecifierError::Empty(" ".into()))),
+        (" ?x", ResolveError::Specifier(SpecifierError::Empty("?x".into()))),
+        (" #/", ResolveError::NotFound("#/".into())),
+    ];
+
+    for (specifier, error) in fail {
+        let resolution = resolver.resolve(&f, specifier);
+        assert_eq!(resolution, Err(error), "{specifier:?}");
+    }
+}
+
+#[test]
+fn options_fingerprint() {
+    let resolver = Resolver::new(ResolveOptions::default().with_condition_names(&["import"]));
+
+    let same = Resolver::new(ResolveOptions::default().with_condition_names(&["import"]));
+    assert_eq!(resolver.options_fingerprint(), same.options_fingerprint());
+
+    let other = resolver.clone_with_condition_names(&["require"]);
+    assert_ne!(resolver.options_fingerprint(), other.options_fingerprint());
+
+    let other = Resolver::new(
+        ResolveOptions::default().with_condition_names(&["import"]).with_extension(".ts"),
+    );
+    assert_ne!(resolver.options_fingerprint(), other.options_fingerprint());
+
+    // The abort signal does not participate.
+    let signal = Arc::new(AtomicBool::new(false));
+    let with_signal = Resolver::new(
+        ResolveOptions::default()
+            .with_condition_names(&["import"])
+            .with_abort_signal(Arc::clone(&signal)),
+    );
+    assert_eq!(resolver.options_fingerprint(), with_signal.options_fingerprint());
+    signal.store(true, Ordering::Relaxed);
+    assert_eq!(resolver.options_fingerprint(), with_signal.options_fingerprint());
+}
+
+#[test]
+fn new_prints_nothing() {
+    // The resolver is constructed in a child process of this test binary to capture its stdout.
+    if std::env::var_os("OXC_RESOLVER_NEW_PRINTS_NOTHING").is_some() {
+        println!("<start>");
+        drop(Resolver::default());
+        println!("<end>");
+        return;
+    }
+    let output = std::process::Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", "tests::resolve::new_prints_nothing", "--nocapture", "--test-threads=1"])
+        .env("OXC_RESOLVER_NEW_PRINTS_NOTHING", "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let start = stdout.find("<start>\n").unwrap() + "<start>\n".len();
+    let end = stdout.find("<end>").unwrap();
+    assert_eq!(&stdout[start..end], "");
+}
@@ -2,8 +2,7 @@
 
 use crate::{ResolveError, ResolveOptions, Resolver, Restriction};
 
-// TODO: regex
-// * should respect RegExp restriction
+// TODO: Restrictions are checked after resolving, alternatives are not tried.
 // * should try to find alternative #1
 // * should try to find alternative #2
 // * should try to find alternative #3
@@ -23,3 +22,60 @@ fn restriction1() {
     let resolution = resolver.resolve(&f, "pck2");
     assert_eq!(resolution, Err(ResolveError::Restriction(fixture.join("c.js"), f)));
 }
+
+// should respect RegExp restriction
+#[test]
+fn restriction_regex() {
+    let f = super::fixture().join("restrictions");
+
+    let resolver = Resolver::new(ResolveOptions {
+        extensions: vec![".js".into()],
+        restrictions: vec![Restriction::RegExp(r"\.(sass|scss|css)$".into())],
+        ..ResolveOptions::default()
+    });
+
+    let resolution = resolver.resolve(&f, "pck1");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::Restriction(
+            f.join("node_modules/pck1/index.js"),
+            r"\.(sass|scss|css)$".into()
+        ))
+    );
+    let resolution = resolver.resolve(&f, "pck1/index.css").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(f.join("node_modules/pck1/index.css")));
+
+    // Separators are matched as forward slashes.
+    let resolver = Resolver::new(ResolveOptions {
+        restrictions: vec![Restriction::RegExp(".*/node_modules/pck1/.*".into())],
+        ..ResolveOptions::default()
+    });
+    assert!(resolver.resolve(&f, "pck1").is_ok());
+    assert!(matches!(resolver.resolve(&f, "pck2/module.js"), Err(ResolveError::Restriction(..))));
+
+    // Case insensitive patterns.
+    let resolver = Resolver::new(ResolveOptions {
+        restrictions: vec![Restriction::RegExp(r"(?i)INDEX\.CSS$".into())],
+        ..ResolveOptions::default()
+    });
+    let resolution = resolver.resolve(&f, "pck1/index.css").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(f.join("node_modules/pck1/index.css")));
+}
+
+#[test]
+fn restriction_regex_invalid() {
+    let f = super::fixture().join("restrictions");
+
+    // Lookaheads are valid in JavaScript but not supported by the regex crate.
+    for pattern in ["(", "^(?!.*node_modules).*$"] {
+        let resolver = Resolver::new(ResolveOptions {
+            restrictions: vec![Restriction::RegExp(pattern.into())],
+            ..ResolveOptions::default()
+        });
+        let error = resolver.resolve(&f, "pck1").unwrap_err();
+        assert!(
+            matches!(&error, ResolveError::InvalidRestriction(p, _) if p == pattern),
+            "{error:?}"
+        );
+    }
+}
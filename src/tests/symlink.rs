@@ -1,9 +1,15 @@
-use std::{fs, io, path::Path};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 #[cfg(target_family = "windows")]
 use normalize_path::NormalizePath;
 
-use crate::{ResolveOptions, Resolver};
+use crate::{
+    FileSystemOs, FsCache, ResolveError, ResolveOptions, Resolver, ResolverGeneric, SymlinksFilter,
+};
 
 #[derive(Debug, Clone, Copy)]
 enum FileType {
@@ -60,10 +66,29 @@ fn create_symlinks(dirname: &Path, temp_path: &Path) -> io::Result<()> {
     Ok(())
 }
 
-fn cleanup_symlinks(temp_path: &Path) {
+pub(super) fn cleanup_symlinks(temp_path: &Path) {
     _ = fs::remove_dir_all(temp_path);
 }
 
+/// Creates `temp_path/node_modules/@scope` as a symlink to `misc/scope-symlink/packages/@scope`
+/// and returns the `packages` directory.
+///
+/// Returns `None` when the symlink cannot be created, e.g. without admin rights on windows.
+pub(super) fn create_scope_symlink(temp_path: &Path) -> Option<PathBuf> {
+    if temp_path.exists() {
+        _ = fs::remove_dir_all(temp_path);
+    }
+    fs::create_dir_all(temp_path.join("node_modules")).ok()?;
+    let packages = super::fixture_root().join("misc/scope-symlink/packages");
+    if symlink(packages.join("@scope"), temp_path.join("node_modules/@scope"), FileType::Dir)
+        .is_err()
+    {
+        cleanup_symlinks(temp_path);
+        return None;
+    }
+    Some(packages)
+}
+
 #[test]
 fn test() -> io::Result<()> {
     let root = super::fixture_root().join("enhanced_resolve");
@@ -83,6 +108,8 @@ fn test() -> io::Result<()> {
     let resolver_without_symlinks =
         Resolver::new(ResolveOptions { symlinks: false, ..ResolveOptions::default() });
     let resolver_with_symlinks = Resolver::default();
+    let resolver_with_lazy_symlinks =
+        Resolver::new(ResolveOptions { lazy_symlinks: true, ..ResolveOptions::default() });
 
     #[rustfmt::skip]
     let pass = [
@@ -115,6 +142,15 @@ fn test() -> io::Result<()> {
         let filename = resolver_with_symlinks.resolve(&path, request).map(|r| r.full_path());
         assert_eq!(filename, Ok(root.join("lib/index.js")), "{comment:?}");
 
+        // The package.json of the real path is found when symlinks are resolved lazily.
+        let resolution = resolver_with_lazy_symlinks.resolve(&path, request).unwrap();
+        assert_eq!(resolution.full_path(), root.join("lib/index.js"), "{comment:?}");
+        assert_eq!(
+            resolution.package_json().map(|p| p.realpath.clone()),
+            Some(root.join("package.json")),
+            "{comment:?}"
+        );
+
         let resolved_path =
             resolver_without_symlinks.resolve(&path, request).map(|r| r.full_path());
         assert_eq!(resolved_path, Ok(path.join(request)));
@@ -122,3 +158,165 @@ fn test() -> io::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn max_symlink_depth() -> io::Result<()> {
+    let dirname = super::fixture_root().join("enhanced_resolve").join("test");
+    let temp_path = dirname.join("temp-symlink-depth");
+    if temp_path.exists() {
+        _ = fs::remove_dir_all(&temp_path);
+    }
+    fs::create_dir(&temp_path)?;
+    fs::write(temp_path.join("index.js"), "")?;
+    // link5 -> link4 -> link3 -> link2 -> link1 -> index.js
+    let mut target = temp_path.join("index.js");
+    for i in 1..=5 {
+        let link = temp_path.join(format!("link{i}"));
+        if symlink(&target, &link, FileType::File).is_err() {
+            // Symlinks cannot be created without admin rights on windows.
+            cleanup_symlinks(&temp_path);
+            return Ok(());
+        }
+        target = link;
+    }
+
+    let resolver = ResolverGeneric::new_with_cache(
+        Arc::new(FsCache::<FileSystemOs>::default().with_max_symlink_depth(5)),
+        ResolveOptions::default(),
+    );
+    let resolved_path = resolver.resolve(&temp_path, "./link5").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(temp_path.join("index.js")));
+
+    let resolver = ResolverGeneric::new_with_cache(
+        Arc::new(FsCache::<FileSystemOs>::default().with_max_symlink_depth(4)),
+        ResolveOptions::default(),
+    );
+    let resolved_path = resolver.resolve(&temp_path, "./link5").map(|r| r.full_path());
+    assert_eq!(resolved_path, Err(ResolveError::TooManySymlinks(temp_path.join("link1"))));
+    // The error depends on the chain, a shorter one to the same links still resolves.
+    for specifier in ["./link1", "./link4"] {
+        let resolved_path = resolver.resolve(&temp_path, specifier).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(temp_path.join("index.js")), "{specifier}");
+    }
+
+    cleanup_symlinks(&temp_path);
+    Ok(())
+}
+
+#[test]
+fn scope_symlink() {
+    // `node_modules/@scope` is a symlink to `packages/@scope`
+    let f = super::fixture_root().join("enhanced_resolve/test/temp-scope-symlink");
+    let Some(packages) = create_scope_symlink(&f) else {
+        return;
+    };
+
+    let resolver = Resolver::default();
+    let resolver_without_symlinks =
+        Resolver::new(ResolveOptions { symlinks: false, ..ResolveOptions::default() });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("package", "@scope/pkg"),
+        ("subpath", "@scope/pkg/index.js"),
+    ];
+
+    for (comment, request) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(packages.join("@scope/pkg/index.js")), "{comment}");
+
+        let resolved_path = resolver_without_symlinks.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(f.join("node_modules/@scope/pkg/index.js")), "{comment}");
+    }
+
+    let resolved_path = resolver.resolve(&f, "@scope/missing").map(|r| r.full_path());
+    assert_eq!(resolved_path, Err(ResolveError::NotFound("@scope/missing".into())));
+    cleanup_symlinks(&f);
+}
+
+#[test]
+fn symlinks_filter() {
+    // `linked` and `vendor/linked` are symlinks to `real`
+    let f = super::fixture_root().join("enhanced_resolve/test/temp-symlinks-filter");
+    let real = f.join("real");
+    if f.exists() {
+        _ = fs::remove_dir_all(&f);
+    }
+    fs::create_dir_all(&real).unwrap();
+    fs::create_dir(f.join("vendor")).unwrap();
+    fs::write(real.join("index.js"), "").unwrap();
+    if symlink(&real, f.join("linked"), FileType::Dir).is_err()
+        || symlink(&real, f.join("vendor/linked"), FileType::Dir).is_err()
+    {
+        // Symlinks cannot be created without admin rights on windows.
+        cleanup_symlinks(&f);
+        return;
+    }
+    let vendor = f.join("vendor");
+
+    let resolver = Resolver::new(ResolveOptions {
+        symlinks_filter: Some(SymlinksFilter::new(move |path| !path.starts_with(&vendor))),
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("followed outside vendor", "./linked/index.js", real.join("index.js")),
+        ("preserved inside vendor", "./vendor/linked/index.js", f.join("vendor/linked/index.js")),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{comment}");
+    }
+
+    // Not consulted when symlinks are disabled.
+    let resolver = Resolver::new(ResolveOptions {
+        symlinks: false,
+        symlinks_filter: Some(SymlinksFilter::new(|_| true)),
+        ..ResolveOptions::default()
+    });
+    let resolved_path = resolver.resolve(&f, "./linked/index.js").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("linked/index.js")));
+    cleanup_symlinks(&f);
+}
+
+#[test]
+fn canonicalize() -> io::Result<()> {
+    // `node_modules/@scope` is a symlink to `packages/@scope`
+    let f = super::fixture_root().join("enhanced_resolve/test/temp-canonicalize-scope");
+    let Some(packages) = create_scope_symlink(&f) else {
+        return Ok(());
+    };
+    let resolver = Resolver::default();
+
+    #[rustfmt::skip]
+    let pass = [
+        (f.join("node_modules/@scope/pkg/index.js"), packages.join("@scope/pkg/index.js")),
+        (f.join("node_modules/@scope/pkg"), packages.join("@scope/pkg")),
+        (packages.join("@scope/pkg/index.js"), packages.join("@scope/pkg/index.js")),
+    ];
+
+    for (path, expected) in pass {
+        assert_eq!(resolver.canonicalize(&path), Ok(expected), "{path:?}");
+    }
+    cleanup_symlinks(&f);
+
+    // Circular symlinks fail instead of recursing forever.
+    let temp_path = super::fixture_root().join("enhanced_resolve/test/temp-canonicalize");
+    if temp_path.exists() {
+        _ = fs::remove_dir_all(&temp_path);
+    }
+    fs::create_dir(&temp_path)?;
+    if symlink(temp_path.join("b"), temp_path.join("a"), FileType::File).is_err()
+        || symlink(temp_path.join("a"), temp_path.join("b"), FileType::File).is_err()
+    {
+        // Symlinks cannot be created without admin rights on windows.
+        cleanup_symlinks(&temp_path);
+        return Ok(());
+    }
+    let result = resolver.canonicalize(temp_path.join("a"));
+    assert!(matches!(result, Err(ResolveError::IOError(_))), "{result:?}");
+    cleanup_symlinks(&temp_path);
+    Ok(())
+}
@@ -150,6 +150,43 @@ fn test_paths() {
     }
 }
 
+#[test]
+fn test_paths_built() {
+    let path = Path::new("/foo/tsconfig.json");
+    let mut tsconfig_json = serde_json::json!({
+        "compilerOptions": {
+            "paths": {
+                "*": ["generated/*"],
+                "bar/*": ["test/*"],
+                "bar/*.js": ["js/*"],
+                "bar/baz/*": ["baz/*"],
+                "b*": ["b/*"],
+                "bar/baz": ["exact"],
+            }
+        }
+    })
+    .to_string();
+    let tsconfig = TsConfigSerde::parse(true, path, &mut tsconfig_json.clone()).unwrap();
+    let mut built_tsconfig = TsConfigSerde::parse(true, path, &mut tsconfig_json).unwrap();
+    built_tsconfig.build();
+
+    let data = [
+        ("test", vec!["/foo/generated/test"]),
+        ("bar/hi", vec!["/foo/test/hi"]),
+        // Same prefix length, the first key wins
+        ("bar/hi.js", vec!["/foo/test/hi.js"]),
+        ("bar/baz/hi", vec!["/foo/baz/hi"]),
+        ("bar/baz", vec!["/foo/exact"]),
+        ("baz", vec!["/foo/b/az"]),
+    ];
+
+    for (specifier, expected) in data {
+        let expected = expected.into_iter().map(PathBuf::from).collect::<Vec<_>>();
+        assert_eq!(tsconfig.resolve_path_alias(specifier), expected, "{specifier}");
+        assert_eq!(built_tsconfig.resolve_path_alias(specifier), expected, "{specifier}");
+    }
+}
+
 // <https://github.com/parcel-bundler/parcel/blob/c8f5c97a01f643b4d5c333c02d019ef2618b44a5/packages/utils/node-resolver-rs/src/tsconfig.rs#L233C6-L233C19>
 #[test]
 fn test_base_url() {
@@ -236,6 +273,36 @@ fn test_template_variable() {
     }
 }
 
+#[test]
+fn test_auto_discovery() {
+    let f = super::fixture_root().join("tsconfig/cases/auto-discovery/packages");
+
+    let resolver = Resolver::new(ResolveOptions {
+        tsconfig_auto_discovery: true,
+        extensions: vec![".ts".into()],
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        (f.join("a/src"), "~/util", f.join("a/src/util.ts")),
+        (f.join("b/src"), "~/util", f.join("b/src/util.ts")),
+        (f.join("b"), "~/index", f.join("b/src/index.ts")),
+    ];
+
+    for (dir, request, expected) in pass {
+        let resolved_path = resolver.resolve(&dir, request).map(|f| f.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request} {dir:?}");
+    }
+
+    let tsconfig = resolver.find_tsconfig(f.join("a/src")).unwrap().unwrap();
+    assert_eq!(tsconfig.path(), f.join("a/tsconfig.json"));
+
+    // Disabled by default
+    let resolution = Resolver::default().resolve(f.join("a/src"), "~/util");
+    assert_eq!(resolution, Err(ResolveError::NotFound("~/util".into())));
+}
+
 #[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
 mod windows_test {
     use std::{
@@ -531,3 +598,30 @@ mod windows_test {
         }
     }
 }
+
+#[test]
+fn paths_query() {
+    let f = super::fixture_root().join("tsconfig/cases/paths-query");
+
+    let resolver = Resolver::new(ResolveOptions {
+        tsconfig: Some(TsconfigOptions {
+            config_file: f.join("tsconfig.json"),
+            references: TsconfigReferences::Auto,
+        }),
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("@q/x?raw", f.join("src/x.js?raw")),
+        ("@q/x.js?raw#hash", f.join("src/x.js?raw#hash")),
+        ("@q/x#hash", f.join("src/x.js#hash")),
+        ("@inline", f.join("src/y.js?inline")),
+        ("@inline?raw", f.join("src/y.js?raw")),
+    ];
+
+    for (request, expected) in pass {
+        let resolution = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolution, Ok(expected), "{request}");
+    }
+}
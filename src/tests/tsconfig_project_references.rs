@@ -29,7 +29,9 @@ fn auto() {
         {
             let dir = f.parent().unwrap().join("paths_template_variable");
             (dir.clone(), "foo", dir.join("foo.js"))
-        }
+        },
+        // Template variable in a config extended by a reference
+        (f.join("project_d"), "#/index.ts", f.join("project_d/src/index.ts")),
     ];
 
     for (path, request, expected) in pass {
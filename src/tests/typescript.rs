@@ -0,0 +1,181 @@
+//! Tests for [crate::TypeScriptOptions]
+
+use crate::{ResolveError, ResolveOptions, Resolver, TypeScriptOptions};
+
+#[test]
+fn node_builtin_types() {
+    let f = super::fixture_root().join("misc/typescript");
+    let types = f.join("node_modules/@types/node");
+
+    let resolver = Resolver::new(ResolveOptions {
+        builtin_modules: true,
+        typescript: Some(TypeScriptOptions::default()),
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("node: prefix", "node:path", types.join("path.d.ts")),
+        ("without node: prefix", "fs", types.join("fs.d.ts")),
+        ("submodule", "node:fs/promises", types.join("fs/promises.d.ts")),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{comment} {request}");
+    }
+
+    // Falls back to the builtin module when the declaration file does not exist.
+    let resolution = resolver.resolve(&f, "node:zlib");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::Builtin { resolved: "node:zlib".into(), is_runtime_module: true })
+    );
+
+    // Disabled
+    let resolver = Resolver::new(ResolveOptions {
+        builtin_modules: true,
+        typescript: Some(TypeScriptOptions {
+            node_builtin_types: false,
+            ..TypeScriptOptions::default()
+        }),
+        ..ResolveOptions::default()
+    });
+    let resolution = resolver.resolve(&f, "node:path");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::Builtin { resolved: "node:path".into(), is_runtime_module: true })
+    );
+}
+
+#[test]
+fn types_scopes() {
+    let f = super::fixture_root().join("misc/typescript");
+    let node_modules = f.join("node_modules");
+
+    let resolver = Resolver::new(ResolveOptions {
+        builtin_modules: true,
+        typescript: Some(TypeScriptOptions {
+            types_scopes: vec!["@types".into(), "@company-types".into()],
+            ..TypeScriptOptions::default()
+        }),
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("found in the first scope", "node:path", node_modules.join("@types/node/path.d.ts")),
+        ("falls through to the second scope", "node:zlib", node_modules.join("@company-types/node/zlib.d.ts")),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{comment} {request}");
+    }
+
+    // `@types` is not searched when not listed.
+    let resolver = Resolver::new(ResolveOptions {
+        builtin_modules: true,
+        typescript: Some(TypeScriptOptions {
+            types_scopes: vec!["@company-types".into()],
+            ..TypeScriptOptions::default()
+        }),
+        ..ResolveOptions::default()
+    });
+    let resolution = resolver.resolve(&f, "node:path");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::Builtin { resolved: "node:path".into(), is_runtime_module: true })
+    );
+}
+
+#[test]
+fn types_condition() {
+    let f = super::fixture_root().join("misc/typescript");
+    let dual = f.join("node_modules/dual");
+
+    let options =
+        ResolveOptions { condition_names: vec!["import".into()], ..ResolveOptions::default() };
+
+    // Runtime resolution is unaffected
+    let resolver = Resolver::new(options.clone());
+    let resolved_path = resolver.resolve(&f, "dual").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(dual.join("index.mjs")));
+
+    let resolver = Resolver::new(ResolveOptions {
+        typescript: Some(TypeScriptOptions::default()),
+        ..options.clone()
+    });
+    let resolved_path = resolver.resolve(&f, "dual").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(dual.join("index.d.ts")));
+    // Only the package root is affected
+    let resolved_path = resolver.resolve(&f, "dual/feature").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(dual.join("feature.mjs")));
+
+    // Disabled
+    let resolver = Resolver::new(ResolveOptions {
+        typescript: Some(TypeScriptOptions {
+            types_condition: false,
+            ..TypeScriptOptions::default()
+        }),
+        ..options
+    });
+    let resolved_path = resolver.resolve(&f, "dual").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(dual.join("index.mjs")));
+}
+
+#[test]
+fn sibling_declarations() {
+    let f = super::fixture_root().join("misc/typescript");
+    let p = f.join("node_modules/untyped-exports");
+
+    let typescript =
+        TypeScriptOptions { sibling_declarations: true, ..TypeScriptOptions::default() };
+
+    let resolver = Resolver::new(ResolveOptions {
+        condition_names: vec!["import".into()],
+        typescript: Some(typescript.clone()),
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("untyped-exports", p.join("index.d.ts")),
+        // Subpaths and patterns of "exports" as well.
+        ("untyped-exports/feature", p.join("feature.d.ts")),
+        ("untyped-exports/lib/util", p.join("lib/util.d.mts")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    // Kept when the declaration file does not exist.
+    let resolver = Resolver::new(ResolveOptions {
+        condition_names: vec!["require".into()],
+        typescript: Some(typescript),
+        ..ResolveOptions::default()
+    });
+    let resolved_path = resolver.resolve(&f, "untyped-exports").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(p.join("index.cjs")));
+
+    // Disabled by default
+    let resolver = Resolver::new(ResolveOptions {
+        condition_names: vec!["import".into()],
+        typescript: Some(TypeScriptOptions::default()),
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("untyped-exports", p.join("index.js")),
+        ("untyped-exports/feature", p.join("feature.js")),
+        ("untyped-exports/lib/util", p.join("lib/util.mjs")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+}
@@ -0,0 +1,46 @@
+//! Not part of enhanced_resolve's test suite
+
+use crate::{ResolveError, ResolveOptions, Resolver};
+
+#[test]
+fn workspace_protocol() {
+    let f = super::fixture_root().join("misc/workspaces");
+    let packages = f.join("packages");
+
+    let resolver = Resolver::new(ResolveOptions {
+        workspaces: vec![packages.join("foo"), packages.join("bar")],
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("workspace:foo", packages.join("foo/index.js")),
+        ("workspace:foo/sub", packages.join("foo/sub.js")),
+        ("workspace:@scope/bar", packages.join("bar/lib/index.js")),
+        ("workspace:@scope/bar/sub", packages.join("bar/lib/sub.js")),
+    ];
+
+    for (request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request}");
+    }
+
+    let resolution = resolver.resolve(&f, "workspace:missing");
+    assert_eq!(resolution, Err(ResolveError::NotFound("workspace:missing".into())));
+
+    let resolution = resolver.resolve(&f, "workspace:@scope/bar/missing");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::PackagePathNotExported(
+            "./missing".into(),
+            packages.join("bar/package.json")
+        ))
+    );
+}
+
+#[test]
+fn workspaces_not_configured() {
+    let f = super::fixture_root().join("misc/workspaces");
+    let resolution = Resolver::default().resolve(&f, "workspace:foo");
+    assert_eq!(resolution, Err(ResolveError::WorkspacesNotConfigured("workspace:foo".into())));
+}
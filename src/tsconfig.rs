@@ -1,4 +1,5 @@
 use std::{
+    cmp::Reverse,
     hash::BuildHasherDefault,
     path::{Path, PathBuf},
     sync::Arc,
@@ -89,6 +90,29 @@ pub trait TsConfig: Sized {
         }
     }
 
+    /// Prepares this tsconfig for resolving, after all configs are extended and merged.
+    ///
+    /// Expands template variables and precomputes the lookup order of wildcard
+    /// keys in `compilerOptions.paths`.
+    fn build(&mut self) {
+        self.expand_template_variables();
+        let compiler_options = self.compiler_options_mut();
+        if let Some(paths) = compiler_options.paths() {
+            let mut wildcard_keys = paths
+                .keys()
+                .enumerate()
+                .filter_map(|(index, key)| {
+                    key.find('*').map(|prefix_length| (prefix_length, index))
+                })
+                .collect::<Vec<_>>();
+            // Stable sort, keys with the same prefix length stay in insertion order.
+            wildcard_keys.sort_by_key(|(prefix_length, _)| Reverse(*prefix_length));
+            compiler_options.set_paths_wildcard_indices(Some(
+                wildcard_keys.into_iter().map(|(_, index)| index).collect(),
+            ));
+        }
+    }
+
     /// Inherits settings from the given tsconfig into `self`.
     fn extend_tsconfig(&mut self, tsconfig: &Self) {
         let compiler_options = self.compiler_options_mut();
@@ -153,15 +177,30 @@ pub trait TsConfig: Sized {
                 let mut longest_suffix_length = 0;
                 let mut best_key: Option<&String> = None;
 
-                for key in paths_map.keys() {
-                    if let Some((prefix, suffix)) = key.split_once('*') {
-                        if (best_key.is_none() || prefix.len() > longest_prefix_length)
-                            && specifier.starts_with(prefix)
-                            && specifier.ends_with(suffix)
-                        {
-                            longest_prefix_length = prefix.len();
-                            longest_suffix_length = suffix.len();
-                            best_key.replace(key);
+                if let Some(wildcard_indices) = compiler_options.paths_wildcard_indices() {
+                    // Sorted by descending prefix length, the first match is the best match.
+                    for (key, _) in wildcard_indices.iter().filter_map(|i| paths_map.get_index(*i))
+                    {
+                        if let Some((prefix, suffix)) = key.split_once('*') {
+                            if specifier.starts_with(prefix) && specifier.ends_with(suffix) {
+                                longest_prefix_length = prefix.len();
+                                longest_suffix_length = suffix.len();
+                                best_key.replace(key);
+                                break;
+                            }
+                        }
+                    }
+                } else {
+                    for key in paths_map.keys() {
+                        if let Some((prefix, suffix)) = key.split_once('*') {
+                            if (best_key.is_none() || prefix.len() > longest_prefix_length)
+                                && specifier.starts_with(prefix)
+                                && specifier.ends_with(suffix)
+                            {
+                                longest_prefix_length = prefix.len();
+                                longest_suffix_length = suffix.len();
+                                best_key.replace(key);
+                            }
                         }
                     }
                 }
@@ -232,6 +271,19 @@ pub trait CompilerOptions {
 
     /// Sets the path base.
     fn set_paths_base(&mut self, paths_base: PathBuf);
+
+    /// Indices of the wildcard keys in [CompilerOptions::paths], ordered by
+    /// descending prefix length.
+    ///
+    /// Returns `None` when they are not precomputed by [TsConfig::build], in
+    /// which case all keys are scanned on every lookup.
+    #[must_use]
+    fn paths_wildcard_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    /// Sets the precomputed indices of the wildcard keys.
+    fn set_paths_wildcard_indices(&mut self, _indices: Option<Vec<usize>>) {}
 }
 
 /// Project Reference.
@@ -110,6 +110,10 @@ pub struct CompilerOptionsSerde {
     /// The actual base from where path aliases are resolved.
     #[serde(skip)]
     paths_base: PathBuf,
+
+    /// Precomputed lookup order of the wildcard keys in `paths`.
+    #[serde(skip)]
+    paths_wildcard_indices: Option<Vec<usize>>,
 }
 
 impl CompilerOptions for CompilerOptionsSerde {
@@ -131,6 +135,7 @@ impl CompilerOptions for CompilerOptionsSerde {
 
     fn set_paths(&mut self, paths: Option<CompilerOptionsPathsMap>) {
         self.paths = paths;
+        self.paths_wildcard_indices = None;
     }
 
     fn paths_base(&self) -> &Path {
@@ -140,6 +145,14 @@ impl CompilerOptions for CompilerOptionsSerde {
     fn set_paths_base(&mut self, paths_base: PathBuf) {
         self.paths_base = paths_base;
     }
+
+    fn paths_wildcard_indices(&self) -> Option<&[usize]> {
+        self.paths_wildcard_indices.as_deref()
+    }
+
+    fn set_paths_wildcard_indices(&mut self, indices: Option<Vec<usize>>) {
+        self.paths_wildcard_indices = indices;
+    }
 }
 
 /// Value for the "extends" field.